@@ -1,9 +1,13 @@
+use crate::{
+	genesis_allocation::{self, Allocation},
+	rpc::dev_signer::dev_accounts,
+};
 use cumulus_primitives_core::ParaId;
 use diora_runtime::{AccountId, NimbusId, Signature};
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{ecdsa, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
 /// Specialized `ChainSpec` for the normal parachain runtime.
@@ -43,7 +47,10 @@ pub fn get_collator_keys_from_seed(seed: &str) -> NimbusId {
 	get_pair_from_seed::<NimbusId>(seed)
 }
 
-/// Helper function to generate an account ID from seed
+/// Helper function to generate an account ID from seed.
+///
+/// Accounts are Ethereum addresses, so the seed should be an ECDSA one: the account for
+/// `//Alice` is the address of `subkey inspect --scheme ecdsa //Alice`.
 pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId
 where
 	AccountPublic: From<<TPublic::Pair as Pair>::Public>,
@@ -51,14 +58,34 @@ where
 	AccountPublic::from(get_pair_from_seed::<TPublic>(seed)).into_account()
 }
 
-pub fn development_config() -> ChainSpec {
+pub fn development_config(allocations: Vec<Allocation>) -> Result<ChainSpec, String> {
 	// Give your base currency a unit name and decimal places
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "DIR".into());
 	properties.insert("tokenDecimals".into(), 18.into());
 	properties.insert("ss58Format".into(), 42.into());
 
-	ChainSpec::from_genesis(
+	let endowed_accounts: Vec<AccountId> = vec![
+		get_account_id_from_seed::<ecdsa::Public>("Alice"),
+		get_account_id_from_seed::<ecdsa::Public>("Bob"),
+		get_account_id_from_seed::<ecdsa::Public>("Charlie"),
+		get_account_id_from_seed::<ecdsa::Public>("Dave"),
+		get_account_id_from_seed::<ecdsa::Public>("Eve"),
+		get_account_id_from_seed::<ecdsa::Public>("Ferdie"),
+		get_account_id_from_seed::<ecdsa::Public>("Alice//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Bob//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Charlie//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Dave//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Eve//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Ferdie//stash"),
+	]
+	.into_iter()
+	// Accounts of `--enable-dev-signer`.
+	.chain(dev_accounts())
+	.collect();
+	genesis_allocation::validate(&allocations, &endowed_accounts)?;
+
+	Ok(ChainSpec::from_genesis(
 		// Name
 		"Development",
 		// ID
@@ -68,23 +95,11 @@ pub fn development_config() -> ChainSpec {
 			testnet_genesis(
 				// initial collators.
 				vec![(
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<ecdsa::Public>("Alice"),
 					get_collator_keys_from_seed("Alice"),
 				)],
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				endowed_accounts.clone(),
+				allocations.clone(),
 				1000.into(),
 			)
 		},
@@ -97,17 +112,33 @@ pub fn development_config() -> ChainSpec {
 			relay_chain: "rococo".into(), // You MUST set this to the correct network!
 			para_id: 2000,
 		},
-	)
+	))
 }
 
-pub fn local_testnet_config() -> ChainSpec {
+pub fn local_testnet_config(allocations: Vec<Allocation>) -> Result<ChainSpec, String> {
 	// Give your base currency a unit name and decimal places
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "DIR".into());
 	properties.insert("tokenDecimals".into(), 18.into());
 	properties.insert("ss58Format".into(), 42.into());
 
-	ChainSpec::from_genesis(
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<ecdsa::Public>("Alice"),
+		get_account_id_from_seed::<ecdsa::Public>("Bob"),
+		get_account_id_from_seed::<ecdsa::Public>("Charlie"),
+		get_account_id_from_seed::<ecdsa::Public>("Dave"),
+		get_account_id_from_seed::<ecdsa::Public>("Eve"),
+		get_account_id_from_seed::<ecdsa::Public>("Ferdie"),
+		get_account_id_from_seed::<ecdsa::Public>("Alice//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Bob//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Charlie//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Dave//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Eve//stash"),
+		get_account_id_from_seed::<ecdsa::Public>("Ferdie//stash"),
+	];
+	genesis_allocation::validate(&allocations, &endowed_accounts)?;
+
+	Ok(ChainSpec::from_genesis(
 		// Name
 		"Local Testnet",
 		// ID
@@ -118,28 +149,16 @@ pub fn local_testnet_config() -> ChainSpec {
 				// initial collators.
 				vec![
 					(
-						get_account_id_from_seed::<sr25519::Public>("Alice"),
+						get_account_id_from_seed::<ecdsa::Public>("Alice"),
 						get_collator_keys_from_seed("Alice"),
 					),
 					(
-						get_account_id_from_seed::<sr25519::Public>("Bob"),
+						get_account_id_from_seed::<ecdsa::Public>("Bob"),
 						get_collator_keys_from_seed("Bob"),
 					),
 				],
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				endowed_accounts.clone(),
+				allocations.clone(),
 				1000.into(),
 			)
 		},
@@ -158,14 +177,18 @@ pub fn local_testnet_config() -> ChainSpec {
 			relay_chain: "rococo".into(), // You MUST set this to the correct network!
 			para_id: 2000,
 		},
-	)
+	))
 }
 
 fn testnet_genesis(
 	authorities: Vec<(AccountId, NimbusId)>,
 	endowed_accounts: Vec<AccountId>,
+	allocations: Vec<Allocation>,
 	id: ParaId,
 ) -> diora_runtime::GenesisConfig {
+	// The authors start out as the members of both collectives, until governance elects others.
+	let members: Vec<AccountId> = authorities.iter().map(|(account, _)| account.clone()).collect();
+
 	diora_runtime::GenesisConfig {
 		system: diora_runtime::SystemConfig {
 			code: diora_runtime::WASM_BINARY
@@ -187,8 +210,25 @@ fn testnet_genesis(
 			mapping: authorities,
 		},
 		parachain_system: Default::default(),
-		evm: Default::default(),
+		vesting: diora_runtime::VestingConfig {
+			vesting: genesis_allocation::vesting_schedules(&allocations),
+		},
+		evm: diora_runtime::EvmConfig {
+			accounts: genesis_allocation::evm_accounts(allocations),
+		},
 		ethereum: Default::default(),
 		base_fee: Default::default(),
+		base_fee_params: Default::default(),
+		deployment_allowlist: Default::default(),
+		democracy: Default::default(),
+		treasury: Default::default(),
+		council: diora_runtime::CouncilConfig {
+			phantom: Default::default(),
+			members: members.clone(),
+		},
+		technical_committee: diora_runtime::TechnicalCommitteeConfig {
+			phantom: Default::default(),
+			members,
+		},
 	}
 }
@@ -35,12 +35,23 @@ pub enum Subcommand {
 	Revert(sc_cli::RevertCmd),
 
 	/// Run Instant Seal
-	RunInstantSeal(sc_cli::RunCmd),
+	RunInstantSeal(RunInstantSealCmd),
 
 	/// Sub-commands concerned with benchmarking.
 	/// The pallet benchmarking moved to the `pallet` sub-command.
 	#[clap(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some testing command against a specified runtime state, e.g.
+	/// `try-runtime on-runtime-upgrade snap --snapshot-path <FILE>` to run the
+	/// runtime upgrade hooks against a locally stored state snapshot.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some testing command against a specified runtime state.
+	/// Only available when the node is built with `--features try-runtime`.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
 
 /// Command for exporting the genesis state of the parachain
@@ -81,6 +92,87 @@ pub struct ExportGenesisWasmCommand {
 	pub chain: Option<String>,
 }
 
+/// Command for running an instant seal node.
+#[derive(Debug, Parser)]
+pub struct RunInstantSealCmd {
+	#[clap(flatten)]
+	pub base: sc_cli::RunCmd,
+
+	#[clap(flatten)]
+	pub eth: EthConfiguration,
+
+	#[clap(flatten)]
+	pub tracing: TracingConfig,
+}
+
+/// Format of the Ethereum RPC responses.
+#[derive(Clone, Copy, Debug, clap::ArgEnum)]
+pub enum EthRpcFormat {
+	Geth,
+	Parity,
+}
+
+/// Limits of the Ethereum RPCs.
+#[derive(Clone, Debug, Parser)]
+pub struct EthConfiguration {
+	/// Maximum number of blocks `eth_feeHistory` can return.
+	#[clap(long, default_value = "2048")]
+	pub fee_history_limit: u64,
+
+	/// Maximum number of logs `eth_getLogs` can return.
+	#[clap(long, default_value = "10000")]
+	pub max_past_logs: u32,
+
+	/// Maximum number of blocks `eth_getLogs` can scan.
+	#[clap(long, default_value = "1024")]
+	pub max_block_range: u32,
+
+	/// Maximum number of filters installed with `eth_newFilter` and the like.
+	#[clap(long, default_value = "500")]
+	pub max_stored_filters: usize,
+
+	/// Size of the cache of Ethereum blocks, in number of blocks.
+	#[clap(long, default_value = "50")]
+	pub eth_log_block_cache: usize,
+
+	/// Size of the cache of Ethereum transaction statuses, in number of blocks.
+	#[clap(long, default_value = "50")]
+	pub eth_statuses_cache: usize,
+
+	/// Format of the RPC responses, notably of errors.
+	#[clap(long, arg_enum, default_value = "geth")]
+	pub eth_rpc_format: EthRpcFormat,
+
+	/// Let `eth_sendTransaction`, `eth_sign` and `personal_sign` sign with the well-known
+	/// development keys.
+	///
+	/// Only allowed on development chains.
+	#[clap(long)]
+	pub enable_dev_signer: bool,
+}
+
+/// Options of the EVM tracing RPCs.
+#[derive(Clone, Debug, Parser)]
+pub struct TracingConfig {
+	/// Enable the `debug_traceTransaction`, `debug_traceBlockByNumber` and `trace_filter` RPCs.
+	///
+	/// Only accepted by nodes built with the `evm-tracing` feature, the only ones producing traces.
+	#[clap(long)]
+	pub enable_evm_tracing: bool,
+
+	/// Maximum number of traces computed concurrently.
+	#[clap(long, default_value = "10")]
+	pub tracing_max_permits: usize,
+
+	/// Maximum number of traces returned by `trace_filter`.
+	#[clap(long, default_value = "500")]
+	pub tracing_max_count: u32,
+
+	/// Maximum number of blocks `trace_filter` can trace.
+	#[clap(long, default_value = "100")]
+	pub tracing_max_block_range: u32,
+}
+
 #[derive(Debug, Parser)]
 #[clap(propagate_version = true)]
 #[clap(args_conflicts_with_subcommands = true)]
@@ -92,6 +184,12 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub run: cumulus_client_cli::RunCmd,
 
+	#[clap(flatten)]
+	pub eth: EthConfiguration,
+
+	#[clap(flatten)]
+	pub tracing: TracingConfig,
+
 	/// Relaychain arguments
 	#[clap(raw = true)]
 	pub relay_chain_args: Vec<String>,
@@ -1,5 +1,6 @@
 use crate::{
 	chain_spec,
+	genesis_allocation,
 	cli::{Cli, RelayChainCli, Subcommand},
 	service::{new_partial, TemplateRuntimeExecutor},
 };
@@ -21,9 +22,10 @@ use std::{io::Write, net::SocketAddr};
 
 fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
 	Ok(match id {
-		"rococo" => Box::new(chain_spec::development_config()),
-		"template-rococo" => Box::new(chain_spec::local_testnet_config()),
-		"" | "local" => Box::new(chain_spec::local_testnet_config()),
+		"rococo" => Box::new(chain_spec::development_config(genesis_allocation::load_from_env()?)?),
+		"template-rococo" | "" | "local" => {
+			Box::new(chain_spec::local_testnet_config(genesis_allocation::load_from_env()?)?)
+		}
 		path => Box::new(chain_spec::ChainSpec::from_json_file(
 			std::path::PathBuf::from(path),
 		)?),
@@ -259,15 +261,39 @@ pub fn run() -> Result<()> {
 				BenchmarkCmd::Machine(cmd) => runner.sync_run(|config| cmd.run(&config)),
 			}
 		}
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::TryRuntime(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+
+			// The task manager is only needed to keep the prometheus registry alive for the
+			// duration of the command, `try-runtime` doesn't spawn any tasks of its own.
+			runner.async_run(|config| {
+				let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
+				let task_manager =
+					sc_service::TaskManager::new(config.tokio_handle.clone(), registry)
+						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+
+				Ok((cmd.run::<Block, TemplateRuntimeExecutor>(config), task_manager))
+			})
+		}
+		#[cfg(not(feature = "try-runtime"))]
+		Some(Subcommand::TryRuntime) => Err("TryRuntime wasn't enabled when building the node. \
+			You can enable it with `--features try-runtime`."
+			.into()),
 		Some(Subcommand::RunInstantSeal(run_cmd)) => {
-			let runner = cli.create_runner(run_cmd)?;
+			let runner = cli.create_runner(&run_cmd.base)?;
+			let eth = run_cmd.eth.clone();
+			let tracing = run_cmd.tracing.clone();
 			runner.run_node_until_exit(|config| async move {
-				crate::service::start_instant_seal_node(config).map_err(sc_cli::Error::Service)
+				crate::service::start_instant_seal_node(config, eth, tracing)
+					.map_err(sc_cli::Error::Service)
 			})
 		}
 		None => {
 			let runner = cli.create_runner(&cli.run.normalize())?;
 			let collator_options = cli.run.collator_options();
+			let eth = cli.eth.clone();
+			let tracing = cli.tracing.clone();
 
 			runner.run_node_until_exit(|config| async move {
 				let para_id = chain_spec::Extensions::try_get(&*config.chain_spec)
@@ -309,8 +335,15 @@ pub fn run() -> Result<()> {
 					}
 				);
 
-				crate::service::start_parachain_node(config, polkadot_config, collator_options, id)
-					.await
+				crate::service::start_parachain_node(
+					config,
+					polkadot_config,
+					collator_options,
+					id,
+					eth,
+					tracing,
+				)
+				.await
 					.map(|r| r.0)
 					.map_err(Into::into)
 			})
@@ -0,0 +1,343 @@
+//! EVM accounts allocated at genesis, loaded from a JSON or CSV file.
+//!
+//! The file is given through the `DIORA_GENESIS_ALLOCATION` environment variable when building
+//! the `rococo` and `local` chain specs, e.g.
+//! `DIORA_GENESIS_ALLOCATION=alloc.json diora build-spec --chain local`. The resulting chain spec
+//! JSON then holds the accounts in its `evm` genesis.
+//!
+//! A JSON file lists accounts with an optional balance, nonce, code and storage, balances and
+//! nonces being decimal or `0x`-prefixed hexadecimal strings:
+//!
+//! ```json
+//! [
+//!   { "address": "0xcA11bde05977b3631167028862bE2a173976CA11", "code": "0x6080...", "storage": {} },
+//!   { "address": "0x1111111111111111111111111111111111111111", "balance": "1000000000000000000" },
+//!   {
+//!     "address": "0x2222222222222222222222222222222222222222",
+//!     "balance": "1000000000000000000",
+//!     "vesting": { "start": 0, "length": 2628000, "liquid": "100000000000000000" }
+//!   }
+//! ]
+//! ```
+//!
+//! A `vesting` entry locks the balance but for its `liquid` part, and releases it linearly over
+//! `length` blocks from block `start`.
+//!
+//! Allocated addresses must not be endowed by the chain spec already, like the development
+//! accounts of `--enable-dev-signer` (Alith, Baltathar, ...) in the `rococo` chain spec.
+//!
+//! A CSV file only holds balances, as `address,balance` lines with an optional header.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::Path,
+};
+
+use diora_runtime::{AccountId, Balance, BlockNumber, EXISTENTIAL_DEPOSIT};
+use pallet_evm::GenesisAccount;
+use serde::{Deserialize, Deserializer};
+use sp_core::{Bytes, H160, H256, U256};
+
+/// Environment variable holding the path of the allocation file.
+pub const GENESIS_ALLOCATION_ENV: &str = "DIORA_GENESIS_ALLOCATION";
+
+/// An EVM account created at genesis.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Allocation {
+	pub address: H160,
+	/// Balance of the mapped Substrate account, in wei.
+	#[serde(default, deserialize_with = "deserialize_u256")]
+	pub balance: U256,
+	#[serde(default, deserialize_with = "deserialize_u256")]
+	pub nonce: U256,
+	/// Runtime bytecode of a contract.
+	#[serde(default)]
+	pub code: Bytes,
+	#[serde(default)]
+	pub storage: BTreeMap<H256, H256>,
+	#[serde(default)]
+	pub vesting: Option<Vesting>,
+}
+
+/// Lockup of the balance of an allocation, released linearly.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Vesting {
+	/// Block the release starts at.
+	pub start: BlockNumber,
+	/// Number of blocks the release lasts.
+	pub length: BlockNumber,
+	/// Part of the balance that is never locked.
+	#[serde(default, deserialize_with = "deserialize_u256")]
+	pub liquid: U256,
+}
+
+impl Allocation {
+	/// An account only holding `balance`.
+	pub fn with_balance(address: H160, balance: U256) -> Self {
+		Self {
+			address,
+			balance,
+			nonce: U256::zero(),
+			code: Bytes(Vec::new()),
+			storage: BTreeMap::new(),
+			vesting: None,
+		}
+	}
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+	let value = value.trim();
+	match value.strip_prefix("0x") {
+		Some(hex) => U256::from_str_radix(hex, 16).map_err(|e| format!("{}: {:?}", value, e)),
+		None => U256::from_dec_str(value).map_err(|e| format!("{}: {:?}", value, e)),
+	}
+}
+
+fn deserialize_u256<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Number {
+		Int(u64),
+		Str(String),
+	}
+
+	match Number::deserialize(deserializer)? {
+		Number::Int(value) => Ok(value.into()),
+		Number::Str(value) => parse_u256(&value).map_err(serde::de::Error::custom),
+	}
+}
+
+fn parse_json(content: &str) -> Result<Vec<Allocation>, String> {
+	serde_json::from_str(content).map_err(|e| format!("invalid genesis allocation: {}", e))
+}
+
+fn parse_csv(content: &str) -> Result<Vec<Allocation>, String> {
+	content
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty())
+		.filter(|(index, line)| !(*index == 0 && line.trim_start().starts_with("address")))
+		.map(|(index, line)| {
+			let error = |e: String| format!("invalid genesis allocation, line {}: {}", index + 1, e);
+			let (address, balance) =
+				line.split_once(',').ok_or_else(|| error("expected `address,balance`".into()))?;
+			let address = address
+				.trim()
+				.parse::<H160>()
+				.map_err(|e| error(format!("{}: {:?}", address.trim(), e)))?;
+			Ok(Allocation::with_balance(address, parse_u256(balance).map_err(error)?))
+		})
+		.collect()
+}
+
+/// Reads the allocations of a `.json` or `.csv` file.
+pub fn load(path: &Path) -> Result<Vec<Allocation>, String> {
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| format!("cannot read genesis allocation {}: {}", path.display(), e))?;
+	match path.extension().and_then(|extension| extension.to_str()) {
+		Some("json") => parse_json(&content),
+		Some("csv") => parse_csv(&content),
+		_ => Err(format!("genesis allocation {} must be a .json or .csv file", path.display())),
+	}
+}
+
+/// Reads the allocation file given in [`GENESIS_ALLOCATION_ENV`], if any.
+pub fn load_from_env() -> Result<Vec<Allocation>, String> {
+	match std::env::var_os(GENESIS_ALLOCATION_ENV) {
+		Some(path) => load(Path::new(&path)),
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Checks that the allocations give every mapped Substrate account exactly its EVM balance, and
+/// that their vesting schedules are valid.
+///
+/// EVM genesis balances are deposited on top of the `balances` genesis into the account the
+/// address maps to, and deposits below the existential deposit are dropped. An allocation must
+/// hence not target an account already endowed in `endowed_accounts`, nor be too small to exist.
+///
+/// `pallet_vesting` locks the whole free balance of an account but `liquid` at genesis, once the
+/// EVM genesis deposited the allocations. A schedule on an endowed account would hence also lock
+/// its `balances` endowment, which is another reason endowed accounts are rejected. It also
+/// panics on a schedule releasing nothing per block, so the locked balance must be at least the
+/// length of the schedule.
+pub fn validate(allocations: &[Allocation], endowed_accounts: &[AccountId]) -> Result<(), String> {
+	let mut seen = BTreeSet::new();
+	for allocation in allocations {
+		let address = allocation.address;
+		if !seen.insert(address) {
+			return Err(format!("{:?} is allocated twice", address));
+		}
+		if endowed_accounts.contains(&AccountId::from(address)) {
+			return Err(match allocation.vesting {
+				Some(_) => format!("vesting of {:?} would lock its balances endowment", address),
+				None => format!("{:?} is also endowed by the balances genesis", address),
+			});
+		}
+		if allocation.balance > U256::from(Balance::MAX) {
+			return Err(format!("balance of {:?} overflows", address));
+		}
+		if !allocation.balance.is_zero() && allocation.balance < U256::from(EXISTENTIAL_DEPOSIT) {
+			return Err(format!("balance of {:?} is below the existential deposit", address));
+		}
+		if let Some(vesting) = &allocation.vesting {
+			let locked = allocation.balance.saturating_sub(vesting.liquid);
+			if vesting.length == 0 || locked < U256::from(vesting.length) {
+				return Err(format!("vesting schedule of {:?} releases nothing", address));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// The `vesting` genesis schedules of `allocations`, as `(account, start, length, liquid)`.
+pub fn vesting_schedules(
+	allocations: &[Allocation],
+) -> Vec<(AccountId, BlockNumber, BlockNumber, Balance)> {
+	allocations
+		.iter()
+		.filter_map(|allocation| {
+			let vesting = allocation.vesting.as_ref()?;
+			Some((
+				allocation.address.into(),
+				vesting.start,
+				vesting.length,
+				vesting.liquid.low_u128(),
+			))
+		})
+		.collect()
+}
+
+/// The `evm` genesis accounts of `allocations`.
+pub fn evm_accounts(allocations: Vec<Allocation>) -> BTreeMap<H160, GenesisAccount> {
+	allocations
+		.into_iter()
+		.map(|allocation| {
+			(
+				allocation.address,
+				GenesisAccount {
+					nonce: allocation.nonce,
+					balance: allocation.balance,
+					storage: allocation.storage,
+					code: allocation.code.0,
+				},
+			)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+
+	const MULTICALL: H160 = H160(hex!("cA11bde05977b3631167028862bE2a173976CA11"));
+	const ALITH: H160 = H160(hex!("f24FF3a9CF04c71Dbc94D0b566f7A27B94566cac"));
+
+	#[test]
+	fn json_allocations_are_parsed() {
+		let allocations = parse_json(
+			r#"[
+				{ "address": "0xcA11bde05977b3631167028862bE2a173976CA11", "code": "0x6080", "storage": {
+					"0x0000000000000000000000000000000000000000000000000000000000000000":
+					"0x0000000000000000000000000000000000000000000000000000000000000001"
+				} },
+				{ "address": "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac", "balance": "0x3e8", "nonce": 1 }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(allocations[0].address, MULTICALL);
+		assert_eq!(allocations[0].code, Bytes(vec![0x60, 0x80]));
+		assert_eq!(allocations[0].storage[&H256::zero()], H256::from_low_u64_be(1));
+		assert_eq!(allocations[1].balance, U256::from(1000));
+		assert_eq!(allocations[1].nonce, U256::one());
+	}
+
+	#[test]
+	fn vesting_allocations_are_parsed() {
+		let allocations = parse_json(
+			r#"[
+				{ "address": "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac", "balance": "1000",
+				  "vesting": { "start": 10, "length": 100, "liquid": "0x64" } }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			allocations[0].vesting,
+			Some(Vesting { start: 10, length: 100, liquid: U256::from(100) })
+		);
+		assert_eq!(
+			vesting_schedules(&allocations),
+			vec![(AccountId::from(ALITH), 10, 100, 100)]
+		);
+	}
+
+	#[test]
+	fn csv_allocations_are_parsed() {
+		let allocations = parse_csv(
+			"address,balance\n\
+			 0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac, 1000000000000000000\n\
+			 \n\
+			 0xcA11bde05977b3631167028862bE2a173976CA11,0x10\n",
+		)
+		.unwrap();
+
+		assert_eq!(
+			allocations,
+			vec![
+				Allocation::with_balance(ALITH, U256::exp10(18)),
+				Allocation::with_balance(MULTICALL, U256::from(16)),
+			]
+		);
+		assert!(parse_csv("0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac").is_err());
+	}
+
+	#[test]
+	fn allocations_must_agree_with_balances_genesis() {
+		let alith = Allocation::with_balance(ALITH, U256::from(EXISTENTIAL_DEPOSIT));
+		let contract = Allocation {
+			code: Bytes(vec![0x00]),
+			..Allocation::with_balance(MULTICALL, U256::zero())
+		};
+
+		assert!(validate(&[alith.clone(), contract.clone()], &[]).is_ok());
+		assert!(validate(&[alith.clone(), alith.clone()], &[]).is_err());
+		assert!(validate(&[alith.clone()], &[AccountId::from(ALITH)]).is_err());
+		assert!(validate(
+			&[Allocation::with_balance(ALITH, U256::from(EXISTENTIAL_DEPOSIT - 1))],
+			&[]
+		)
+		.is_err());
+		assert!(validate(&[Allocation::with_balance(ALITH, U256::MAX)], &[]).is_err());
+	}
+
+	#[test]
+	fn vesting_must_release_something_per_block() {
+		let vesting = |length, liquid: u128| Allocation {
+			vesting: Some(Vesting { start: 0, length, liquid: liquid.into() }),
+			..Allocation::with_balance(ALITH, U256::from(1_000 * EXISTENTIAL_DEPOSIT))
+		};
+
+		assert!(validate(&[vesting(100, 0)], &[]).is_ok());
+		assert!(validate(&[vesting(0, 0)], &[]).is_err());
+		assert!(validate(&[vesting(100, 1_000 * EXISTENTIAL_DEPOSIT)], &[]).is_err());
+	}
+
+	#[test]
+	fn vesting_must_not_lock_an_endowment() {
+		let allocation = Allocation {
+			vesting: Some(Vesting { start: 0, length: 100, liquid: U256::zero() }),
+			..Allocation::with_balance(ALITH, U256::from(1_000 * EXISTENTIAL_DEPOSIT))
+		};
+
+		assert_eq!(
+			validate(&[allocation], &[AccountId::from(ALITH)]),
+			Err(format!("vesting of {:?} would lock its balances endowment", ALITH))
+		);
+	}
+}
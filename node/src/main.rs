@@ -7,6 +7,7 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod genesis_allocation;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
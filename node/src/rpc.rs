@@ -5,6 +5,11 @@
 
 #![warn(missing_docs)]
 
+pub mod dev_signer;
+pub mod eth_filter;
+pub mod tracing;
+pub mod txpool;
+
 use std::sync::Arc;
 
 pub use sc_rpc_api::DenyUnsafe;
@@ -25,6 +30,7 @@ use jsonrpc_pubsub::manager::SubscriptionManager;
 use sc_client_api::{
 	backend::{AuxStore, Backend, StateBackend, StorageProvider},
 	client::BlockchainEvents,
+	BlockBackend,
 };
 use sc_network::NetworkService;
 use sc_rpc::SubscriptionTaskExecutor;
@@ -32,6 +38,8 @@ use sc_transaction_pool::{ChainApi, Pool};
 use sp_runtime::traits::BlakeTwo256;
 use std::collections::BTreeMap;
 
+use crate::cli::{EthConfiguration, EthRpcFormat};
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
@@ -53,16 +61,16 @@ pub struct FullDeps<C, P, A: ChainApi> {
 	pub filter_pool: Option<FilterPool>,
 	/// Backend.
 	pub backend: Arc<fc_db::Backend<Block>>,
-	/// Maximum number of logs in a query.
-	pub max_past_logs: u32,
-	/// Maximum fee history cache size.
-	pub fee_history_limit: u64,
+	/// Limits of the Ethereum RPCs.
+	pub eth: EthConfiguration,
 	/// Fee history cache.
 	pub fee_history_cache: FeeHistoryCache,
 	/// Ethereum data access overrides.
 	pub overrides: Arc<OverrideHandle<Block>>,
 	/// Cache for Ethereum block data.
 	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// Workers of the EVM tracing RPCs, if enabled.
+	pub tracing_workers: Option<tracing::TracingWorkers>,
 }
 
 pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
@@ -108,7 +116,7 @@ pub fn create_full<C, P, BE, A>(
 		BE: Backend<Block> + 'static,
 		BE::State: StateBackend<BlakeTwo256>,
 		C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
-		C: BlockchainEvents<Block>,
+		C: BlockchainEvents<Block> + BlockBackend<Block>,
 		C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
 		C: Send + Sync + 'static,
 		C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
@@ -116,11 +124,13 @@ pub fn create_full<C, P, BE, A>(
 		C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 		C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 		C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+		C::Api: evm_tracer::DebugRuntimeApi<Block>,
 		P: TransactionPool<Block = Block> + 'static,
 		A: ChainApi<Block = Block> + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
+	use txpool::{TxPool, TxPoolApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 
@@ -133,11 +143,11 @@ pub fn create_full<C, P, BE, A>(
 		network,
 		filter_pool,
 		backend,
-		max_past_logs,
-		fee_history_limit,
+		eth,
 		fee_history_cache,
 		overrides,
 		block_data_cache,
+		tracing_workers,
 	} = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(
@@ -153,7 +163,7 @@ pub fn create_full<C, P, BE, A>(
 	// eth api
 	{
 		use fc_rpc::{
-			Eth, EthApi, EthDevSigner, EthFilter, EthFilterApi, EthPubSub, EthPubSubApi, EthSigner,
+			Eth, EthApi, EthFilter, EthFilterApi, EthPubSub, EthPubSubApi, EthSigner,
 			HexEncodedIdProvider, Net, NetApi, Web3, Web3Api,
 		};
 		enum Never {}
@@ -166,30 +176,51 @@ pub fn create_full<C, P, BE, A>(
 			}
 		}
 		let convert_transaction: Option<Never> = None;
-		io.extend_with(EthApi::to_delegate(Eth::new(
-			client.clone(),
-			pool.clone(),
-			graph,
-			convert_transaction,
-			network.clone(),
-			Vec::new(),
-			overrides.clone(),
-			backend.clone(),
-			is_authority,
-			block_data_cache.clone(),
-			fc_rpc::format::Geth,
-			fee_history_cache,
-			fee_history_limit,
-		)));
+		let mut signers = Vec::new();
+		if eth.enable_dev_signer {
+			signers.push(Box::new(dev_signer::DevSigner::default()) as Box<dyn EthSigner>);
+		}
+		macro_rules! eth {
+			($format:expr) => {
+				EthApi::to_delegate(Eth::new(
+					client.clone(),
+					pool.clone(),
+					graph.clone(),
+					convert_transaction,
+					network.clone(),
+					signers,
+					overrides.clone(),
+					backend.clone(),
+					is_authority,
+					block_data_cache.clone(),
+					$format,
+					fee_history_cache,
+					eth.fee_history_limit,
+				))
+			};
+		}
+		io.extend_with(match eth.eth_rpc_format {
+			EthRpcFormat::Geth => eth!(fc_rpc::format::Geth),
+			EthRpcFormat::Parity => eth!(fc_rpc::format::Parity),
+		});
+		if eth.enable_dev_signer {
+			// Replaces the `eth_sign` of `EthApi`.
+			use dev_signer::DevSignApi;
+			io.extend_with(DevSignApi::to_delegate(dev_signer::DevSigner::default()));
+		}
 
 		if let Some(filter_pool) = filter_pool {
-			io.extend_with(EthFilterApi::to_delegate(EthFilter::new(
+			io.extend_with(EthFilterApi::to_delegate(eth_filter::BlockRangeLimit::new(
+				EthFilter::new(
+					client.clone(),
+					backend.clone(),
+					filter_pool,
+					eth.max_stored_filters,
+					eth.max_past_logs,
+					block_data_cache,
+				),
 				client.clone(),
-				backend,
-				filter_pool,
-				500_usize, // max stored filters
-				max_past_logs,
-				block_data_cache,
+				eth.max_block_range,
 			)));
 		}
 
@@ -202,6 +233,8 @@ pub fn create_full<C, P, BE, A>(
 
 		io.extend_with(Web3Api::to_delegate(Web3::new(client.clone())));
 
+		io.extend_with(TxPoolApi::to_delegate(TxPool::new(client.clone(), graph)));
+
 		io.extend_with(EthPubSubApi::to_delegate(EthPubSub::new(
 			pool,
 			client.clone(),
@@ -210,9 +243,17 @@ pub fn create_full<C, P, BE, A>(
 				HexEncodedIdProvider::default(),
 				Arc::new(subscription_task_executor),
 			),
-			overrides,
+			overrides.clone(),
 		)));
 	}
 
+	if let Some(workers) = tracing_workers {
+		use tracing::{DebugApi, TraceApi, Tracing};
+
+		let tracing = Tracing::new(client, backend.clone(), overrides, workers);
+		io.extend_with(DebugApi::to_delegate(tracing.clone()));
+		io.extend_with(TraceApi::to_delegate(tracing));
+	}
+
 	io
 }
@@ -0,0 +1,283 @@
+//! Signer of `eth_sendTransaction`, `eth_sign` and `personal_sign` holding the well-known
+//! development keys.
+//!
+//! Frontier's `EthDevSigner` holds a single hard-coded key, while tools like Truffle and Remix
+//! expect the usual set of development accounts (Alith, Baltathar, ...) to be unlocked. Their
+//! keys are public, so this must never be enabled outside of development chains.
+//!
+//! Frontier answers `eth_sign` with an error whatever its signers, so [`DevSignApi`] serves it
+//! in place of the `EthApi` one, and must be registered after it.
+
+use diora_runtime::{AccountId, Signature};
+use ethereum::{
+	EIP1559Transaction, EIP2930Transaction, LegacyTransaction, TransactionSignature,
+	TransactionV2,
+};
+use fc_rpc::{internal_err, EthSigner};
+use fc_rpc_core::types::{Bytes, TransactionMessage};
+use hex_literal::hex;
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+use sp_core::{ecdsa, keccak_256, Pair, H160, H256};
+use sp_runtime::traits::{IdentifyAccount, Verify};
+
+/// Private keys of the development accounts, in order: Alith, Baltathar, Charleth, Dorothy,
+/// Ethan and Faith.
+pub const DEV_KEYS: [[u8; 32]; 6] = [
+	hex!("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133"),
+	hex!("8075991ce870b93a8870eca0c0f91913d12f47948ca0fd25b49c6fa7cdbeee8b"),
+	hex!("0b6e18cafb6ed99687ec547bd28139cafdd2bffe70e6b688025de6b445aa5c5b"),
+	hex!("39539ab1876910bbf3a223d84a29e28f1cb4e2e456503e7e91ed39b2e7223d68"),
+	hex!("7dce9bc8babb68fec1409be38c8e1a52650206a7ed90ff956ae8a6d15eeaaef4"),
+	hex!("b9d2ea9a615f3165812e8d44de0d24da9bbd164b65c4f0573e1ce2c8dbd9c8df"),
+];
+
+fn account_of(pair: &ecdsa::Pair) -> AccountId {
+	<Signature as Verify>::Signer::from(pair.public()).into_account()
+}
+
+/// Accounts of [`DEV_KEYS`].
+pub fn dev_accounts() -> Vec<AccountId> {
+	DEV_KEYS
+		.iter()
+		.map(|key| account_of(&ecdsa::Pair::from_seed(key)))
+		.collect()
+}
+
+/// [`EthSigner`] holding [`DEV_KEYS`].
+pub struct DevSigner {
+	pairs: Vec<(H160, ecdsa::Pair)>,
+}
+
+impl Default for DevSigner {
+	fn default() -> Self {
+		Self {
+			pairs: DEV_KEYS
+				.iter()
+				.map(|key| {
+					let pair = ecdsa::Pair::from_seed(key);
+					(account_of(&pair).into(), pair)
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Signs `hash`, returning `r`, `s` and the recovery id.
+fn sign(pair: &ecdsa::Pair, hash: H256) -> (H256, H256, u8) {
+	let signature = pair.sign_prehashed(&hash.0);
+	(
+		H256::from_slice(&signature.0[0..32]),
+		H256::from_slice(&signature.0[32..64]),
+		signature.0[64],
+	)
+}
+
+impl DevSigner {
+	fn pair(&self, address: &H160) -> RpcResult<&ecdsa::Pair> {
+		self.pairs
+			.iter()
+			.find(|(account, _)| account == address)
+			.map(|(_, pair)| pair)
+			.ok_or_else(|| internal_err("no signer available"))
+	}
+
+	/// Signs `data` as an EIP-191 personal message, returning `r`, `s` and `v`.
+	fn sign_message(&self, address: &H160, data: &[u8]) -> RpcResult<Bytes> {
+		let mut message = format!("\x19Ethereum Signed Message:\n{}", data.len()).into_bytes();
+		message.extend_from_slice(data);
+		let (r, s, recovery_id) = sign(self.pair(address)?, keccak_256(&message).into());
+
+		let mut signature = Vec::with_capacity(65);
+		signature.extend_from_slice(r.as_bytes());
+		signature.extend_from_slice(s.as_bytes());
+		signature.push(27 + recovery_id);
+		Ok(Bytes(signature))
+	}
+}
+
+/// Message signing of the development accounts.
+#[rpc(server)]
+pub trait DevSignApi {
+	/// Signs `data` as a personal message of `address`.
+	#[rpc(name = "eth_sign")]
+	fn eth_sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes>;
+
+	/// Signs `data` as a personal message of `address`, ignoring the password.
+	#[rpc(name = "personal_sign")]
+	fn personal_sign(
+		&self,
+		data: Bytes,
+		address: H160,
+		password: Option<String>,
+	) -> RpcResult<Bytes>;
+}
+
+impl DevSignApi for DevSigner {
+	fn eth_sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes> {
+		self.sign_message(&address, &data.0)
+	}
+
+	fn personal_sign(
+		&self,
+		data: Bytes,
+		address: H160,
+		_password: Option<String>,
+	) -> RpcResult<Bytes> {
+		self.sign_message(&address, &data.0)
+	}
+}
+
+impl EthSigner for DevSigner {
+	fn accounts(&self) -> Vec<H160> {
+		self.pairs.iter().map(|(address, _)| *address).collect()
+	}
+
+	fn sign(&self, message: TransactionMessage, address: &H160) -> RpcResult<TransactionV2> {
+		let pair = self.pair(address)?;
+
+		Ok(match message {
+			TransactionMessage::Legacy(m) => {
+				let (r, s, recovery_id) = sign(pair, m.hash());
+				let v = match m.chain_id {
+					Some(chain_id) => chain_id * 2 + 35 + recovery_id as u64,
+					None => 27 + recovery_id as u64,
+				};
+				TransactionV2::Legacy(LegacyTransaction {
+					nonce: m.nonce,
+					gas_price: m.gas_price,
+					gas_limit: m.gas_limit,
+					action: m.action,
+					value: m.value,
+					input: m.input,
+					signature: TransactionSignature::new(v, r, s)
+						.ok_or_else(|| internal_err("signer generated invalid signature"))?,
+				})
+			}
+			TransactionMessage::EIP2930(m) => {
+				let (r, s, recovery_id) = sign(pair, m.hash());
+				TransactionV2::EIP2930(EIP2930Transaction {
+					chain_id: m.chain_id,
+					nonce: m.nonce,
+					gas_price: m.gas_price,
+					gas_limit: m.gas_limit,
+					action: m.action,
+					value: m.value,
+					input: m.input,
+					access_list: m.access_list,
+					odd_y_parity: recovery_id != 0,
+					r,
+					s,
+				})
+			}
+			TransactionMessage::EIP1559(m) => {
+				let (r, s, recovery_id) = sign(pair, m.hash());
+				TransactionV2::EIP1559(EIP1559Transaction {
+					chain_id: m.chain_id,
+					nonce: m.nonce,
+					max_priority_fee_per_gas: m.max_priority_fee_per_gas,
+					max_fee_per_gas: m.max_fee_per_gas,
+					gas_limit: m.gas_limit,
+					action: m.action,
+					value: m.value,
+					input: m.input,
+					access_list: m.access_list,
+					odd_y_parity: recovery_id != 0,
+					r,
+					s,
+				})
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{EIP1559TransactionMessage, LegacyTransactionMessage, TransactionAction};
+
+	#[test]
+	fn dev_accounts_are_the_well_known_addresses() {
+		let accounts: Vec<H160> = dev_accounts().into_iter().map(Into::into).collect();
+
+		assert_eq!(
+			accounts,
+			vec![
+				H160(hex!("f24FF3a9CF04c71Dbc94D0b566f7A27B94566cac")),
+				H160(hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0")),
+				H160(hex!("798d4Ba9baf0064Ec19eB4F0a1a45785ae9D6DFc")),
+				H160(hex!("773539d4Ac0e786233D90A233654ccEE26a613D9")),
+				H160(hex!("Ff64d3F6efE2317EE2807d223a0Bdc4c0c49dfDB")),
+				H160(hex!("C0F0f4ab324C46e55D02D0033343B4Be8A55532d")),
+			]
+		);
+	}
+
+	#[test]
+	fn signed_transactions_recover_to_the_signing_account() {
+		let signer = DevSigner::default();
+		let baltathar = signer.accounts()[1];
+		let legacy = TransactionMessage::Legacy(LegacyTransactionMessage {
+			nonce: 0u64.into(),
+			gas_price: 1_000_000_000u64.into(),
+			gas_limit: 21_000u64.into(),
+			action: TransactionAction::Call(H160::repeat_byte(0x22)),
+			value: 1u64.into(),
+			input: Vec::new(),
+			chain_id: Some(1),
+		});
+		let eip1559 = TransactionMessage::EIP1559(EIP1559TransactionMessage {
+			chain_id: 1,
+			nonce: 0u64.into(),
+			max_priority_fee_per_gas: 0u64.into(),
+			max_fee_per_gas: 1_000_000_000u64.into(),
+			gas_limit: 21_000u64.into(),
+			action: TransactionAction::Create,
+			value: 0u64.into(),
+			input: vec![0x60, 0x00],
+			access_list: Vec::new(),
+		});
+
+		for message in [legacy, eip1559] {
+			let transaction = signer.sign(message, &baltathar).unwrap();
+			assert_eq!(crate::rpc::txpool::recover_signer(&transaction), Some(baltathar));
+		}
+	}
+
+	#[test]
+	fn signed_messages_recover_to_the_signing_account() {
+		let signer = DevSigner::default();
+		let alith = signer.accounts()[0];
+		let data = b"diora".to_vec();
+
+		let signature = signer.eth_sign(alith, Bytes(data.clone())).unwrap().0;
+		assert_eq!(signature.len(), 65);
+		assert!(signature[64] == 27 || signature[64] == 28);
+
+		let mut raw = [0u8; 65];
+		raw.copy_from_slice(&signature);
+		raw[64] -= 27;
+		let hash = keccak_256(b"\x19Ethereum Signed Message:\n5diora");
+		let public = ecdsa::Signature::from_raw(raw).recover_prehashed(&hash).unwrap();
+		let recovered = <Signature as Verify>::Signer::from(public).into_account();
+		assert_eq!(H160::from(recovered), alith);
+
+		assert_eq!(signer.personal_sign(Bytes(data.clone()), alith, None).unwrap().0, signature);
+		assert!(signer.eth_sign(H160::repeat_byte(0x01), Bytes(data)).is_err());
+	}
+
+	#[test]
+	fn unknown_accounts_cannot_sign() {
+		let message = TransactionMessage::Legacy(LegacyTransactionMessage {
+			nonce: 0u64.into(),
+			gas_price: 0u64.into(),
+			gas_limit: 21_000u64.into(),
+			action: TransactionAction::Create,
+			value: 0u64.into(),
+			input: Vec::new(),
+			chain_id: None,
+		});
+
+		assert!(DevSigner::default().sign(message, &H160::repeat_byte(0x01)).is_err());
+	}
+}
@@ -0,0 +1,220 @@
+//! Block range cap of `eth_getLogs`.
+//!
+//! Frontier only bounds the number of logs a query returns, which is checked after every block of
+//! the range has been scanned. This wraps its `EthFilterApi` to reject too wide ranges upfront,
+//! both for `eth_getLogs` and for the filters `eth_getFilterLogs` would scan.
+//!
+//! A filter is checked when it is installed. One ending at the latest block covers the blocks
+//! produced since as well, as `eth_getLogs` does for a query sent later.
+
+use std::sync::Arc;
+
+use diora_runtime::opaque::Block;
+use fc_rpc::internal_err;
+use fc_rpc_core::{
+	types::{BlockNumber, Filter, FilterChanges, Index, Log},
+	EthFilterApi,
+};
+use jsonrpc_core::{BoxFuture, Result as RpcResult};
+use sp_blockchain::HeaderBackend;
+use sp_core::U256;
+
+/// `EthFilterApi` rejecting `eth_getLogs` queries and log filters spanning more than
+/// `max_block_range` blocks.
+pub struct BlockRangeLimit<F, C> {
+	inner: F,
+	client: Arc<C>,
+	max_block_range: u32,
+}
+
+impl<F, C> BlockRangeLimit<F, C>
+where
+	C: HeaderBackend<Block>,
+{
+	/// Wraps `inner`.
+	pub fn new(inner: F, client: Arc<C>, max_block_range: u32) -> Self {
+		Self {
+			inner,
+			client,
+			max_block_range,
+		}
+	}
+
+	/// Resolves `number` when it can be without a database lookup.
+	fn resolve(&self, number: Option<&BlockNumber>) -> Option<u64> {
+		match number {
+			Some(BlockNumber::Num(number)) => Some(*number),
+			Some(BlockNumber::Earliest) => Some(0),
+			Some(BlockNumber::Latest) | Some(BlockNumber::Pending) | None => {
+				Some(self.client.info().best_number.into())
+			}
+			Some(BlockNumber::Hash { .. }) => None,
+		}
+	}
+
+	fn check_range(&self, filter: &Filter) -> RpcResult<()> {
+		if filter.block_hash.is_some() {
+			return Ok(());
+		}
+		let from = self.resolve(filter.from_block.as_ref());
+		let to = self.resolve(filter.to_block.as_ref());
+		match (from, to) {
+			(Some(from), Some(to)) if to.saturating_sub(from) > self.max_block_range.into() => {
+				Err(internal_err(format!(
+					"block range is too wide (maximum {})",
+					self.max_block_range
+				)))
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+impl<F, C> EthFilterApi for BlockRangeLimit<F, C>
+where
+	F: EthFilterApi,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		self.check_range(&filter)?;
+		self.inner.new_filter(filter)
+	}
+
+	fn new_block_filter(&self) -> RpcResult<U256> {
+		self.inner.new_block_filter()
+	}
+
+	fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+		self.inner.new_pending_transaction_filter()
+	}
+
+	fn filter_changes(&self, index: Index) -> BoxFuture<RpcResult<FilterChanges>> {
+		self.inner.filter_changes(index)
+	}
+
+	fn filter_logs(&self, index: Index) -> BoxFuture<RpcResult<Vec<Log>>> {
+		self.inner.filter_logs(index)
+	}
+
+	fn uninstall_filter(&self, index: Index) -> RpcResult<bool> {
+		self.inner.uninstall_filter(index)
+	}
+
+	fn logs(&self, filter: Filter) -> BoxFuture<RpcResult<Vec<Log>>> {
+		if let Err(error) = self.check_range(&filter) {
+			return Box::pin(futures::future::err(error));
+		}
+		self.inner.logs(filter)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use diora_runtime::{opaque::Header, BlockNumber as Number, Hash};
+	use sp_blockchain::{BlockStatus, Info};
+	use sp_runtime::generic::BlockId;
+
+	const MAX_BLOCK_RANGE: u32 = 1_024;
+
+	/// Chain whose best block has the given number, which only answers `info`.
+	struct Chain(Number);
+
+	impl HeaderBackend<Block> for Chain {
+		fn header(&self, _: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+			Ok(None)
+		}
+
+		fn info(&self) -> Info<Block> {
+			Info {
+				best_hash: Default::default(),
+				best_number: self.0,
+				genesis_hash: Default::default(),
+				finalized_hash: Default::default(),
+				finalized_number: 0,
+				finalized_state: None,
+				number_leaves: 1,
+				block_gap: None,
+			}
+		}
+
+		fn status(&self, _: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+			Ok(BlockStatus::Unknown)
+		}
+
+		fn number(&self, _: Hash) -> sp_blockchain::Result<Option<Number>> {
+			Ok(None)
+		}
+
+		fn hash(&self, _: Number) -> sp_blockchain::Result<Option<Hash>> {
+			Ok(None)
+		}
+	}
+
+	/// Filter API installing every filter and returning no logs.
+	struct NoLogs;
+
+	impl EthFilterApi for NoLogs {
+		fn new_filter(&self, _: Filter) -> RpcResult<U256> {
+			Ok(U256::one())
+		}
+
+		fn new_block_filter(&self) -> RpcResult<U256> {
+			Ok(U256::one())
+		}
+
+		fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+			Ok(U256::one())
+		}
+
+		fn filter_changes(&self, _: Index) -> BoxFuture<RpcResult<FilterChanges>> {
+			Box::pin(futures::future::ok(FilterChanges::Empty))
+		}
+
+		fn filter_logs(&self, _: Index) -> BoxFuture<RpcResult<Vec<Log>>> {
+			Box::pin(futures::future::ok(Vec::new()))
+		}
+
+		fn uninstall_filter(&self, _: Index) -> RpcResult<bool> {
+			Ok(true)
+		}
+
+		fn logs(&self, _: Filter) -> BoxFuture<RpcResult<Vec<Log>>> {
+			Box::pin(futures::future::ok(Vec::new()))
+		}
+	}
+
+	fn api(best_number: Number) -> BlockRangeLimit<NoLogs, Chain> {
+		BlockRangeLimit::new(NoLogs, Arc::new(Chain(best_number)), MAX_BLOCK_RANGE)
+	}
+
+	fn filter(from_block: Option<BlockNumber>, to_block: Option<BlockNumber>) -> Filter {
+		Filter {
+			from_block,
+			to_block,
+			block_hash: None,
+			address: None,
+			topics: None,
+		}
+	}
+
+	#[test]
+	fn wide_filter_is_not_installed() {
+		let wide = filter(
+			Some(BlockNumber::Earliest),
+			Some(BlockNumber::Num(MAX_BLOCK_RANGE as u64 + 1)),
+		);
+
+		// Otherwise `eth_getFilterLogs` would scan the range `eth_getLogs` rejects.
+		assert!(futures::executor::block_on(api(0).logs(wide.clone())).is_err());
+		assert!(api(0).new_filter(wide).is_err());
+	}
+
+	#[test]
+	fn filter_up_to_the_latest_block_is_checked_against_the_best_block() {
+		let from_genesis = filter(Some(BlockNumber::Earliest), None);
+
+		assert!(api(MAX_BLOCK_RANGE).new_filter(from_genesis.clone()).is_ok());
+		assert!(api(MAX_BLOCK_RANGE + 1).new_filter(from_genesis).is_err());
+	}
+}
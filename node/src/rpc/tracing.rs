@@ -0,0 +1,742 @@
+//! EVM tracing RPCs: `debug_traceTransaction`, `debug_traceBlockByNumber` and `trace_filter`.
+//!
+//! Traces are computed by the runtime, which re-executes the traced block on top of its parent
+//! state through `evm_tracer::DebugRuntimeApi`. This is expensive, so the work runs on a bounded
+//! pool of blocking tasks instead of the RPC threads. The runtime only produces traces when the
+//! node is built with the `evm-tracing` feature.
+//!
+//! `trace_filter` traces every block of its range, which is capped by
+//! `--tracing-max-block-range`. Wider ranges are rejected before any block is traced or a worker
+//! is waited for.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use diora_runtime::opaque::Block;
+use evm_tracer::{CallFrame, CallType, DebugRuntimeApi, TraceType, TransactionTrace};
+use fc_rpc::{frontier_backend_client, internal_err, OverrideHandle};
+use fc_rpc_core::types::{BlockNumber, Bytes};
+use futures::channel::oneshot;
+use jsonrpc_core::{BoxFuture, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockBackend,
+};
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_api::{BlockId, Core, HeaderT, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::BlakeTwo256;
+use tokio::sync::Semaphore;
+
+/// Options of `debug_traceTransaction` and `debug_traceBlockByNumber`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+	/// `callTracer`, or none for the struct logger.
+	pub tracer: Option<String>,
+	/// Omit the storage of the struct logs.
+	pub disable_storage: Option<bool>,
+	/// Omit the memory of the struct logs.
+	pub disable_memory: Option<bool>,
+	/// Omit the stack of the struct logs.
+	pub disable_stack: Option<bool>,
+}
+
+impl TraceParams {
+	fn trace_type(params: Option<TraceParams>) -> RpcResult<TraceType> {
+		let params = params.unwrap_or_default();
+		match params.tracer.as_deref() {
+			Some("callTracer") => Ok(TraceType::CallTracer),
+			None => Ok(TraceType::StructLogger {
+				disable_storage: params.disable_storage.unwrap_or(false),
+				disable_memory: params.disable_memory.unwrap_or(false),
+				disable_stack: params.disable_stack.unwrap_or(false),
+			}),
+			Some(tracer) => Err(internal_err(format!("unsupported tracer: {}", tracer))),
+		}
+	}
+}
+
+/// Trace of one transaction of a block, as returned by `debug_traceBlockByNumber`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionTrace {
+	/// Hash of the traced transaction.
+	pub tx_hash: H256,
+	/// Its trace.
+	pub result: TransactionTrace,
+}
+
+/// Parameters of `trace_filter`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterRequest {
+	/// First block to trace, the latest one by default.
+	pub from_block: Option<BlockNumber>,
+	/// Last block to trace, the latest one by default.
+	pub to_block: Option<BlockNumber>,
+	/// Only return frames made by one of these addresses.
+	pub from_address: Option<Vec<H160>>,
+	/// Only return frames made to one of these addresses.
+	pub to_address: Option<Vec<H160>>,
+	/// Number of matching traces to skip.
+	pub after: Option<u32>,
+	/// Maximum number of traces to return.
+	pub count: Option<u32>,
+}
+
+/// A call frame in the flat format of `trace_filter`, as returned by OpenEthereum.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatTrace {
+	/// What the frame did.
+	pub action: TraceAction,
+	/// Hash of the Ethereum block.
+	pub block_hash: H256,
+	/// Number of the block.
+	pub block_number: u32,
+	/// Outcome of the frame, unless it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub result: Option<TraceResult>,
+	/// Why the frame failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Number of frames called by this one.
+	pub subtraces: usize,
+	/// Position of the frame in the call tree.
+	pub trace_address: Vec<usize>,
+	/// Hash of the transaction.
+	pub transaction_hash: H256,
+	/// Index of the transaction in the block.
+	pub transaction_position: usize,
+	/// `call`, `create` or `suicide`.
+	#[serde(rename = "type")]
+	pub trace_type: &'static str,
+}
+
+/// Action of a [`FlatTrace`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TraceAction {
+	#[serde(rename_all = "camelCase")]
+	Call {
+		call_type: &'static str,
+		from: H160,
+		to: H160,
+		gas: U256,
+		input: Bytes,
+		value: U256,
+	},
+	#[serde(rename_all = "camelCase")]
+	Create {
+		from: H160,
+		gas: U256,
+		init: Bytes,
+		value: U256,
+	},
+	#[serde(rename_all = "camelCase")]
+	Suicide {
+		address: H160,
+		refund_address: H160,
+		balance: U256,
+	},
+}
+
+/// Result of a successful [`FlatTrace`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+	#[serde(rename_all = "camelCase")]
+	Call { gas_used: U256, output: Bytes },
+	#[serde(rename_all = "camelCase")]
+	Create {
+		address: H160,
+		code: Bytes,
+		gas_used: U256,
+	},
+}
+
+impl FlatTrace {
+	fn sender(&self) -> H160 {
+		match &self.action {
+			TraceAction::Call { from, .. } | TraceAction::Create { from, .. } => *from,
+			TraceAction::Suicide { address, .. } => *address,
+		}
+	}
+
+	fn recipient(&self) -> Option<H160> {
+		match (&self.action, &self.result) {
+			(TraceAction::Call { to, .. }, _) => Some(*to),
+			(TraceAction::Create { .. }, Some(TraceResult::Create { address, .. })) => {
+				Some(*address)
+			}
+			(TraceAction::Suicide { refund_address, .. }, _) => Some(*refund_address),
+			_ => None,
+		}
+	}
+}
+
+/// Flattens the call tree of a transaction, depth first, as `trace_filter` expects.
+fn flatten(
+	frame: CallFrame,
+	trace_address: Vec<usize>,
+	block_hash: H256,
+	block_number: u32,
+	transaction_hash: H256,
+	transaction_position: usize,
+	traces: &mut Vec<FlatTrace>,
+) {
+	let call_type = match frame.call_type {
+		CallType::Call => Some("call"),
+		CallType::CallCode => Some("callcode"),
+		CallType::DelegateCall => Some("delegatecall"),
+		CallType::StaticCall => Some("staticcall"),
+		_ => None,
+	};
+	let (trace_type, action, result) = match (frame.call_type, call_type) {
+		(_, Some(call_type)) => (
+			"call",
+			TraceAction::Call {
+				call_type,
+				from: frame.from,
+				to: frame.to,
+				gas: frame.gas,
+				input: Bytes(frame.input),
+				value: frame.value,
+			},
+			TraceResult::Call {
+				gas_used: frame.gas_used,
+				output: Bytes(frame.output),
+			},
+		),
+		(CallType::SelfDestruct, _) => (
+			"suicide",
+			TraceAction::Suicide {
+				address: frame.from,
+				refund_address: frame.to,
+				balance: frame.value,
+			},
+			TraceResult::Call {
+				gas_used: U256::zero(),
+				output: Bytes(Vec::new()),
+			},
+		),
+		_ => (
+			"create",
+			TraceAction::Create {
+				from: frame.from,
+				gas: frame.gas,
+				init: Bytes(frame.input),
+				value: frame.value,
+			},
+			TraceResult::Create {
+				address: frame.to,
+				code: Bytes(frame.output),
+				gas_used: frame.gas_used,
+			},
+		),
+	};
+	let error = frame
+		.error
+		.map(|error| String::from_utf8_lossy(&error).into_owned());
+
+	traces.push(FlatTrace {
+		action,
+		block_hash,
+		block_number,
+		result: if error.is_none() { Some(result) } else { None },
+		error,
+		subtraces: frame.calls.len(),
+		trace_address: trace_address.clone(),
+		transaction_hash,
+		transaction_position,
+		trace_type,
+	});
+
+	for (index, call) in frame.calls.into_iter().enumerate() {
+		let mut child_address = trace_address.clone();
+		child_address.push(index);
+		flatten(
+			call,
+			child_address,
+			block_hash,
+			block_number,
+			transaction_hash,
+			transaction_position,
+			traces,
+		);
+	}
+}
+
+/// Geth `debug` namespace.
+#[rpc(server)]
+pub trait DebugApi {
+	/// Traces a transaction, with Geth's struct logger or `callTracer`.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> BoxFuture<RpcResult<TransactionTrace>>;
+
+	/// Traces every transaction of a block.
+	#[rpc(name = "debug_traceBlockByNumber")]
+	fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		params: Option<TraceParams>,
+	) -> BoxFuture<RpcResult<Vec<BlockTransactionTrace>>>;
+}
+
+/// OpenEthereum `trace` namespace.
+#[rpc(server)]
+pub trait TraceApi {
+	/// Call frames of a range of blocks, filtered by sender and recipient.
+	#[rpc(name = "trace_filter")]
+	fn filter(&self, filter: FilterRequest) -> BoxFuture<RpcResult<Vec<FlatTrace>>>;
+}
+
+/// Bounded pool of blocking tasks computing traces.
+#[derive(Clone)]
+pub struct TracingWorkers {
+	spawn_handle: SpawnTaskHandle,
+	permits: Arc<Semaphore>,
+	/// Maximum number of traces returned by `trace_filter`.
+	max_count: u32,
+	/// Maximum number of blocks `trace_filter` can trace, besides the first one.
+	max_block_range: u32,
+}
+
+impl TracingWorkers {
+	/// Creates a pool running at most `max_permits` traces at once.
+	pub fn new(
+		spawn_handle: SpawnTaskHandle,
+		max_permits: usize,
+		max_count: u32,
+		max_block_range: u32,
+	) -> Self {
+		Self {
+			spawn_handle,
+			permits: Arc::new(Semaphore::new(max_permits)),
+			max_count,
+			max_block_range,
+		}
+	}
+
+	/// Runs `task` once a worker is available.
+	async fn run<R: Send + 'static>(
+		&self,
+		task: impl FnOnce() -> R + Send + 'static,
+	) -> RpcResult<R> {
+		let permit = self
+			.permits
+			.clone()
+			.acquire_owned()
+			.await
+			.map_err(|_| internal_err("tracing workers are shut down"))?;
+		let (sender, receiver) = oneshot::channel();
+
+		self.spawn_handle
+			.spawn_blocking("evm-tracing", Some("rpc"), async move {
+				let _ = sender.send(task());
+				drop(permit);
+			});
+
+		receiver
+			.await
+			.map_err(|_| internal_err("tracing task was cancelled"))
+	}
+}
+
+/// Implementation of the tracing RPCs.
+pub struct Tracing<C, BE> {
+	client: Arc<C>,
+	backend: Arc<fc_db::Backend<Block>>,
+	overrides: Arc<OverrideHandle<Block>>,
+	workers: TracingWorkers,
+	_marker: PhantomData<BE>,
+}
+
+impl<C, BE> Clone for Tracing<C, BE> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			overrides: self.overrides.clone(),
+			workers: self.workers.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<C, BE> Tracing<C, BE>
+where
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + BlockBackend<Block>,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: Core<Block> + DebugRuntimeApi<Block>,
+{
+	/// Creates the RPC handler.
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<fc_db::Backend<Block>>,
+		overrides: Arc<OverrideHandle<Block>>,
+		workers: TracingWorkers,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			overrides,
+			workers,
+			_marker: PhantomData,
+		}
+	}
+
+	fn block_id(&self, number: BlockNumber) -> RpcResult<BlockId<Block>> {
+		frontier_backend_client::native_block_id::<Block, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)?
+		.ok_or_else(|| internal_err("block not found"))
+	}
+
+	fn block_number(&self, id: &BlockId<Block>) -> RpcResult<u32> {
+		self.client
+			.block_number_from_id(id)
+			.map_err(|e| internal_err(format!("{:?}", e)))?
+			.ok_or_else(|| internal_err("block not found"))
+	}
+
+	fn ethereum_block(&self, id: &BlockId<Block>) -> RpcResult<ethereum::BlockV2> {
+		let schema = frontier_backend_client::onchain_storage_schema::<Block, C, BE>(
+			self.client.as_ref(),
+			*id,
+		);
+		self.overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&self.overrides.fallback)
+			.current_block(id)
+			.ok_or_else(|| internal_err("ethereum block not found"))
+	}
+
+	/// Runtime API initialized for re-executing block `id`, with the block extrinsics and the
+	/// parent block the runtime calls must be made at.
+	fn replay(
+		&self,
+		id: &BlockId<Block>,
+	) -> RpcResult<(
+		sp_api::ApiRef<'_, C::Api>,
+		BlockId<Block>,
+		Vec<<Block as sp_runtime::traits::Block>::Extrinsic>,
+	)> {
+		let header = self
+			.client
+			.header(*id)
+			.map_err(|e| internal_err(format!("{:?}", e)))?
+			.ok_or_else(|| internal_err("block header not found"))?;
+		let extrinsics = self
+			.client
+			.block_body(id)
+			.map_err(|e| internal_err(format!("{:?}", e)))?
+			.ok_or_else(|| internal_err("block body not found"))?;
+		let parent = BlockId::Hash(*header.parent_hash());
+
+		let api = self.client.runtime_api();
+		api.initialize_block(&parent, &header)
+			.map_err(|e| internal_err(format!("runtime api error: {:?}", e)))?;
+
+		Ok((api, parent, extrinsics))
+	}
+
+	fn trace_transaction_blocking(
+		&self,
+		transaction_hash: H256,
+		trace_type: TraceType,
+	) -> RpcResult<TransactionTrace> {
+		let (ethereum_block_hash, index) = frontier_backend_client::load_transactions::<Block, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			transaction_hash,
+			true,
+		)?
+		.ok_or_else(|| internal_err("transaction not found"))?;
+		let id = frontier_backend_client::load_hash::<Block>(
+			self.backend.as_ref(),
+			ethereum_block_hash,
+		)?
+		.ok_or_else(|| internal_err("block not found"))?;
+		let transaction = self
+			.ethereum_block(&id)?
+			.transactions
+			.get(index as usize)
+			.cloned()
+			.ok_or_else(|| internal_err("transaction not found"))?;
+
+		let (api, parent, extrinsics) = self.replay(&id)?;
+		api.trace_transaction(&parent, extrinsics, &transaction, trace_type)
+			.map_err(|e| internal_err(format!("runtime api error: {:?}", e)))?
+			.map_err(|e| internal_err(format!("failed to trace transaction: {:?}", e)))
+	}
+
+	fn trace_block_blocking(
+		&self,
+		id: BlockId<Block>,
+		trace_type: TraceType,
+	) -> RpcResult<(ethereum::BlockV2, Vec<TransactionTrace>)> {
+		let block = self.ethereum_block(&id)?;
+		let hashes = block.transactions.iter().map(|t| t.hash()).collect();
+
+		let (api, parent, extrinsics) = self.replay(&id)?;
+		let traces = api
+			.trace_block(&parent, extrinsics, hashes, trace_type)
+			.map_err(|e| internal_err(format!("runtime api error: {:?}", e)))?
+			.map_err(|e| internal_err(format!("failed to trace block: {:?}", e)))?;
+
+		Ok((block, traces))
+	}
+
+	/// Blocks `request` covers, rejecting requests over the limits of the workers. This only
+	/// looks up block numbers, so too wide requests are rejected before tracing any block.
+	fn filter_range(&self, request: &FilterRequest) -> RpcResult<(u32, u32)> {
+		let from_block =
+			self.block_number(&self.block_id(request.from_block.unwrap_or(BlockNumber::Latest))?)?;
+		let to_block =
+			self.block_number(&self.block_id(request.to_block.unwrap_or(BlockNumber::Latest))?)?;
+		if from_block > to_block {
+			return Err(internal_err("fromBlock must not be after toBlock"));
+		}
+		if to_block - from_block > self.workers.max_block_range {
+			return Err(internal_err(format!(
+				"block range is too wide (maximum {})",
+				self.workers.max_block_range
+			)));
+		}
+		if request.count.map_or(false, |count| count > self.workers.max_count) {
+			return Err(internal_err(format!(
+				"count must not exceed {}",
+				self.workers.max_count
+			)));
+		}
+
+		Ok((from_block, to_block))
+	}
+
+	fn filter_blocking(
+		&self,
+		(from_block, to_block): (u32, u32),
+		request: FilterRequest,
+	) -> RpcResult<Vec<FlatTrace>> {
+		let count = request.count.unwrap_or(self.workers.max_count) as usize;
+		let mut to_skip = request.after.unwrap_or(0) as usize;
+
+		let matches = |trace: &FlatTrace| {
+			request
+				.from_address
+				.as_ref()
+				.map_or(true, |addresses| addresses.contains(&trace.sender()))
+				&& request.to_address.as_ref().map_or(true, |addresses| {
+					trace.recipient().map_or(false, |to| addresses.contains(&to))
+				})
+		};
+
+		let mut result = Vec::new();
+		for number in from_block..=to_block {
+			let (block, traces) =
+				self.trace_block_blocking(BlockId::Number(number), TraceType::CallTracer)?;
+			let block_hash = block.header.hash();
+
+			for (position, (transaction, trace)) in
+				block.transactions.iter().zip(traces).enumerate()
+			{
+				let frame = match trace {
+					TransactionTrace::CallTracer(frame) => frame,
+					TransactionTrace::StructLogger(_) => continue,
+				};
+				let mut flat = Vec::new();
+				flatten(
+					frame,
+					Vec::new(),
+					block_hash,
+					number,
+					transaction.hash(),
+					position,
+					&mut flat,
+				);
+
+				for trace in flat.into_iter().filter(|trace| matches(trace)) {
+					if to_skip > 0 {
+						to_skip -= 1;
+						continue;
+					}
+					result.push(trace);
+					if result.len() == count {
+						return Ok(result);
+					}
+				}
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+impl<C, BE> DebugApi for Tracing<C, BE>
+where
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + BlockBackend<Block>,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: Core<Block> + DebugRuntimeApi<Block>,
+{
+	fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> BoxFuture<RpcResult<TransactionTrace>> {
+		let tracing = self.clone();
+		Box::pin(async move {
+			let trace_type = TraceParams::trace_type(params)?;
+			let workers = tracing.workers.clone();
+			workers
+				.run(move || tracing.trace_transaction_blocking(transaction_hash, trace_type))
+				.await?
+		})
+	}
+
+	fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		params: Option<TraceParams>,
+	) -> BoxFuture<RpcResult<Vec<BlockTransactionTrace>>> {
+		let tracing = self.clone();
+		Box::pin(async move {
+			let trace_type = TraceParams::trace_type(params)?;
+			let workers = tracing.workers.clone();
+			workers
+				.run(move || {
+					let id = tracing.block_id(number)?;
+					let (block, traces) = tracing.trace_block_blocking(id, trace_type)?;
+					Ok(block
+						.transactions
+						.iter()
+						.zip(traces)
+						.map(|(transaction, result)| BlockTransactionTrace {
+							tx_hash: transaction.hash(),
+							result,
+						})
+						.collect())
+				})
+				.await?
+		})
+	}
+}
+
+impl<C, BE> TraceApi for Tracing<C, BE>
+where
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + BlockBackend<Block>,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: Core<Block> + DebugRuntimeApi<Block>,
+{
+	fn filter(&self, filter: FilterRequest) -> BoxFuture<RpcResult<Vec<FlatTrace>>> {
+		let tracing = self.clone();
+		Box::pin(async move {
+			let range = tracing.filter_range(&filter)?;
+			let workers = tracing.workers.clone();
+			workers.run(move || tracing.filter_blocking(range, filter)).await?
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn frame(call_type: CallType, calls: Vec<CallFrame>) -> CallFrame {
+		CallFrame {
+			call_type,
+			from: H160::repeat_byte(0x01),
+			to: H160::repeat_byte(0x02),
+			value: U256::zero(),
+			gas: U256::from(100_000),
+			gas_used: U256::from(21_000),
+			input: Vec::new(),
+			output: Vec::new(),
+			error: None,
+			calls,
+		}
+	}
+
+	#[test]
+	fn call_tree_is_flattened_depth_first() {
+		let tree = frame(
+			CallType::Call,
+			vec![
+				frame(CallType::DelegateCall, vec![frame(CallType::StaticCall, vec![])]),
+				frame(CallType::Create, vec![]),
+			],
+		);
+
+		let mut traces = Vec::new();
+		flatten(tree, Vec::new(), H256::zero(), 1, H256::zero(), 0, &mut traces);
+
+		let shape: Vec<_> = traces
+			.iter()
+			.map(|t| (t.trace_type, t.trace_address.clone(), t.subtraces))
+			.collect();
+		assert_eq!(
+			shape,
+			vec![
+				("call", vec![], 2),
+				("call", vec![0], 1),
+				("call", vec![0, 0], 0),
+				("create", vec![1], 0),
+			]
+		);
+	}
+
+	#[test]
+	fn failed_frames_report_an_error_instead_of_a_result() {
+		let mut failed = frame(CallType::Call, vec![]);
+		failed.error = Some(b"execution reverted".to_vec());
+
+		let mut traces = Vec::new();
+		flatten(failed, Vec::new(), H256::zero(), 1, H256::zero(), 0, &mut traces);
+
+		assert!(traces[0].result.is_none());
+		assert_eq!(traces[0].error.as_deref(), Some("execution reverted"));
+	}
+
+	#[test]
+	fn tracer_is_selected_from_params() {
+		assert_eq!(
+			TraceParams::trace_type(Some(TraceParams {
+				tracer: Some("callTracer".into()),
+				..Default::default()
+			}))
+			.unwrap(),
+			TraceType::CallTracer
+		);
+		assert_eq!(
+			TraceParams::trace_type(None).unwrap(),
+			TraceType::StructLogger {
+				disable_storage: false,
+				disable_memory: false,
+				disable_stack: false,
+			}
+		);
+		assert!(TraceParams::trace_type(Some(TraceParams {
+			tracer: Some("prestateTracer".into()),
+			..Default::default()
+		}))
+		.is_err());
+	}
+}
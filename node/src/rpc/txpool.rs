@@ -0,0 +1,309 @@
+//! `txpool` RPCs: `txpool_status`, `txpool_content` and `txpool_inspect`.
+//!
+//! Ethereum transactions are extracted from the ready (pending) and future (queued) extrinsics of
+//! the transaction pool with `EthereumRuntimeRPCApi::extrinsic_filter`, then grouped by sender and
+//! nonce as Geth does.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use diora_runtime::opaque::Block;
+use ethereum::{
+	EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage,
+	TransactionAction, TransactionV2,
+};
+use fc_rpc::internal_err;
+use fc_rpc_core::types::Bytes;
+use fp_rpc::EthereumRuntimeRPCApi;
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::InPoolTransaction;
+use serde::Serialize;
+use sp_api::{BlockId, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{keccak_256, H160, H256, U256};
+
+/// Transactions of the pool, split as Geth does.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TxPoolResult<T> {
+	/// Transactions that can be included in the next block.
+	pub pending: T,
+	/// Transactions waiting for an earlier nonce.
+	pub queued: T,
+}
+
+/// Transactions indexed by sender, then by nonce in decimal.
+pub type TransactionMap<T> = BTreeMap<H160, BTreeMap<String, T>>;
+
+/// A pool transaction, as returned by `txpool_content`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+	/// Hash of the transaction.
+	pub hash: H256,
+	/// Nonce of the sender.
+	pub nonce: U256,
+	/// Always null, the transaction is not in a block yet.
+	pub block_hash: Option<H256>,
+	/// Always null, the transaction is not in a block yet.
+	pub block_number: Option<U256>,
+	/// Always null, the transaction is not in a block yet.
+	pub transaction_index: Option<U256>,
+	/// Sender.
+	pub from: H160,
+	/// Recipient, none for contract creations.
+	pub to: Option<H160>,
+	/// Transferred value.
+	pub value: U256,
+	/// Gas price, or maximum fee per gas for EIP-1559 transactions.
+	pub gas_price: U256,
+	/// Gas limit.
+	pub gas: U256,
+	/// Call data or init code.
+	pub input: Bytes,
+}
+
+impl Transaction {
+	fn new(transaction: &TransactionV2, from: H160) -> Self {
+		let (nonce, action, value, gas_price, gas, input) = match transaction {
+			TransactionV2::Legacy(t) => {
+				(t.nonce, t.action, t.value, t.gas_price, t.gas_limit, &t.input)
+			}
+			TransactionV2::EIP2930(t) => {
+				(t.nonce, t.action, t.value, t.gas_price, t.gas_limit, &t.input)
+			}
+			TransactionV2::EIP1559(t) => {
+				(t.nonce, t.action, t.value, t.max_fee_per_gas, t.gas_limit, &t.input)
+			}
+		};
+
+		Self {
+			hash: transaction.hash(),
+			nonce,
+			block_hash: None,
+			block_number: None,
+			transaction_index: None,
+			from,
+			to: match action {
+				TransactionAction::Call(to) => Some(to),
+				TransactionAction::Create => None,
+			},
+			value,
+			gas_price,
+			gas,
+			input: Bytes(input.clone()),
+		}
+	}
+
+	/// One-line summary returned by `txpool_inspect`.
+	fn summary(&self) -> String {
+		let to = match self.to {
+			Some(to) => format!("{:?}", to),
+			None => "contract creation".into(),
+		};
+		format!("{}: {} wei + {} gas × {} wei", to, self.value, self.gas, self.gas_price)
+	}
+}
+
+/// Recovers the sender of a signed Ethereum transaction.
+pub(crate) fn recover_signer(transaction: &TransactionV2) -> Option<H160> {
+	let mut sig = [0u8; 65];
+	let message = match transaction {
+		TransactionV2::Legacy(t) => {
+			sig[0..32].copy_from_slice(&t.signature.r()[..]);
+			sig[32..64].copy_from_slice(&t.signature.s()[..]);
+			sig[64] = t.signature.standard_v();
+			LegacyTransactionMessage::from(t.clone()).hash()
+		}
+		TransactionV2::EIP2930(t) => {
+			sig[0..32].copy_from_slice(&t.r[..]);
+			sig[32..64].copy_from_slice(&t.s[..]);
+			sig[64] = t.odd_y_parity as u8;
+			EIP2930TransactionMessage::from(t.clone()).hash()
+		}
+		TransactionV2::EIP1559(t) => {
+			sig[0..32].copy_from_slice(&t.r[..]);
+			sig[32..64].copy_from_slice(&t.s[..]);
+			sig[64] = t.odd_y_parity as u8;
+			EIP1559TransactionMessage::from(t.clone()).hash()
+		}
+	};
+	let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &message.0).ok()?;
+	Some(H160::from(H256::from(keccak_256(&pubkey))))
+}
+
+/// Groups transactions by sender and nonce, skipping those whose signature is invalid.
+fn group<T>(transactions: Vec<TransactionV2>, f: impl Fn(Transaction) -> T) -> TransactionMap<T> {
+	let mut map = TransactionMap::new();
+	for transaction in transactions {
+		if let Some(from) = recover_signer(&transaction) {
+			let transaction = Transaction::new(&transaction, from);
+			map.entry(from)
+				.or_default()
+				.insert(transaction.nonce.to_string(), f(transaction));
+		}
+	}
+	map
+}
+
+/// Geth `txpool` namespace.
+#[rpc(server)]
+pub trait TxPoolApi {
+	/// Every pending and queued transaction.
+	#[rpc(name = "txpool_content")]
+	fn content(&self) -> RpcResult<TxPoolResult<TransactionMap<Transaction>>>;
+
+	/// A textual summary of every pending and queued transaction.
+	#[rpc(name = "txpool_inspect")]
+	fn inspect(&self) -> RpcResult<TxPoolResult<TransactionMap<String>>>;
+
+	/// Number of pending and queued transactions.
+	#[rpc(name = "txpool_status")]
+	fn status(&self) -> RpcResult<TxPoolResult<U256>>;
+}
+
+/// Implementation of the `txpool` RPCs.
+pub struct TxPool<C, A: ChainApi> {
+	client: Arc<C>,
+	graph: Arc<Pool<A>>,
+}
+
+impl<C, A> TxPool<C, A>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	A: ChainApi<Block = Block> + 'static,
+{
+	/// Creates the RPC handler.
+	pub fn new(client: Arc<C>, graph: Arc<Pool<A>>) -> Self {
+		Self { client, graph }
+	}
+
+	/// Ethereum transactions of the pool, at the best block.
+	fn transactions(&self) -> RpcResult<TxPoolResult<Vec<TransactionV2>>> {
+		let pool = self.graph.validated_pool();
+		let ready = pool.ready().map(|in_pool_tx| in_pool_tx.data().clone()).collect();
+		let future = pool.futures().into_iter().map(|(_, xt)| xt).collect();
+
+		let best = BlockId::Hash(self.client.info().best_hash);
+		let api = self.client.runtime_api();
+		let filter = |extrinsics| {
+			api.extrinsic_filter(&best, extrinsics)
+				.map_err(|e| internal_err(format!("runtime api error: {:?}", e)))
+		};
+
+		Ok(TxPoolResult {
+			pending: filter(ready)?,
+			queued: filter(future)?,
+		})
+	}
+}
+
+impl<C, A> TxPoolApi for TxPool<C, A>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	A: ChainApi<Block = Block> + 'static,
+{
+	fn content(&self) -> RpcResult<TxPoolResult<TransactionMap<Transaction>>> {
+		let transactions = self.transactions()?;
+		Ok(TxPoolResult {
+			pending: group(transactions.pending, |t| t),
+			queued: group(transactions.queued, |t| t),
+		})
+	}
+
+	fn inspect(&self) -> RpcResult<TxPoolResult<TransactionMap<String>>> {
+		let transactions = self.transactions()?;
+		Ok(TxPoolResult {
+			pending: group(transactions.pending, |t| t.summary()),
+			queued: group(transactions.queued, |t| t.summary()),
+		})
+	}
+
+	fn status(&self) -> RpcResult<TxPoolResult<U256>> {
+		let transactions = self.transactions()?;
+		Ok(TxPoolResult {
+			pending: transactions.pending.len().into(),
+			queued: transactions.queued.len().into(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{LegacyTransaction, TransactionSignature};
+
+	/// Alith's development key.
+	const SECRET: [u8; 32] = hex_literal::hex!(
+		"5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133"
+	);
+	const ALITH: [u8; 20] = hex_literal::hex!("f24FF3a9CF04c71Dbc94D0b566f7A27B94566cac");
+	const CHAIN_ID: u64 = 1;
+
+	fn signed(nonce: u64, action: TransactionAction) -> TransactionV2 {
+		let message = LegacyTransactionMessage {
+			nonce: nonce.into(),
+			gas_price: 1_000_000_000u64.into(),
+			gas_limit: 21_000u64.into(),
+			action,
+			value: 1u64.into(),
+			input: Vec::new(),
+			chain_id: Some(CHAIN_ID),
+		};
+		let pair = <sp_core::ecdsa::Pair as sp_core::Pair>::from_seed(&SECRET);
+		let signature = pair.sign_prehashed(&message.hash().0);
+		let signature = TransactionSignature::new(
+			// EIP-155 `v`.
+			signature.0[64] as u64 + CHAIN_ID * 2 + 35,
+			H256::from_slice(&signature.0[0..32]),
+			H256::from_slice(&signature.0[32..64]),
+		)
+		.expect("valid signature");
+
+		TransactionV2::Legacy(LegacyTransaction {
+			nonce: message.nonce,
+			gas_price: message.gas_price,
+			gas_limit: message.gas_limit,
+			action: message.action,
+			value: message.value,
+			input: message.input,
+			signature,
+		})
+	}
+
+	#[test]
+	fn transactions_are_grouped_by_sender_and_nonce() {
+		let to = H160::repeat_byte(0x22);
+		let grouped = group(
+			vec![
+				signed(10, TransactionAction::Call(to)),
+				signed(9, TransactionAction::Create),
+			],
+			|t| t.summary(),
+		);
+
+		let by_nonce = &grouped[&H160(ALITH)];
+		assert_eq!(grouped.len(), 1);
+		assert_eq!(
+			by_nonce["10"],
+			"0x2222222222222222222222222222222222222222: 1 wei + 21000 gas × 1000000000 wei"
+		);
+		assert_eq!(
+			by_nonce["9"],
+			"contract creation: 1 wei + 21000 gas × 1000000000 wei"
+		);
+	}
+
+	#[test]
+	fn pool_transactions_have_no_block() {
+		let transaction = signed(0, TransactionAction::Create);
+		let json = serde_json::to_value(Transaction::new(&transaction, H160(ALITH))).unwrap();
+
+		assert_eq!(json["blockHash"], serde_json::Value::Null);
+		assert_eq!(json["to"], serde_json::Value::Null);
+		assert_eq!(json["gasPrice"], "0x3b9aca00");
+		assert_eq!(json["hash"], serde_json::json!(transaction.hash()));
+	}
+}
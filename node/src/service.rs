@@ -50,7 +50,12 @@ use futures::StreamExt;
 use maplit::hashmap;
 use sc_client_api::BlockchainEvents;
 use sc_service::config::PrometheusConfig;
-use sc_service::BasePath;
+use sc_service::{BasePath, ChainType};
+
+use crate::{
+	cli::{EthConfiguration, TracingConfig},
+	rpc::tracing::TracingWorkers,
+};
 use std::{collections::BTreeMap, sync::Mutex};
 
 /// Native executor instance.
@@ -122,6 +127,29 @@ fn set_prometheus_registry(config: &mut Configuration) -> Result<(), ServiceErro
 }
 
 
+/// Rejects the Ethereum RPC options that do not apply to the chain being run.
+fn check_eth_configuration(
+	config: &Configuration,
+	eth: &EthConfiguration,
+) -> Result<(), ServiceError> {
+	if eth.enable_dev_signer && config.chain_spec.chain_type() != ChainType::Development {
+		return Err("`--enable-dev-signer` is only allowed on development chains".into());
+	}
+
+	Ok(())
+}
+
+/// Rejects `--enable-evm-tracing` on a node whose runtime cannot produce traces.
+fn check_tracing_configuration(tracing: &TracingConfig) -> Result<(), ServiceError> {
+	if tracing.enable_evm_tracing && !cfg!(feature = "evm-tracing") {
+		return Err(
+			"`--enable-evm-tracing` requires a node built with the `evm-tracing` feature".into(),
+		);
+	}
+
+	Ok(())
+}
+
 /// Starts a `ServiceBuilder` for a full service.
 ///
 /// Use this macro if you don't actually need the full service, but just the builder in order to
@@ -267,6 +295,8 @@ async fn start_node_impl<RB, BIC>(
 	polkadot_config: Configuration,
 	collator_options: CollatorOptions,
 	id: ParaId,
+	eth: EthConfiguration,
+	tracing: TracingConfig,
 	_rpc_ext_builder: RB,
 	build_consensus: BIC,
 ) -> sc_service::error::Result<(
@@ -303,6 +333,8 @@ where
 		return Err("Light client not supported!".into());
 	}
 
+	check_eth_configuration(&parachain_config, &eth)?;
+	check_tracing_configuration(&tracing)?;
 	let parachain_config = prepare_node_config(parachain_config);
 
 	let sc_service::PartialComponents {
@@ -352,16 +384,24 @@ where
 	let subscription_task_executor =
 		sc_rpc::SubscriptionTaskExecutor::new(task_manager.spawn_handle());
 	let overrides = crate::rpc::overrides_handle(client.clone());
-	let fee_history_limit = 2048;
 
 	let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
 		task_manager.spawn_handle(),
 		overrides.clone(),
-		50,
-		50,
+		eth.eth_log_block_cache,
+		eth.eth_statuses_cache,
 		prometheus_registry.clone(),
 	));
 
+	let tracing_workers = tracing.enable_evm_tracing.then(|| {
+		TracingWorkers::new(
+			task_manager.spawn_handle(),
+			tracing.tracing_max_permits,
+			tracing.tracing_max_count,
+			tracing.tracing_max_block_range,
+		)
+	});
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
@@ -371,7 +411,6 @@ where
 		let overrides = overrides.clone();
 		let fee_history_cache = fee_history_cache.clone();
 		let is_authority = false;
-		let max_past_logs = 10000;
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
@@ -383,11 +422,11 @@ where
 				network: network.clone(),
 				filter_pool: filter_pool.clone(),
 				backend: frontier_backend.clone(),
-				max_past_logs,
-				fee_history_limit,
+				eth: eth.clone(),
 				fee_history_cache: fee_history_cache.clone(),
 				overrides: overrides.clone(),
 				block_data_cache: block_data_cache.clone(),
+				tracing_workers: tracing_workers.clone(),
 			};
 
 			Ok(crate::rpc::create_full(
@@ -473,6 +512,8 @@ pub async fn start_parachain_node(
 	polkadot_config: Configuration,
 	collator_options: CollatorOptions,
 	id: ParaId,
+	eth: EthConfiguration,
+	tracing: TracingConfig,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<TemplateRuntimeExecutor>>>,
@@ -482,6 +523,8 @@ pub async fn start_parachain_node(
 		polkadot_config,
 		collator_options,
 		id,
+		eth,
+		tracing,
 		|_| Ok(Default::default()),
 		|client,
 		 prometheus_registry,
@@ -543,7 +586,14 @@ pub async fn start_parachain_node(
 }
 
 /// Builds a new service for a full client.
-pub fn start_instant_seal_node(config: Configuration) -> Result<TaskManager, sc_service::Error> {
+pub fn start_instant_seal_node(
+	config: Configuration,
+	eth: EthConfiguration,
+	tracing: TracingConfig,
+) -> Result<TaskManager, sc_service::Error> {
+	check_eth_configuration(&config, &eth)?;
+	check_tracing_configuration(&tracing)?;
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -581,16 +631,24 @@ pub fn start_instant_seal_node(config: Configuration) -> Result<TaskManager, sc_
 	let subscription_task_executor =
 		sc_rpc::SubscriptionTaskExecutor::new(task_manager.spawn_handle());
 	let overrides = crate::rpc::overrides_handle(client.clone());
-	let fee_history_limit = 2048;
 
 	let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
 		task_manager.spawn_handle(),
 		overrides.clone(),
-		50,
-		50,
+		eth.eth_log_block_cache,
+		eth.eth_statuses_cache,
 		prometheus_registry.clone(),
 	));
 
+	let tracing_workers = tracing.enable_evm_tracing.then(|| {
+		TracingWorkers::new(
+			task_manager.spawn_handle(),
+			tracing.tracing_max_permits,
+			tracing.tracing_max_count,
+			tracing.tracing_max_block_range,
+		)
+	});
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
@@ -600,7 +658,6 @@ pub fn start_instant_seal_node(config: Configuration) -> Result<TaskManager, sc_
 		let overrides = overrides.clone();
 		let fee_history_cache = fee_history_cache.clone();
 		let is_authority = false;
-		let max_past_logs = 10000;
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
@@ -612,11 +669,11 @@ pub fn start_instant_seal_node(config: Configuration) -> Result<TaskManager, sc_
 				network: network.clone(),
 				filter_pool: filter_pool.clone(),
 				backend: frontier_backend.clone(),
-				max_past_logs,
-				fee_history_limit,
+				eth: eth.clone(),
 				fee_history_cache: fee_history_cache.clone(),
 				overrides: overrides.clone(),
 				block_data_cache: block_data_cache.clone(),
+				tracing_workers: tracing_workers.clone(),
 			};
 
 			Ok(crate::rpc::create_full(
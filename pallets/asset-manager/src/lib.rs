@@ -0,0 +1,349 @@
+//! Registry of foreign assets.
+//!
+//! Assets of other chains reach Diora over XCM as concrete locations, e.g.
+//! `(1, [Parachain(2000), GeneralKey(..)])`. Governance registers each of them under an asset of
+//! `pallet_assets`, created and owned by this pallet with the given metadata and minimum balance,
+//! which then holds the balances of the asset on Diora.
+//!
+//! The pallet does not hook into XCM itself. The runtime plugs [`AsAssetId`] into the `Matcher` of
+//! a `FungiblesAdapter` asset transactor, and [`IsReserveChain`] into `IsReserve` so that the
+//! chain an asset is located in is trusted as its reserve.
+//!
+//! Governance may also set how many units of an asset buy one second of weight.
+//! [`FirstAssetTrader`] then accepts the asset as payment for XCM execution, and hands the fees it
+//! collects to [`XcmFeesToAccount`] or any other `TakeRevenue`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{fungibles, Get},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
+};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{borrow::Borrow, marker::PhantomData, vec::Vec};
+use xcm::latest::prelude::*;
+use xcm_builder::TakeRevenue;
+use xcm_executor::traits::{Convert, FilterAssetLocation, MatchesFungibles, WeightTrader};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Metadata of a foreign asset, as stored by `pallet_assets`.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AssetMetadata {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional, PalletId};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::AccountIdConversion;
+	use sp_std::boxed::Box;
+	use xcm::VersionedMultiLocation;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Identifier of an asset in `Assets`.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+		/// Balance of an asset in `Assets`.
+		type Balance: Member + Parameter + Copy + MaxEncodedLen;
+		/// The assets holding the balances of foreign assets, usually `pallet_assets`.
+		type Assets: fungibles::Create<
+				Self::AccountId,
+				AssetId = Self::AssetId,
+				Balance = Self::Balance,
+			> + fungibles::metadata::Mutate<Self::AccountId>;
+		/// Identifier of the account owning the foreign assets in `Assets`.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Origin allowed to register foreign assets and change their location.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location cannot be converted to the current XCM version.
+		BadVersion,
+		/// An asset is already registered with this identifier.
+		AssetAlreadyRegistered,
+		/// No asset is registered with this identifier.
+		AssetNotRegistered,
+		/// An asset is already registered at this location.
+		LocationAlreadyRegistered,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A foreign asset has been registered at a location.
+		AssetRegistered(T::AssetId, MultiLocation),
+		/// The location of a foreign asset has been changed.
+		AssetLocationUpdated(T::AssetId, MultiLocation),
+		/// The units of a foreign asset paying for one second of XCM execution have been set.
+		UnitsPerSecondChanged(T::AssetId, u128),
+	}
+
+	/// Location of each registered asset.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_location)]
+	pub type AssetLocations<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, MultiLocation, OptionQuery>;
+
+	/// Asset registered at each location.
+	#[pallet::storage]
+	#[pallet::getter(fn location_asset)]
+	pub type LocationAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, MultiLocation, T::AssetId, OptionQuery>;
+
+	/// Units of each asset paying for one second of XCM execution. Assets without an entry cannot
+	/// pay for execution.
+	#[pallet::storage]
+	#[pallet::getter(fn units_per_second)]
+	pub type AssetUnitsPerSecond<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, u128, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the asset at `location` as `asset_id`, creating it in `Assets`.
+		///
+		/// Balances below `min_balance` are removed, as for the existential deposit of the native
+		/// token. A sufficient asset lets accounts exist without holding the native token.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 4).saturating_add(50_000_000))]
+		#[transactional]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+			metadata: AssetMetadata,
+			min_balance: T::Balance,
+			is_sufficient: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let location = MultiLocation::try_from(*location).map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				!AssetLocations::<T>::contains_key(asset_id),
+				Error::<T>::AssetAlreadyRegistered
+			);
+			ensure!(
+				!LocationAssets::<T>::contains_key(&location),
+				Error::<T>::LocationAlreadyRegistered
+			);
+
+			let owner = Self::account_id();
+			T::Assets::create(asset_id, owner.clone(), is_sufficient, min_balance)?;
+			T::Assets::set(
+				asset_id,
+				&owner,
+				metadata.name,
+				metadata.symbol,
+				metadata.decimals,
+			)?;
+
+			AssetLocations::<T>::insert(asset_id, &location);
+			LocationAssets::<T>::insert(&location, asset_id);
+			Self::deposit_event(Event::AssetRegistered(asset_id, location));
+
+			Ok(())
+		}
+
+		/// Move a registered asset to another location, e.g. after its chain changed how it
+		/// identifies the asset. Balances are kept.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 3).saturating_add(20_000_000))]
+		pub fn update_asset_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let location = MultiLocation::try_from(*location).map_err(|()| Error::<T>::BadVersion)?;
+			let previous =
+				AssetLocations::<T>::get(asset_id).ok_or(Error::<T>::AssetNotRegistered)?;
+			ensure!(
+				!LocationAssets::<T>::contains_key(&location),
+				Error::<T>::LocationAlreadyRegistered
+			);
+
+			LocationAssets::<T>::remove(&previous);
+			LocationAssets::<T>::insert(&location, asset_id);
+			AssetLocations::<T>::insert(asset_id, &location);
+			Self::deposit_event(Event::AssetLocationUpdated(asset_id, location));
+
+			Ok(())
+		}
+
+		/// Accept a registered asset as payment for XCM execution, at `units_per_second` units of
+		/// it per second of weight.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1).saturating_add(10_000_000))]
+		pub fn set_asset_units_per_second(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			units_per_second: u128,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(AssetLocations::<T>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+
+			AssetUnitsPerSecond::<T>::insert(asset_id, units_per_second);
+			Self::deposit_event(Event::UnitsPerSecondChanged(asset_id, units_per_second));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account owning the foreign assets.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+	}
+}
+
+/// Converts between the location of a registered asset and its identifier, to match foreign
+/// assets in a `FungiblesAdapter` through `ConvertedConcreteAssetId`.
+pub struct AsAssetId<T>(PhantomData<T>);
+
+impl<T: Config> Convert<MultiLocation, T::AssetId> for AsAssetId<T> {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<T::AssetId, ()> {
+		LocationAssets::<T>::get(location.borrow()).ok_or(())
+	}
+
+	fn reverse_ref(asset_id: impl Borrow<T::AssetId>) -> Result<MultiLocation, ()> {
+		AssetLocations::<T>::get(asset_id.borrow()).ok_or(())
+	}
+}
+
+/// Trusts the chain a registered asset is located in as the reserve of that asset.
+pub struct IsReserveChain<T>(PhantomData<T>);
+
+impl<T: Config> FilterAssetLocation for IsReserveChain<T> {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		match &asset.id {
+			Concrete(location) if LocationAssets::<T>::contains_key(location) => {
+				reserve_chain(location).as_ref() == Some(origin)
+			}
+			_ => false,
+		}
+	}
+}
+
+/// The chain holding the asset at `location`: the sibling parachain it is located in, or else the
+/// relay chain. Assets of this chain have no remote reserve.
+pub fn reserve_chain(location: &MultiLocation) -> Option<MultiLocation> {
+	match (location.parents, location.first_interior()) {
+		(1, Some(Parachain(id))) => Some(MultiLocation::new(1, X1(Parachain(*id)))),
+		(1, _) => Some(MultiLocation::parent()),
+		_ => None,
+	}
+}
+
+/// Fee for `weight` at `units_per_second`.
+fn weight_fee(units_per_second: u128, weight: Weight) -> u128 {
+	units_per_second.saturating_mul(weight as u128) / WEIGHT_PER_SECOND as u128
+}
+
+/// Buys XCM execution with the first asset of the payment, if it is registered with a price in
+/// [`AssetUnitsPerSecond`]. The fees left once unused weight is refunded go to `R` when the trader
+/// is dropped.
+pub struct FirstAssetTrader<T: Config, R: TakeRevenue> {
+	/// Weight bought and not refunded.
+	weight: Weight,
+	/// Asset paid with, amount paid and not refunded, and the price it was paid at.
+	paid: Option<(MultiLocation, u128, u128)>,
+	_phantom: PhantomData<(T, R)>,
+}
+
+impl<T: Config, R: TakeRevenue> WeightTrader for FirstAssetTrader<T, R> {
+	fn new() -> Self {
+		Self { weight: 0, paid: None, _phantom: PhantomData }
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: xcm_executor::Assets,
+	) -> Result<xcm_executor::Assets, XcmError> {
+		let location = match payment.fungible_assets_iter().next() {
+			Some(MultiAsset { id: Concrete(location), .. }) => location,
+			_ => return Err(XcmError::TooExpensive),
+		};
+		let units_per_second = LocationAssets::<T>::get(&location)
+			.and_then(AssetUnitsPerSecond::<T>::get)
+			.ok_or(XcmError::TooExpensive)?;
+		// Refunds are made in the asset paid with, so all of the weight is bought with one asset.
+		let paid = match &self.paid {
+			Some((paid_location, _, _)) if *paid_location != location => {
+				return Err(XcmError::TooExpensive)
+			}
+			Some((_, paid, _)) => *paid,
+			None => 0,
+		};
+
+		let amount = weight_fee(units_per_second, weight);
+		let unused = payment
+			.checked_sub((location.clone(), amount).into())
+			.map_err(|_| XcmError::TooExpensive)?;
+
+		self.weight = self.weight.saturating_add(weight);
+		self.paid = Some((location, paid.saturating_add(amount), units_per_second));
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		let (location, paid, units_per_second) = self.paid.as_mut()?;
+		let weight = weight.min(self.weight);
+		let amount = weight_fee(*units_per_second, weight).min(*paid);
+
+		self.weight -= weight;
+		*paid -= amount;
+		if amount > 0 {
+			Some((location.clone(), amount).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: Config, R: TakeRevenue> Drop for FirstAssetTrader<T, R> {
+	fn drop(&mut self) {
+		if let Some((location, paid, _)) = self.paid.take() {
+			if paid > 0 {
+				R::take_revenue((location, paid).into());
+			}
+		}
+	}
+}
+
+/// Mints the fees paid in foreign assets to the `Receiver` account. Fees below the minimum balance
+/// of their asset are lost, unless the account already holds some of it.
+pub struct XcmFeesToAccount<Assets, Matcher, AccountId, Receiver>(
+	PhantomData<(Assets, Matcher, AccountId, Receiver)>,
+);
+
+impl<
+		Assets: fungibles::Mutate<AccountId>,
+		Matcher: MatchesFungibles<Assets::AssetId, Assets::Balance>,
+		AccountId,
+		Receiver: Get<AccountId>,
+	> TakeRevenue for XcmFeesToAccount<Assets, Matcher, AccountId, Receiver>
+{
+	fn take_revenue(revenue: MultiAsset) {
+		if let Ok((asset_id, amount)) = Matcher::matches_fungibles(&revenue) {
+			let _ = Assets::mint_into(asset_id, &Receiver::get(), amount);
+		}
+	}
+}
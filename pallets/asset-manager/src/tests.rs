@@ -0,0 +1,294 @@
+use super::*;
+use crate::mock::*;
+
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungibles::{metadata::Inspect as _, Inspect as _},
+};
+use sp_runtime::{DispatchError, DispatchResult};
+
+fn sibling_token() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(2000), GeneralKey(b"TOKEN".to_vec())))
+}
+
+fn metadata() -> AssetMetadata {
+	AssetMetadata { name: b"Sibling token".to_vec(), symbol: b"TOKEN".to_vec(), decimals: 12 }
+}
+
+fn register(asset_id: u32, location: MultiLocation) -> DispatchResult {
+	AssetManager::register_asset(
+		Origin::root(),
+		asset_id,
+		Box::new(location.into()),
+		metadata(),
+		1_000,
+		true,
+	)
+}
+
+#[test]
+fn register_asset_creates_it_with_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, sibling_token()));
+
+		assert_eq!(AssetManager::asset_location(1), Some(sibling_token()));
+		assert_eq!(AssetManager::location_asset(sibling_token()), Some(1));
+		assert_eq!(Assets::minimum_balance(1), 1_000);
+		assert_eq!(Assets::name(&1), b"Sibling token".to_vec());
+		assert_eq!(Assets::symbol(&1), b"TOKEN".to_vec());
+		assert_eq!(Assets::decimals(&1), 12);
+		System::assert_last_event(
+			crate::mock::Event::AssetManager(crate::Event::AssetRegistered(1, sibling_token())),
+		);
+	});
+}
+
+#[test]
+fn only_admin_registers_assets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetManager::register_asset(
+				Origin::signed(1),
+				1,
+				Box::new(sibling_token().into()),
+				metadata(),
+				1_000,
+				true,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn register_asset_twice_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, sibling_token()));
+
+		assert_noop!(register(1, MultiLocation::parent()), Error::<Test>::AssetAlreadyRegistered);
+		assert_noop!(register(2, sibling_token()), Error::<Test>::LocationAlreadyRegistered);
+	});
+}
+
+#[test]
+fn failed_asset_creation_registers_nothing() {
+	new_test_ext().execute_with(|| {
+		// `pallet_assets` refuses a zero minimum balance.
+		assert!(AssetManager::register_asset(
+			Origin::root(),
+			1,
+			Box::new(sibling_token().into()),
+			metadata(),
+			0,
+			true,
+		)
+		.is_err());
+
+		assert_eq!(AssetManager::location_asset(sibling_token()), None);
+		assert_eq!(Assets::minimum_balance(1), 0);
+	});
+}
+
+#[test]
+fn update_asset_location_moves_the_mapping() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, sibling_token()));
+		let moved = MultiLocation::new(1, X2(Parachain(2000), GeneralIndex(7)));
+
+		assert_ok!(AssetManager::update_asset_location(
+			Origin::root(),
+			1,
+			Box::new(moved.clone().into())
+		));
+
+		assert_eq!(AssetManager::asset_location(1), Some(moved.clone()));
+		assert_eq!(AssetManager::location_asset(&moved), Some(1));
+		assert_eq!(AssetManager::location_asset(sibling_token()), None);
+		System::assert_last_event(
+			crate::mock::Event::AssetManager(crate::Event::AssetLocationUpdated(1, moved)),
+		);
+	});
+}
+
+#[test]
+fn update_asset_location_checks_both_sides() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetManager::update_asset_location(
+				Origin::root(),
+				1,
+				Box::new(sibling_token().into())
+			),
+			Error::<Test>::AssetNotRegistered
+		);
+
+		assert_ok!(register(1, sibling_token()));
+		assert_ok!(register(2, MultiLocation::parent()));
+		assert_noop!(
+			AssetManager::update_asset_location(
+				Origin::root(),
+				2,
+				Box::new(sibling_token().into())
+			),
+			Error::<Test>::LocationAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn as_asset_id_converts_registered_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(1, sibling_token()));
+
+		assert_eq!(AsAssetId::<Test>::convert_ref(sibling_token()), Ok(1));
+		assert_eq!(AsAssetId::<Test>::reverse_ref(1), Ok(sibling_token()));
+		assert_eq!(AsAssetId::<Test>::convert_ref(MultiLocation::parent()), Err(()));
+		assert_eq!(AsAssetId::<Test>::reverse_ref(2), Err(()));
+	});
+}
+
+#[test]
+fn reserve_chain_of_locations() {
+	assert_eq!(
+		reserve_chain(&sibling_token()),
+		Some(MultiLocation::new(1, X1(Parachain(2000))))
+	);
+	assert_eq!(reserve_chain(&MultiLocation::parent()), Some(MultiLocation::parent()));
+	assert_eq!(reserve_chain(&MultiLocation::here()), None);
+	assert_eq!(reserve_chain(&MultiLocation::new(2, X1(Parachain(1000)))), None);
+}
+
+#[test]
+fn only_the_chain_of_a_registered_asset_is_its_reserve() {
+	new_test_ext().execute_with(|| {
+		let sibling = MultiLocation::new(1, X1(Parachain(2000)));
+		let asset: MultiAsset = (sibling_token(), 1_000u128).into();
+
+		assert!(!IsReserveChain::<Test>::filter_asset_location(&asset, &sibling));
+
+		assert_ok!(register(1, sibling_token()));
+		assert!(IsReserveChain::<Test>::filter_asset_location(&asset, &sibling));
+		assert!(!IsReserveChain::<Test>::filter_asset_location(
+			&asset,
+			&MultiLocation::new(1, X1(Parachain(2001)))
+		));
+		assert!(!IsReserveChain::<Test>::filter_asset_location(&asset, &MultiLocation::parent()));
+	});
+}
+
+#[test]
+fn set_asset_units_per_second_needs_a_registered_asset() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetManager::set_asset_units_per_second(Origin::root(), 1, 1_000),
+			Error::<Test>::AssetNotRegistered
+		);
+
+		assert_ok!(register(1, sibling_token()));
+		assert_noop!(
+			AssetManager::set_asset_units_per_second(Origin::signed(1), 1, 1_000),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AssetManager::set_asset_units_per_second(Origin::root(), 1, 1_000));
+
+		assert_eq!(AssetManager::units_per_second(1), Some(1_000));
+		System::assert_last_event(crate::mock::Event::AssetManager(
+			crate::Event::UnitsPerSecondChanged(1, 1_000),
+		));
+	});
+}
+
+fn payment(location: MultiLocation, amount: u128) -> xcm_executor::Assets {
+	MultiAsset::from((location, amount)).into()
+}
+
+/// Registers the sibling token, at two units per unit of weight.
+fn priced_sibling_token() {
+	assert_ok!(register(1, sibling_token()));
+	assert_ok!(AssetManager::set_asset_units_per_second(
+		Origin::root(),
+		1,
+		2 * WEIGHT_PER_SECOND as u128
+	));
+}
+
+#[test]
+fn trader_charges_registered_asset_and_refunds_unused_weight() {
+	new_test_ext().execute_with(|| {
+		priced_sibling_token();
+
+		let mut trader = Trader::new();
+		let unused = trader.buy_weight(1_000_000, payment(sibling_token(), 5_000_000));
+		assert_eq!(unused, Ok(payment(sibling_token(), 3_000_000)));
+
+		assert_eq!(trader.refund_weight(400_000), Some((sibling_token(), 800_000).into()));
+		// Weight that was never bought is not refunded.
+		assert_eq!(trader.refund_weight(1_000_000), Some((sibling_token(), 1_200_000).into()));
+		assert_eq!(trader.refund_weight(1_000_000), None);
+	});
+}
+
+#[test]
+fn trader_deposits_fees_when_dropped() {
+	new_test_ext().execute_with(|| {
+		priced_sibling_token();
+
+		let mut trader = Trader::new();
+		assert_ok!(trader.buy_weight(1_000_000, payment(sibling_token(), 2_000_000)));
+		assert_ok!(trader.buy_weight(500_000, payment(sibling_token(), 1_000_000)));
+		assert!(trader.refund_weight(400_000).is_some());
+		assert_eq!(Assets::balance(1, FeesAccount::get()), 0);
+
+		drop(trader);
+		assert_eq!(Assets::balance(1, FeesAccount::get()), 2_200_000);
+	});
+}
+
+#[test]
+fn trader_rejects_unpriced_assets() {
+	new_test_ext().execute_with(|| {
+		let mut trader = Trader::new();
+		assert_eq!(
+			trader.buy_weight(1_000_000, payment(sibling_token(), 5_000_000)),
+			Err(XcmError::TooExpensive)
+		);
+
+		// Registered, but without a price.
+		assert_ok!(register(1, sibling_token()));
+		assert_eq!(
+			trader.buy_weight(1_000_000, payment(sibling_token(), 5_000_000)),
+			Err(XcmError::TooExpensive)
+		);
+	});
+}
+
+#[test]
+fn trader_rejects_insufficient_payment() {
+	new_test_ext().execute_with(|| {
+		priced_sibling_token();
+
+		let mut trader = Trader::new();
+		assert_eq!(
+			trader.buy_weight(1_000_000, payment(sibling_token(), 1_999_999)),
+			Err(XcmError::TooExpensive)
+		);
+		assert_eq!(trader.refund_weight(1_000_000), None);
+	});
+}
+
+#[test]
+fn trader_buys_all_weight_with_one_asset() {
+	new_test_ext().execute_with(|| {
+		priced_sibling_token();
+		let other = MultiLocation::new(1, X1(GeneralIndex(42)));
+		assert_ok!(register(2, other.clone()));
+		assert_ok!(AssetManager::set_asset_units_per_second(Origin::root(), 2, 1));
+
+		let mut trader = Trader::new();
+		assert_ok!(trader.buy_weight(1_000_000, payment(sibling_token(), 2_000_000)));
+		assert_eq!(
+			trader.buy_weight(1_000_000, payment(other, 1_000_000)),
+			Err(XcmError::TooExpensive)
+		);
+	});
+}
@@ -19,12 +19,13 @@
 use crate::num::NonZeroU32;
 use crate::{Call, Config, Pallet};
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
-use frame_system::RawOrigin;
+use frame_support::traits::EnsureOrigin;
 
 benchmarks! {
 	set_eligible {
 		let count = NonZeroU32::new_unchecked(34);
-	}: _(RawOrigin::Root, count.clone())
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, count.clone())
 	verify {
 		assert_eq!(Pallet::<T>::eligible_count(), count);
 	}
@@ -73,6 +73,8 @@ pub mod pallet {
 		/// A source for the complete set of potential authors.
 		/// The starting point of the filtering.
 		type PotentialAuthors: Get<Vec<Self::AccountId>>;
+		/// Origin allowed to change the number of eligible authors.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -153,7 +155,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			new: EligibilityValue,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
 			EligibleCount::<T>::put(&new);
 			<Pallet<T>>::deposit_event(Event::EligibleUpdated(new));
 
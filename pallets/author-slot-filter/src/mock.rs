@@ -81,6 +81,7 @@ impl pallet_testing::Config for Test {
 	type Event = Event;
 	type RandomnessSource = TestRandomness<Self>;
 	type PotentialAuthors = Authors;
+	type UpdateOrigin = frame_system::EnsureRoot<u64>;
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,159 @@
+//! Governance-tunable parameters for `pallet_base_fee`.
+//!
+//! `pallet_base_fee` reads its block-fullness thresholds from a `BaseFeeThreshold` type that is
+//! fixed at compile time, and only allows `Root` to change the elasticity. This pallet stores the
+//! thresholds on-chain so they can be changed without a runtime upgrade, and lets a configurable
+//! governance origin update both the thresholds and the elasticity.
+//!
+//! To use it, set `pallet_base_fee::Config::Threshold` to this pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{Permill, RuntimeDebug};
+
+pub use pallet::*;
+
+pub mod migration;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Block fullness bounds used by `pallet_base_fee` when adjusting the base fee.
+///
+/// Block fullness is clamped to `[lower, upper]` and rescaled to that range. The base fee
+/// increases when the rescaled fullness is above `ideal`, and decreases when it is below.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FeeThreshold {
+	pub lower: Permill,
+	pub ideal: Permill,
+	pub upper: Permill,
+}
+
+impl Default for FeeThreshold {
+	fn default() -> Self {
+		Self {
+			lower: Permill::zero(),
+			ideal: Permill::from_percent(50),
+			upper: Permill::one(),
+		}
+	}
+}
+
+impl FeeThreshold {
+	/// The base fee adjustment divides by `upper - lower`, so the range must not be empty.
+	pub fn is_valid(&self) -> bool {
+		self.lower < self.upper
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_base_fee::Config {
+		/// The overarching event type
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to change the base fee parameters.
+		type SetOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The lower threshold must be strictly below the upper threshold.
+		InvalidThreshold,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event {
+		/// The block fullness thresholds have been changed.
+		ThresholdUpdated(FeeThreshold),
+		/// The base fee elasticity has been changed.
+		ElasticityUpdated(Permill),
+	}
+
+	/// The block fullness thresholds currently in use by `pallet_base_fee`.
+	#[pallet::storage]
+	#[pallet::getter(fn threshold)]
+	pub type Threshold<T: Config> = StorageValue<_, FeeThreshold, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub threshold: FeeThreshold,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {
+				threshold: FeeThreshold::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			assert!(
+				self.threshold.is_valid(),
+				"Base fee threshold lower bound must be below the upper bound"
+			);
+			Threshold::<T>::put(self.threshold);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the block fullness thresholds used to adjust the base fee.
+		#[pallet::weight(T::DbWeight::get().writes(1).saturating_add(10_000))]
+		pub fn set_threshold(
+			origin: OriginFor<T>,
+			lower: Permill,
+			ideal: Permill,
+			upper: Permill,
+		) -> DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+
+			let threshold = FeeThreshold { lower, ideal, upper };
+			ensure!(threshold.is_valid(), Error::<T>::InvalidThreshold);
+
+			Threshold::<T>::put(threshold);
+			Self::deposit_event(Event::ThresholdUpdated(threshold));
+
+			Ok(())
+		}
+
+		/// Update the maximum relative change of the base fee between two blocks.
+		#[pallet::weight(T::DbWeight::get().writes(1).saturating_add(10_000))]
+		pub fn set_elasticity(origin: OriginFor<T>, elasticity: Permill) -> DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+
+			pallet_base_fee::Pallet::<T>::set_elasticity_inner(elasticity);
+			Self::deposit_event(Event::ElasticityUpdated(elasticity));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> pallet_base_fee::BaseFeeThreshold for Pallet<T> {
+		fn lower() -> Permill {
+			Threshold::<T>::get().lower
+		}
+		fn ideal() -> Permill {
+			Threshold::<T>::get().ideal
+		}
+		fn upper() -> Permill {
+			Threshold::<T>::get().upper
+		}
+	}
+}
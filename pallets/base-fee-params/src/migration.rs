@@ -0,0 +1,73 @@
+use core::marker::PhantomData;
+use frame_support::traits::{Get, OnRuntimeUpgrade};
+use frame_support::weights::Weight;
+use sp_core::U256;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+use super::pallet::{Config, Threshold};
+
+/// Turns on the base fee adjustment for chains that were launched with it disabled.
+///
+/// `pallet_base_fee` writes `IsActive` and `BaseFeePerGas` to storage at genesis, so changing the
+/// `IsActive` default in the runtime has no effect on a running chain. This migration flips the
+/// stored flag, re-seeds `BaseFeePerGas` with `DefaultBaseFeePerGas` if it was never set (or was
+/// driven to zero), and writes the default thresholds so they become visible in storage.
+pub struct ActivateBaseFee<T>(PhantomData<T>);
+
+impl<T> OnRuntimeUpgrade for ActivateBaseFee<T>
+where
+	T: Config,
+{
+	fn on_runtime_upgrade() -> Weight {
+		log::info!(target: "ActivateBaseFee", "starting migration");
+
+		let mut writes = 0;
+
+		if !pallet_base_fee::IsActive::<T>::get() {
+			pallet_base_fee::IsActive::<T>::put(true);
+			writes += 1;
+		}
+
+		let base_fee_per_gas = pallet_base_fee::BaseFeePerGas::<T>::get();
+		if !pallet_base_fee::BaseFeePerGas::<T>::exists() || base_fee_per_gas == U256::zero() {
+			pallet_base_fee::BaseFeePerGas::<T>::put(
+				<T as pallet_base_fee::Config>::DefaultBaseFeePerGas::get(),
+			);
+			writes += 1;
+		}
+
+		if !Threshold::<T>::exists() {
+			Threshold::<T>::put(Threshold::<T>::get());
+			writes += 1;
+		}
+
+		T::DbWeight::get().reads_writes(3, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		let base_fee_per_gas = pallet_base_fee::BaseFeePerGas::<T>::get();
+		let expected = if base_fee_per_gas == U256::zero() {
+			<T as pallet_base_fee::Config>::DefaultBaseFeePerGas::get()
+		} else {
+			base_fee_per_gas
+		};
+
+		Self::set_temp_storage(expected, "expected_base_fee_per_gas");
+
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		let expected = Self::get_temp_storage::<U256>("expected_base_fee_per_gas");
+
+		assert_eq!(expected, Some(pallet_base_fee::BaseFeePerGas::<T>::get()));
+		assert!(pallet_base_fee::IsActive::<T>::get());
+		assert!(Threshold::<T>::get().is_valid());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::mock::*;
+
+use frame_support::traits::OnRuntimeUpgrade;
+use frame_support::{assert_noop, assert_ok};
+use pallet_base_fee::BaseFeeThreshold;
+use sp_core::U256;
+use sp_runtime::DispatchError;
+
+#[test]
+fn default_threshold_matches_previous_static_values() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BaseFeeParams::lower(), Permill::zero());
+		assert_eq!(BaseFeeParams::ideal(), Permill::from_percent(50));
+		assert_eq!(BaseFeeParams::upper(), Permill::one());
+	});
+}
+
+#[test]
+fn set_threshold_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BaseFeeParams::set_threshold(
+			Origin::root(),
+			Permill::from_percent(10),
+			Permill::from_percent(50),
+			Permill::from_percent(90),
+		));
+
+		assert_eq!(BaseFeeParams::lower(), Permill::from_percent(10));
+		assert_eq!(BaseFeeParams::upper(), Permill::from_percent(90));
+	});
+}
+
+#[test]
+fn set_threshold_rejects_empty_range() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFeeParams::set_threshold(
+				Origin::root(),
+				Permill::from_percent(50),
+				Permill::from_percent(50),
+				Permill::from_percent(50),
+			),
+			Error::<Test>::InvalidThreshold
+		);
+	});
+}
+
+#[test]
+fn set_threshold_requires_set_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFeeParams::set_threshold(
+				Origin::signed(1),
+				Permill::zero(),
+				Permill::from_percent(50),
+				Permill::one(),
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_elasticity_updates_base_fee_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BaseFeeParams::set_elasticity(
+			Origin::root(),
+			Permill::from_percent(5)
+		));
+		assert_eq!(BaseFee::elasticity(), Permill::from_percent(5));
+
+		assert_noop!(
+			BaseFeeParams::set_elasticity(Origin::signed(1), Permill::from_percent(20)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn migration_activates_base_fee_and_reseeds_zero_fee() {
+	new_test_ext().execute_with(|| {
+		pallet_base_fee::IsActive::<Test>::put(false);
+		pallet_base_fee::BaseFeePerGas::<Test>::put(U256::zero());
+
+		migration::ActivateBaseFee::<Test>::on_runtime_upgrade();
+
+		assert!(BaseFee::is_active());
+		assert_eq!(
+			BaseFee::base_fee_per_gas(),
+			U256::from(1_000_000_000u64)
+		);
+		assert!(Threshold::<Test>::exists());
+	});
+}
+
+#[test]
+fn migration_keeps_existing_base_fee() {
+	new_test_ext().execute_with(|| {
+		pallet_base_fee::IsActive::<Test>::put(false);
+		pallet_base_fee::BaseFeePerGas::<Test>::put(U256::from(450_000_000_000u128));
+
+		migration::ActivateBaseFee::<Test>::on_runtime_upgrade();
+
+		assert!(BaseFee::is_active());
+		assert_eq!(
+			BaseFee::base_fee_per_gas(),
+			U256::from(450_000_000_000u128)
+		);
+	});
+}
@@ -0,0 +1,203 @@
+//! Permissioned EVM contract deployment.
+//!
+//! Keeps an on-chain allowlist of Ethereum addresses that may deploy contracts, together with a
+//! global mode. While the mode is `Open` anybody may deploy; once it is `Closed` only listed
+//! addresses may. Both are managed by a governance origin.
+//!
+//! The pallet does not hook into the EVM itself. The runtime is expected to call
+//! [`Pallet::ensure_can_deploy`] when validating contract-creation transactions, or to use the
+//! pallet as the `Contains` of the addresses that may deploy. Contracts created from inside an
+//! already running contract (`CREATE`/`CREATE2` opcodes) are not restricted.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	RuntimeDebug,
+};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Who may deploy contracts.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DeploymentMode {
+	/// Any address may deploy contracts.
+	Open,
+	/// Only addresses on the allowlist may deploy contracts.
+	Closed,
+}
+
+impl Default for DeploymentMode {
+	fn default() -> Self {
+		DeploymentMode::Open
+	}
+}
+
+/// Custom `InvalidTransaction` codes returned for rejected deployments.
+///
+/// The codes start at 100 to stay clear of the ones used by `pallet_ethereum`.
+#[repr(u8)]
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum DeploymentValidityError {
+	/// The sender is not on the deployer allowlist and deployments are closed.
+	DeployerNotAllowed = 100,
+}
+
+impl From<DeploymentValidityError> for TransactionValidityError {
+	fn from(error: DeploymentValidityError) -> Self {
+		TransactionValidityError::Invalid(InvalidTransaction::Custom(error as u8))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::pallet_prelude::*;
+	use sp_core::H160;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to manage the allowlist and the deployment mode.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The address is already on the allowlist.
+		AlreadyAllowed,
+		/// The address is not on the allowlist.
+		NotAllowed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event {
+		/// The deployment mode has been changed.
+		ModeChanged(DeploymentMode),
+		/// An address has been added to the allowlist.
+		DeployerAdded(H160),
+		/// An address has been removed from the allowlist.
+		DeployerRemoved(H160),
+	}
+
+	/// Whether deployments are open to everybody or restricted to the allowlist.
+	#[pallet::storage]
+	#[pallet::getter(fn mode)]
+	pub type Mode<T: Config> = StorageValue<_, DeploymentMode, ValueQuery>;
+
+	/// Addresses that may deploy contracts while the mode is `Closed`.
+	#[pallet::storage]
+	pub type AllowedDeployers<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub mode: DeploymentMode,
+		pub deployers: Vec<H160>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {
+				mode: DeploymentMode::Open,
+				deployers: vec![],
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			Mode::<T>::put(self.mode);
+			for deployer in &self.deployers {
+				AllowedDeployers::<T>::insert(deployer, ());
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open or close contract deployment to addresses outside the allowlist.
+		#[pallet::weight(T::DbWeight::get().writes(1).saturating_add(10_000))]
+		pub fn set_mode(origin: OriginFor<T>, mode: DeploymentMode) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Mode::<T>::put(mode);
+			Self::deposit_event(Event::ModeChanged(mode));
+
+			Ok(())
+		}
+
+		/// Allow an address to deploy contracts.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1).saturating_add(10_000))]
+		pub fn add_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				!AllowedDeployers::<T>::contains_key(deployer),
+				Error::<T>::AlreadyAllowed
+			);
+
+			AllowedDeployers::<T>::insert(deployer, ());
+			Self::deposit_event(Event::DeployerAdded(deployer));
+
+			Ok(())
+		}
+
+		/// Revoke an address' permission to deploy contracts.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1).saturating_add(10_000))]
+		pub fn remove_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				AllowedDeployers::<T>::contains_key(deployer),
+				Error::<T>::NotAllowed
+			);
+
+			AllowedDeployers::<T>::remove(deployer);
+			Self::deposit_event(Event::DeployerRemoved(deployer));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `deployer` may currently deploy contracts.
+		pub fn can_deploy(deployer: &H160) -> bool {
+			match Mode::<T>::get() {
+				DeploymentMode::Open => true,
+				DeploymentMode::Closed => AllowedDeployers::<T>::contains_key(deployer),
+			}
+		}
+
+		/// Transaction validity check for contract-creation transactions sent by `deployer`.
+		pub fn ensure_can_deploy(deployer: &H160) -> Result<(), TransactionValidityError> {
+			if Self::can_deploy(deployer) {
+				Ok(())
+			} else {
+				Err(DeploymentValidityError::DeployerNotAllowed.into())
+			}
+		}
+	}
+
+	impl<T: Config> Contains<H160> for Pallet<T> {
+		fn contains(deployer: &H160) -> bool {
+			Self::can_deploy(deployer)
+		}
+	}
+}
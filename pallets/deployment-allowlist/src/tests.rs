@@ -0,0 +1,97 @@
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok, traits::Contains};
+use sp_core::H160;
+use sp_runtime::DispatchError;
+
+fn deployer() -> H160 {
+	H160::repeat_byte(0xaa)
+}
+
+#[test]
+fn anybody_can_deploy_while_open() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(DeploymentAllowlist::mode(), DeploymentMode::Open);
+		assert!(DeploymentAllowlist::can_deploy(&deployer()));
+		assert_ok!(DeploymentAllowlist::ensure_can_deploy(&deployer()));
+	});
+}
+
+#[test]
+fn only_allowlisted_can_deploy_while_closed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::set_mode(
+			Origin::root(),
+			DeploymentMode::Closed
+		));
+		assert_eq!(
+			DeploymentAllowlist::ensure_can_deploy(&deployer()),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				DeploymentValidityError::DeployerNotAllowed as u8
+			)))
+		);
+
+		assert_ok!(DeploymentAllowlist::add_deployer(Origin::root(), deployer()));
+		assert_ok!(DeploymentAllowlist::ensure_can_deploy(&deployer()));
+		assert!(!DeploymentAllowlist::can_deploy(&H160::repeat_byte(0xbb)));
+
+		assert_ok!(DeploymentAllowlist::remove_deployer(
+			Origin::root(),
+			deployer()
+		));
+		assert!(!DeploymentAllowlist::can_deploy(&deployer()));
+	});
+}
+
+#[test]
+fn add_deployer_twice_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::add_deployer(Origin::root(), deployer()));
+		assert_noop!(
+			DeploymentAllowlist::add_deployer(Origin::root(), deployer()),
+			Error::<Test>::AlreadyAllowed
+		);
+	});
+}
+
+#[test]
+fn remove_unknown_deployer_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			DeploymentAllowlist::remove_deployer(Origin::root(), deployer()),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn management_requires_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			DeploymentAllowlist::set_mode(Origin::signed(1), DeploymentMode::Closed),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::add_deployer(Origin::signed(1), deployer()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			DeploymentAllowlist::remove_deployer(Origin::signed(1), deployer()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn contains_the_addresses_that_may_deploy() {
+	new_test_ext().execute_with(|| {
+		assert!(<DeploymentAllowlist as Contains<H160>>::contains(&deployer()));
+
+		assert_ok!(DeploymentAllowlist::set_mode(Origin::root(), DeploymentMode::Closed));
+		assert!(!<DeploymentAllowlist as Contains<H160>>::contains(&deployer()));
+
+		assert_ok!(DeploymentAllowlist::add_deployer(Origin::root(), deployer()));
+		assert!(<DeploymentAllowlist as Contains<H160>>::contains(&deployer()));
+	});
+}
@@ -0,0 +1,232 @@
+//! Ethereum transactions sent by other chains through XCM `Transact`.
+//!
+//! `pallet_ethereum::transact` only executes signed Ethereum transactions, which a chain sending
+//! an XCM message cannot produce for its accounts. This pallet executes an
+//! [`EthereumXcmTransaction`] from the address the XCM origin derives to, as the runtime resolves
+//! it with `XcmEthereumOrigin`:
+//!
+//! 1. [`Pallet::transact`] sends the transaction from that address;
+//! 2. [`Pallet::transact_through_proxy`] sends it from an address that one is a proxy of.
+//!
+//! The transaction is executed through `pallet_ethereum`, so it is part of the Ethereum block,
+//! emits `pallet_ethereum::Event::Executed` and its receipt is served by
+//! `eth_getTransactionReceipt` as the one of any other transaction. Its sender pays for its gas
+//! as for a signed transaction.
+//!
+//! The transaction carries no signature. Its nonce is the one of its sender when it is executed,
+//! which the EVM then increments, so it neither needs nor consumes a nonce from the sending
+//! chain. Its signature is replaced by the address of its sender, so the hashes of the
+//! transactions of different senders never collide.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use ethereum::{AccessListItem, EIP1559Transaction, TransactionAction, TransactionV2};
+use frame_support::{traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Maximum size of the input of an [`EthereumXcmTransaction`], in bytes.
+pub const MAX_ETHEREUM_XCM_INPUT_SIZE: u32 = 65_536;
+
+/// An Ethereum transaction sent through XCM, without the fields its sender determines.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum EthereumXcmTransaction {
+	V1(EthereumXcmTransactionV1),
+}
+
+/// Version 1 of [`EthereumXcmTransaction`], executed as an EIP-1559 transaction.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EthereumXcmTransactionV1 {
+	/// Gas the transaction may use.
+	pub gas_limit: U256,
+	/// Fee per gas the sender pays at most. `None` pays the base fee of the block.
+	pub max_fee_per_gas: Option<U256>,
+	/// Contract called, or `Create` to deploy `input` as init code.
+	pub action: TransactionAction,
+	/// Value transferred by the transaction.
+	pub value: U256,
+	/// Call data or init code.
+	pub input: BoundedVec<u8, ConstU32<MAX_ETHEREUM_XCM_INPUT_SIZE>>,
+	/// Addresses and storage keys the transaction plans to access, as of EIP-2930.
+	pub access_list: Vec<(H160, Vec<H256>)>,
+}
+
+impl EthereumXcmTransaction {
+	/// Gas the transaction may use.
+	pub fn gas_limit(&self) -> U256 {
+		match self {
+			Self::V1(transaction) => transaction.gas_limit,
+		}
+	}
+
+	/// Whether the transaction deploys a contract.
+	pub fn is_create(&self) -> bool {
+		match self {
+			Self::V1(transaction) => transaction.action == TransactionAction::Create,
+		}
+	}
+
+	/// The Ethereum transaction `source` sends with `nonce` on chain `chain_id`, paying
+	/// `base_fee` per gas unless the transaction sets its fee.
+	pub fn into_transaction(
+		self,
+		source: H160,
+		nonce: U256,
+		chain_id: u64,
+		base_fee: U256,
+	) -> TransactionV2 {
+		match self {
+			Self::V1(transaction) => TransactionV2::EIP1559(EIP1559Transaction {
+				chain_id,
+				nonce,
+				max_priority_fee_per_gas: U256::zero(),
+				max_fee_per_gas: transaction.max_fee_per_gas.unwrap_or(base_fee),
+				gas_limit: transaction.gas_limit,
+				action: transaction.action,
+				value: transaction.value,
+				input: transaction.input.into_inner(),
+				access_list: transaction
+					.access_list
+					.into_iter()
+					.map(|(address, slots)| AccessListItem { address, slots })
+					.collect(),
+				odd_y_parity: false,
+				r: H256::from(source),
+				s: H256::from_low_u64_be(1),
+			}),
+		}
+	}
+}
+
+/// Tells whether an account may send transactions on behalf of another.
+pub trait ProxyCheck<AccountId> {
+	/// Whether `delegate` may send any transaction on behalf of `real` without delay.
+	fn is_proxy(real: &AccountId, delegate: &AccountId) -> bool;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::pallet_prelude::*;
+	use pallet_evm::{AddressMapping, FeeCalculator, GasWeightMapping};
+	use sp_runtime::traits::UniqueSaturatedInto;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_ethereum::Config {
+		/// Origin allowed to send transactions, resolving to the address they are sent from.
+		type XcmEthereumOrigin: EnsureOrigin<Self::Origin, Success = H160>;
+		/// Tells which addresses may send transactions on behalf of which.
+		type Proxies: ProxyCheck<Self::AccountId>;
+		/// Addresses that may deploy contracts.
+		type Deployers: Contains<H160>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The gas limit of the transaction is above the one of a block.
+		GasLimitTooHigh,
+		/// The origin is not a proxy of the account to transact as.
+		NotProxy,
+		/// The sender may not deploy contracts.
+		DeployerNotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		OriginFor<T>: From<pallet_ethereum::RawOrigin>
+			+ Into<Result<pallet_ethereum::RawOrigin, OriginFor<T>>>,
+	{
+		/// Execute `xcm_transaction` from the address of the origin.
+		#[pallet::weight(Pallet::<T>::weight_of(xcm_transaction).saturating_add(
+			T::DbWeight::get().reads(1)
+		))]
+		pub fn transact(
+			origin: OriginFor<T>,
+			xcm_transaction: EthereumXcmTransaction,
+		) -> DispatchResultWithPostInfo {
+			let source = T::XcmEthereumOrigin::ensure_origin(origin)?;
+			Self::apply(source, xcm_transaction)
+		}
+
+		/// Execute `xcm_transaction` from `transact_as`, which the address of the origin must be
+		/// a proxy of.
+		#[pallet::weight(Pallet::<T>::weight_of(xcm_transaction).saturating_add(
+			T::DbWeight::get().reads(2)
+		))]
+		pub fn transact_through_proxy(
+			origin: OriginFor<T>,
+			transact_as: H160,
+			xcm_transaction: EthereumXcmTransaction,
+		) -> DispatchResultWithPostInfo {
+			let delegate = T::XcmEthereumOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::Proxies::is_proxy(
+					&T::AddressMapping::into_account_id(transact_as),
+					&T::AddressMapping::into_account_id(delegate),
+				),
+				Error::<T>::NotProxy
+			);
+			Self::apply(transact_as, xcm_transaction)
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		OriginFor<T>: From<pallet_ethereum::RawOrigin>
+			+ Into<Result<pallet_ethereum::RawOrigin, OriginFor<T>>>,
+	{
+		/// Executes `xcm_transaction` from `source` as `pallet_ethereum` executes the signed
+		/// transactions of `source`.
+		fn apply(
+			source: H160,
+			xcm_transaction: EthereumXcmTransaction,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				xcm_transaction.gas_limit() <= T::BlockGasLimit::get(),
+				Error::<T>::GasLimitTooHigh
+			);
+			ensure!(
+				!xcm_transaction.is_create() || T::Deployers::contains(&source),
+				Error::<T>::DeployerNotAllowed
+			);
+
+			let (account, _) = pallet_evm::Pallet::<T>::account_basic(&source);
+			let (base_fee, _) = T::FeeCalculator::min_gas_price();
+			let transaction = xcm_transaction.into_transaction(
+				source,
+				account.nonce,
+				T::ChainId::get(),
+				base_fee,
+			);
+
+			pallet_ethereum::Pallet::<T>::transact(
+				pallet_ethereum::RawOrigin::EthereumTransaction(source).into(),
+				transaction,
+			)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Weight of executing `xcm_transaction`, assuming it uses all of its gas.
+		pub fn weight_of(xcm_transaction: &EthereumXcmTransaction) -> Weight {
+			T::GasWeightMapping::gas_to_weight(
+				xcm_transaction.gas_limit().unique_saturated_into(),
+			)
+		}
+	}
+}
@@ -0,0 +1,171 @@
+use crate as pallet_ethereum_xcm;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Hooks, IsInVec},
+	weights::Weight,
+};
+use frame_system::EnsureSigned;
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, FeeCalculator, IdentityAddressMapping};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type Balance = u128;
+
+pub const BASE_FEE: u128 = 1_000;
+pub const INITIAL_BALANCE: Balance = 1_000_000_000_000_000_000;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		Evm: pallet_evm::{Pallet, Call, Storage, Event<T>},
+		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Origin},
+		EthereumXcm: pallet_ethereum_xcm::{Pallet, Call},
+	}
+);
+
+pub fn alice() -> H160 {
+	H160::repeat_byte(0xaa)
+}
+
+pub fn bob() -> H160 {
+	H160::repeat_byte(0xbb)
+}
+
+pub fn charlie() -> H160 {
+	H160::repeat_byte(0xcc)
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = H160;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+pub struct FixedBaseFee;
+impl FeeCalculator for FixedBaseFee {
+	fn min_gas_price() -> (U256, Weight) {
+		(BASE_FEE.into(), 0)
+	}
+}
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(10_000_000);
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = FixedBaseFee;
+	type GasWeightMapping = ();
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressRoot<H160>;
+	type WithdrawOrigin = EnsureAddressNever<H160>;
+	type AddressMapping = IdentityAddressMapping;
+	type Currency = Balances;
+	type Event = Event;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ConstU64<42>;
+	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, ()>;
+	type BlockGasLimit = BlockGasLimit;
+	type FindAuthor = ();
+	type WeightInfo = ();
+}
+
+impl pallet_ethereum::Config for Test {
+	type Event = Event;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
+}
+
+/// Bob may send transactions on behalf of Alice.
+pub struct Proxies;
+impl pallet_ethereum_xcm::ProxyCheck<H160> for Proxies {
+	fn is_proxy(real: &H160, delegate: &H160) -> bool {
+		*real == alice() && *delegate == bob()
+	}
+}
+
+parameter_types! {
+	pub Deployers: Vec<H160> = vec![alice()];
+}
+
+impl pallet_ethereum_xcm::Config for Test {
+	// Signed origins stand for the XCM origins the runtime derives an address for.
+	type XcmEthereumOrigin = EnsureSigned<H160>;
+	type Proxies = Proxies;
+	type Deployers = IsInVec<Deployers>;
+}
+
+/// Build genesis storage according to the mock runtime, with Alice and Bob funded.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(alice(), INITIAL_BALANCE), (bob(), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Finalizes the Ethereum block, storing its transactions, receipts and statuses.
+pub fn finalize_block() {
+	Ethereum::on_finalize(System::block_number());
+}
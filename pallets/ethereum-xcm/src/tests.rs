@@ -0,0 +1,199 @@
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn transaction(action: TransactionAction, value: u128) -> EthereumXcmTransactionV1 {
+	EthereumXcmTransactionV1 {
+		gas_limit: U256::from(100_000),
+		max_fee_per_gas: None,
+		action,
+		value: U256::from(value),
+		input: Default::default(),
+		access_list: Vec::new(),
+	}
+}
+
+fn transfer_to_charlie(value: u128) -> EthereumXcmTransaction {
+	EthereumXcmTransaction::V1(transaction(TransactionAction::Call(charlie()), value))
+}
+
+/// A contract whose init code only stops, leaving it without code.
+fn create() -> EthereumXcmTransaction {
+	EthereumXcmTransaction::V1(EthereumXcmTransactionV1 {
+		input: vec![0x00].try_into().unwrap(),
+		..transaction(TransactionAction::Create, 0)
+	})
+}
+
+fn statuses() -> Vec<fp_rpc::TransactionStatus> {
+	finalize_block();
+	Ethereum::current_transaction_statuses().unwrap_or_default()
+}
+
+fn nonce(address: H160) -> U256 {
+	Evm::account_basic(&address).0.nonce
+}
+
+#[test]
+fn transaction_is_sent_from_the_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), transfer_to_charlie(100)));
+
+		assert_eq!(Balances::free_balance(&charlie()), 100);
+		let statuses = statuses();
+		assert_eq!(statuses.len(), 1);
+		assert_eq!(statuses[0].from, alice());
+		assert_eq!(statuses[0].to, Some(charlie()));
+		assert_eq!(Ethereum::current_receipts().map(|receipts| receipts.len()), Some(1));
+	});
+}
+
+#[test]
+fn sender_pays_for_gas_at_the_base_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), transfer_to_charlie(100)));
+
+		// A plain transfer uses the intrinsic gas of a transaction.
+		assert_eq!(Balances::free_balance(&alice()), INITIAL_BALANCE - 100 - 21_000 * BASE_FEE);
+	});
+}
+
+#[test]
+fn fee_below_the_base_fee_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let underpriced = EthereumXcmTransaction::V1(EthereumXcmTransactionV1 {
+			max_fee_per_gas: Some(U256::from(BASE_FEE - 1)),
+			..transaction(TransactionAction::Call(charlie()), 100)
+		});
+
+		assert!(EthereumXcm::transact(Origin::signed(alice()), underpriced).is_err());
+		assert_eq!(Balances::free_balance(&charlie()), 0);
+		assert!(statuses().is_empty());
+	});
+}
+
+#[test]
+fn transactions_use_the_nonce_of_the_sender() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), transfer_to_charlie(100)));
+		assert_eq!(nonce(alice()), U256::one());
+
+		// The same transaction sent again is executed, with the next nonce.
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), transfer_to_charlie(100)));
+		assert_eq!(nonce(alice()), U256::from(2));
+
+		let statuses = statuses();
+		assert_eq!(statuses.len(), 2);
+		assert_ne!(statuses[0].transaction_hash, statuses[1].transaction_hash);
+	});
+}
+
+#[test]
+fn transactions_of_different_senders_have_different_hashes() {
+	new_test_ext().execute_with(|| {
+		// Both are the first transaction of their sender, so they only differ by it.
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), transfer_to_charlie(100)));
+		assert_ok!(EthereumXcm::transact(Origin::signed(bob()), transfer_to_charlie(100)));
+
+		let statuses = statuses();
+		assert_eq!((statuses[0].from, statuses[1].from), (alice(), bob()));
+		assert_ne!(statuses[0].transaction_hash, statuses[1].transaction_hash);
+	});
+}
+
+#[test]
+fn failed_transaction_is_not_included() {
+	new_test_ext().execute_with(|| {
+		assert!(EthereumXcm::transact(
+			Origin::signed(alice()),
+			transfer_to_charlie(INITIAL_BALANCE + 1)
+		)
+		.is_err());
+
+		assert_eq!(nonce(alice()), U256::zero());
+		assert!(statuses().is_empty());
+	});
+}
+
+#[test]
+fn allowed_deployer_creates_a_contract() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumXcm::transact(Origin::signed(alice()), create()));
+
+		let statuses = statuses();
+		assert_eq!(statuses[0].to, None);
+		assert!(statuses[0].contract_address.is_some());
+	});
+}
+
+#[test]
+fn other_deployers_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EthereumXcm::transact(Origin::signed(bob()), create()),
+			Error::<Test>::DeployerNotAllowed
+		);
+	});
+}
+
+#[test]
+fn gas_limit_above_the_block_gas_limit_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let too_much_gas = EthereumXcmTransaction::V1(EthereumXcmTransactionV1 {
+			gas_limit: BlockGasLimit::get() + 1,
+			..transaction(TransactionAction::Call(charlie()), 100)
+		});
+
+		assert_noop!(
+			EthereumXcm::transact(Origin::signed(alice()), too_much_gas),
+			Error::<Test>::GasLimitTooHigh
+		);
+	});
+}
+
+#[test]
+fn proxy_transacts_on_behalf_of_the_real_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumXcm::transact_through_proxy(
+			Origin::signed(bob()),
+			alice(),
+			transfer_to_charlie(100)
+		));
+
+		// Alice sends and pays for the transaction, with her nonce.
+		assert_eq!(statuses()[0].from, alice());
+		assert_eq!(nonce(alice()), U256::one());
+		assert_eq!(nonce(bob()), U256::zero());
+		assert_eq!(Balances::free_balance(&bob()), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn only_proxies_transact_on_behalf_of_others() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EthereumXcm::transact_through_proxy(
+				Origin::signed(alice()),
+				bob(),
+				transfer_to_charlie(100)
+			),
+			Error::<Test>::NotProxy
+		);
+	});
+}
+
+#[test]
+fn transactions_require_an_xcm_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EthereumXcm::transact(Origin::root(), transfer_to_charlie(100)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			EthereumXcm::transact_through_proxy(Origin::none(), alice(), transfer_to_charlie(100)),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,26 @@
+//! Benchmarking setup for pallet-upgrade-scheduler
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::traits::{Hash, One};
+use sp_std::vec;
+
+benchmarks! {
+	cancel_upgrade {
+		let code = vec![0u8; 1024];
+		Scheduled::<T>::put(ScheduledUpgrade {
+			at: T::BlockNumber::one(),
+			code_hash: T::Hashing::hash(&code),
+			spec_version: 1,
+		});
+		ScheduledCode::<T>::put(code);
+		let origin = T::CancelOrigin::successful_origin();
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(Pallet::<T>::scheduled_upgrade().is_none());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
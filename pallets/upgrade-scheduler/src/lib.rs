@@ -0,0 +1,253 @@
+//! Runtime upgrades scheduled by governance for a future block.
+//!
+//! `frame_system::set_code` enacts an upgrade in the block it is dispatched in. This pallet puts
+//! a delay between the decision to upgrade and the upgrade itself, during which the upgrade can
+//! be cancelled, and checks the new code is a Diora runtime of a higher version both when the
+//! upgrade is scheduled and when it is enacted:
+//!
+//! 1. [`Pallet::schedule_upgrade`] stores the code and the block it is enacted at, emitting
+//!    [`Event::UpgradeScheduled`];
+//! 2. [`Pallet::cancel_upgrade`] drops it before that block, emitting
+//!    [`Event::UpgradeCancelled`];
+//! 3. from that block, any account can call [`Pallet::enact_upgrade`] to hand the code to
+//!    `frame_system`'s `OnSetCode`, emitting [`Event::UpgradeEnacted`]. If the code is not a
+//!    newer runtime anymore, the upgrade is dropped with [`Event::UpgradeFailed`].
+//!
+//! On a parachain `OnSetCode` is `cumulus_pallet_parachain_system`, which then waits for the
+//! relay chain to apply the new validation code. It needs the validation data of the block, which
+//! its `set_validation_data` inherent only provides after the block is initialized, so the code
+//! cannot be set from `on_initialize`. As for `enact_authorized_upgrade`, the upgrade is enacted
+//! by an extrinsic, which is free when it succeeds.
+//!
+//! With the `try-runtime` feature, the pallet checks on runtime upgrades that an upgrade still
+//! scheduled remains valid under the new runtime. Its `post_upgrade` hook is not run by the pallet
+//! itself: the scheduled code is a wasm blob the running runtime cannot execute, so its
+//! migrations can only be tried by the node. `try-runtime on-runtime-upgrade`, given the
+//! scheduled code, runs `Executive::try_runtime_upgrade` of that code against the chain state,
+//! which calls the `pre_upgrade` and `post_upgrade` checks of the migrations the new runtime
+//! registers (such as `EligibleRatioToEligiblityCount`) together with those of every pallet,
+//! including this one.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// An upgrade waiting for its block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ScheduledUpgrade<BlockNumber, Hash> {
+	/// Block the upgrade can be enacted from.
+	pub at: BlockNumber,
+	/// Hash of the new code.
+	pub code_hash: Hash,
+	/// `spec_version` of the new code.
+	pub spec_version: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::SetCode, transactional};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::vec::Vec;
+	use sp_version::RuntimeVersion;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to schedule an upgrade.
+		type ScheduleOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to cancel a scheduled upgrade.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+		/// Minimum number of blocks between scheduling an upgrade and enacting it.
+		#[pallet::constant]
+		type MinimumDelay: Get<Self::BlockNumber>;
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The runtime version of the code cannot be read.
+		InvalidCode,
+		/// The `spec_name` of the code is not the one of the running runtime.
+		InvalidSpecName,
+		/// The `spec_version` of the code is not above the one of the running runtime.
+		SpecVersionNotIncreased,
+		/// The upgrade would be enacted before `MinimumDelay` blocks.
+		TooEarly,
+		/// An upgrade is already scheduled.
+		AlreadyScheduled,
+		/// No upgrade is scheduled.
+		NotScheduled,
+		/// The block of the scheduled upgrade is not reached yet.
+		NotDue,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An upgrade to the code of `code_hash` is scheduled from block `at`.
+		UpgradeScheduled { code_hash: T::Hash, at: T::BlockNumber, spec_version: u32 },
+		/// The scheduled upgrade was cancelled.
+		UpgradeCancelled { code_hash: T::Hash },
+		/// The code of the scheduled upgrade was set.
+		UpgradeEnacted { code_hash: T::Hash, spec_version: u32 },
+		/// The code of the scheduled upgrade was not a newer runtime anymore when it was enacted.
+		UpgradeFailed { code_hash: T::Hash, error: DispatchError },
+	}
+
+	/// The upgrade waiting for its block, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_upgrade)]
+	pub type Scheduled<T: Config> =
+		StorageValue<_, ScheduledUpgrade<T::BlockNumber, T::Hash>, OptionQuery>;
+
+	/// The code of the scheduled upgrade.
+	#[pallet::storage]
+	pub type ScheduledCode<T: Config> = StorageValue<_, Vec<u8>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			if let Some(upgrade) = Scheduled::<T>::get() {
+				let code = ScheduledCode::<T>::get();
+				if T::Hashing::hash(&code) != upgrade.code_hash {
+					return Err("scheduled code does not match its hash");
+				}
+				let version = Self::check_code(&code).map_err(|_| {
+					"scheduled upgrade is not valid for the new runtime anymore"
+				})?;
+				log::info!(
+					target: "upgrade-scheduler",
+					"upgrade to spec version {} still scheduled at {:?}",
+					version.spec_version,
+					upgrade.at,
+				);
+			}
+			Ok(())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule an upgrade to `code`, which can be enacted from block `at`.
+		///
+		/// Reading the version of the code takes as long as instantiating it, so this takes a
+		/// whole block as `set_code` does.
+		#[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+		pub fn schedule_upgrade(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::ScheduleOrigin::ensure_origin(origin)?;
+			ensure!(!Scheduled::<T>::exists(), Error::<T>::AlreadyScheduled);
+			let earliest =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::MinimumDelay::get());
+			ensure!(at >= earliest, Error::<T>::TooEarly);
+
+			let version = Self::check_code(&code)?;
+			let code_hash = T::Hashing::hash(&code);
+			Scheduled::<T>::put(ScheduledUpgrade {
+				at,
+				code_hash,
+				spec_version: version.spec_version,
+			});
+			ScheduledCode::<T>::put(code);
+			Self::deposit_event(Event::UpgradeScheduled {
+				code_hash,
+				at,
+				spec_version: version.spec_version,
+			});
+
+			Ok(Pays::No.into())
+		}
+
+		/// Cancel the scheduled upgrade.
+		#[pallet::weight(T::WeightInfo::cancel_upgrade())]
+		pub fn cancel_upgrade(origin: OriginFor<T>) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let upgrade = Scheduled::<T>::take().ok_or(Error::<T>::NotScheduled)?;
+
+			ScheduledCode::<T>::kill();
+			Self::deposit_event(Event::UpgradeCancelled { code_hash: upgrade.code_hash });
+
+			Ok(())
+		}
+
+		/// Enact the scheduled upgrade, once its block is reached.
+		///
+		/// This is free if the code is set. As for `set_code`, it takes a whole block.
+		#[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+		#[transactional]
+		pub fn enact_upgrade(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let upgrade = Scheduled::<T>::get().ok_or(Error::<T>::NotScheduled)?;
+			ensure!(upgrade.at <= frame_system::Pallet::<T>::block_number(), Error::<T>::NotDue);
+
+			Scheduled::<T>::kill();
+			let code = ScheduledCode::<T>::take();
+			let code_hash = upgrade.code_hash;
+
+			// The running runtime may have been upgraded in the meantime, in which case the
+			// upgrade is dropped. Other errors, such as an upgrade of the relay chain validation
+			// code still pending, leave it scheduled.
+			if let Err(error) = Self::check_code(&code) {
+				log::warn!(
+					target: "upgrade-scheduler",
+					"scheduled upgrade {:?} failed: {:?}",
+					code_hash,
+					error,
+				);
+				Self::deposit_event(Event::UpgradeFailed { code_hash, error });
+				return Ok(().into());
+			}
+			T::OnSetCode::set_code(code)?;
+			Self::deposit_event(Event::UpgradeEnacted {
+				code_hash,
+				spec_version: upgrade.spec_version,
+			});
+
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks `code` is a runtime with the `spec_name` of the running runtime and a higher
+		/// `spec_version`, and returns its version.
+		pub fn check_code(code: &[u8]) -> Result<RuntimeVersion, DispatchError> {
+			let version = sp_io::misc::runtime_version(code)
+				.and_then(|version| RuntimeVersion::decode(&mut &version[..]).ok())
+				.ok_or(Error::<T>::InvalidCode)?;
+			let current = T::Version::get();
+
+			ensure!(version.spec_name == current.spec_name, Error::<T>::InvalidSpecName);
+			ensure!(
+				version.spec_version > current.spec_version,
+				Error::<T>::SpecVersionNotIncreased
+			);
+			Ok(version)
+		}
+	}
+}
@@ -0,0 +1,111 @@
+use crate as pallet_upgrade_scheduler;
+use codec::Encode;
+use frame_support::parameter_types;
+use frame_support::traits::{ConstU32, ConstU64, Everything};
+use frame_system::EnsureRoot;
+use sp_core::{
+	traits::{Externalities, ReadRuntimeVersion, ReadRuntimeVersionExt},
+	H256,
+};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_version::RuntimeVersion;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		UpgradeScheduler: pallet_upgrade_scheduler::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+/// Version of a runtime named `spec_name`.
+pub fn runtime_version(spec_name: &'static str, spec_version: u32) -> RuntimeVersion {
+	RuntimeVersion {
+		spec_name: spec_name.into(),
+		impl_name: spec_name.into(),
+		authoring_version: 1,
+		spec_version,
+		impl_version: 1,
+		apis: Default::default(),
+		transaction_version: 1,
+		state_version: 1,
+	}
+}
+
+/// Code of a runtime named `spec_name`, which the mock reads as its version.
+pub fn code(spec_name: &'static str, spec_version: u32) -> Vec<u8> {
+	runtime_version(spec_name, spec_version).encode()
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub storage SpecVersion: u32 = 1;
+	pub Version: RuntimeVersion = runtime_version("diora-parachain", SpecVersion::get());
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = Version;
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_upgrade_scheduler::Config for Test {
+	type Event = Event;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type CancelOrigin = EnsureRoot<u64>;
+	type MinimumDelay = ConstU64<3>;
+	type WeightInfo = ();
+}
+
+/// Reads the code as an encoded runtime version, in place of instantiating it.
+struct CodeIsVersion;
+impl ReadRuntimeVersion for CodeIsVersion {
+	fn read_runtime_version(
+		&self,
+		wasm_code: &[u8],
+		_ext: &mut dyn Externalities,
+	) -> Result<Vec<u8>, String> {
+		Ok(wasm_code.to_vec())
+	}
+}
+
+/// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap()
+		.into();
+	ext.register_extension(ReadRuntimeVersionExt::new(CodeIsVersion));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,173 @@
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::{storage::well_known_keys, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+fn last_event() -> Event<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			crate::mock::Event::UpgradeScheduler(event) => Some(event),
+			_ => None,
+		})
+		.last()
+		.expect("an event was deposited")
+}
+
+fn hash(code: &[u8]) -> H256 {
+	BlakeTwo256::hash(code)
+}
+
+fn stored_code() -> Option<Vec<u8>> {
+	sp_io::storage::get(well_known_keys::CODE).map(|code| code.to_vec())
+}
+
+#[test]
+fn upgrade_is_enacted_at_its_block() {
+	new_test_ext().execute_with(|| {
+		let code = code("diora-parachain", 2);
+		assert_ok!(UpgradeScheduler::schedule_upgrade(Origin::root(), code.clone(), 5));
+		assert_eq!(
+			last_event(),
+			Event::UpgradeScheduled { code_hash: hash(&code), at: 5, spec_version: 2 }
+		);
+
+		System::set_block_number(4);
+		assert_noop!(UpgradeScheduler::enact_upgrade(Origin::signed(1)), Error::<Test>::NotDue);
+		assert_eq!(stored_code(), None);
+
+		System::set_block_number(5);
+		assert_ok!(UpgradeScheduler::enact_upgrade(Origin::signed(1)));
+		assert_eq!(stored_code(), Some(code.clone()));
+		assert_eq!(last_event(), Event::UpgradeEnacted { code_hash: hash(&code), spec_version: 2 });
+		assert_eq!(UpgradeScheduler::scheduled_upgrade(), None);
+		assert!(ScheduledCode::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn code_must_be_a_newer_diora_runtime() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::root(), code("other-parachain", 2), 5),
+			Error::<Test>::InvalidSpecName
+		);
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::root(), code("diora-parachain", 1), 5),
+			Error::<Test>::SpecVersionNotIncreased
+		);
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::root(), vec![1, 2, 3], 5),
+			Error::<Test>::InvalidCode
+		);
+	});
+}
+
+#[test]
+fn upgrade_must_respect_minimum_delay() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::root(), code("diora-parachain", 2), 3),
+			Error::<Test>::TooEarly
+		);
+		assert_ok!(UpgradeScheduler::schedule_upgrade(
+			Origin::root(),
+			code("diora-parachain", 2),
+			4
+		));
+	});
+}
+
+#[test]
+fn only_one_upgrade_is_scheduled_at_a_time() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(UpgradeScheduler::schedule_upgrade(
+			Origin::root(),
+			code("diora-parachain", 2),
+			5
+		));
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::root(), code("diora-parachain", 3), 6),
+			Error::<Test>::AlreadyScheduled
+		);
+	});
+}
+
+#[test]
+fn only_governance_schedules_and_cancels() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpgradeScheduler::schedule_upgrade(Origin::signed(1), code("diora-parachain", 2), 5),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(UpgradeScheduler::schedule_upgrade(
+			Origin::root(),
+			code("diora-parachain", 2),
+			5
+		));
+		assert_noop!(UpgradeScheduler::cancel_upgrade(Origin::signed(1)), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn cancelled_upgrade_is_not_enacted() {
+	new_test_ext().execute_with(|| {
+		let code = code("diora-parachain", 2);
+		assert_ok!(UpgradeScheduler::schedule_upgrade(Origin::root(), code.clone(), 5));
+
+		assert_ok!(UpgradeScheduler::cancel_upgrade(Origin::root()));
+		assert_eq!(last_event(), Event::UpgradeCancelled { code_hash: hash(&code) });
+
+		System::set_block_number(5);
+		assert_noop!(
+			UpgradeScheduler::enact_upgrade(Origin::signed(1)),
+			Error::<Test>::NotScheduled
+		);
+		assert_eq!(stored_code(), None);
+		assert_noop!(UpgradeScheduler::cancel_upgrade(Origin::root()), Error::<Test>::NotScheduled);
+	});
+}
+
+#[test]
+fn upgrade_fails_if_runtime_was_upgraded_meanwhile() {
+	new_test_ext().execute_with(|| {
+		let code = code("diora-parachain", 2);
+		assert_ok!(UpgradeScheduler::schedule_upgrade(Origin::root(), code.clone(), 5));
+
+		SpecVersion::set(&2);
+		System::set_block_number(5);
+		assert_ok!(UpgradeScheduler::enact_upgrade(Origin::signed(1)));
+
+		assert_eq!(stored_code(), None);
+		assert_eq!(
+			last_event(),
+			Event::UpgradeFailed {
+				code_hash: hash(&code),
+				error: Error::<Test>::SpecVersionNotIncreased.into(),
+			}
+		);
+		assert_eq!(UpgradeScheduler::scheduled_upgrade(), None);
+	});
+}
+
+#[test]
+fn upgrade_is_enacted_by_a_signed_extrinsic() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(UpgradeScheduler::schedule_upgrade(
+			Origin::root(),
+			code("diora-parachain", 2),
+			5
+		));
+		System::set_block_number(5);
+
+		assert_noop!(UpgradeScheduler::enact_upgrade(Origin::root()), DispatchError::BadOrigin);
+		assert_noop!(UpgradeScheduler::enact_upgrade(Origin::none()), DispatchError::BadOrigin);
+		let result = UpgradeScheduler::enact_upgrade(Origin::signed(1));
+		assert_eq!(result.map(|info| info.pays_fee), Ok(frame_support::weights::Pays::No));
+	});
+}
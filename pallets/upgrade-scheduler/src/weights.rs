@@ -0,0 +1,36 @@
+//! Weights for pallet_upgrade_scheduler.
+//!
+//! Scheduling and enacting an upgrade take a whole block, as `set_code` does, so only cancelling
+//! one is weighed here. The pallet has not been benchmarked on reference hardware yet, so the
+//! weight only accounts for the storage it accesses; `benchmarking.rs` measures the rest.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_upgrade_scheduler.
+pub trait WeightInfo {
+	fn cancel_upgrade() -> Weight;
+}
+
+/// Weights for pallet_upgrade_scheduler using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: UpgradeScheduler Scheduled (r:1 w:1)
+	// Storage: UpgradeScheduler ScheduledCode (r:0 w:1)
+	fn cancel_upgrade() -> Weight {
+		T::DbWeight::get().reads_writes(1 as Weight, 2 as Weight)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn cancel_upgrade() -> Weight {
+		RocksDbWeight::get().reads_writes(1 as Weight, 2 as Weight)
+	}
+}
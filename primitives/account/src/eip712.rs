@@ -0,0 +1,234 @@
+//! EIP-712 typed data of Substrate signed payloads.
+//!
+//! A signed extrinsic authorizes a payload made of the SCALE-encoded call, `SignedExtra` and the
+//! data the extensions add to the signature (spec version, genesis hash, ...). Wallets sign its
+//! fields as the typed data:
+//!
+//! ```json
+//! {
+//!   "types": {
+//!     "EIP712Domain": [
+//!       { "name": "name", "type": "string" },
+//!       { "name": "version", "type": "string" }
+//!     ],
+//!     "SubstrateTransaction": [
+//!       { "name": "palletIndex", "type": "uint8" },
+//!       { "name": "callIndex", "type": "uint8" },
+//!       { "name": "arguments", "type": "bytes" },
+//!       { "name": "nonce", "type": "uint32" },
+//!       { "name": "eraPeriod", "type": "uint64" },
+//!       { "name": "eraPhase", "type": "uint64" },
+//!       { "name": "tip", "type": "uint128" },
+//!       { "name": "specVersion", "type": "uint32" },
+//!       { "name": "transactionVersion", "type": "uint32" },
+//!       { "name": "genesisHash", "type": "bytes32" },
+//!       { "name": "blockHash", "type": "bytes32" }
+//!     ]
+//!   },
+//!   "primaryType": "SubstrateTransaction",
+//!   "domain": { "name": "Diora", "version": "1" },
+//!   "message": { "palletIndex": 10, "callIndex": 0, "arguments": "0x...", "nonce": 0, ... }
+//! }
+//! ```
+//!
+//! `arguments` are the SCALE-encoded arguments of the call, and an immortal transaction has a
+//! zero `eraPeriod` and `eraPhase`. The signed payload itself tells whether it was signed as
+//! typed data, through a signed extension of the runtime, which splits such payloads into these
+//! fields through [`DecodeTransaction`]. The signature keeps its 65-byte encoding either way.
+//! Payloads longer than 256 bytes only reach the signature as their blake2-256 hash and cannot
+//! be split, so they are always signed through their keccak-256 hash.
+//!
+//! The domain has no `chainId`: the genesis hash already prevents replays on other chains, and
+//! the EVM chain id is not known outside of the runtime.
+
+use sha3::{Digest, Keccak256};
+use sp_std::vec::Vec;
+
+/// Name of the signing domain.
+pub const DOMAIN_NAME: &[u8] = b"Diora";
+/// Version of the signing domain.
+pub const DOMAIN_VERSION: &[u8] = b"1";
+
+const DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version)";
+const TRANSACTION_TYPE: &[u8] = b"SubstrateTransaction(uint8 palletIndex,uint8 callIndex,\
+	bytes arguments,uint32 nonce,uint64 eraPeriod,uint64 eraPhase,uint128 tip,uint32 specVersion,\
+	uint32 transactionVersion,bytes32 genesisHash,bytes32 blockHash)";
+
+fn keccak_256(data: &[u8]) -> [u8; 32] {
+	Keccak256::digest(data).into()
+}
+
+fn keccak_256_of(parts: &[&[u8]]) -> [u8; 32] {
+	let mut hasher = Keccak256::new();
+	for part in parts {
+		hasher.update(part);
+	}
+	hasher.finalize().into()
+}
+
+/// ABI encoding of an unsigned integer field.
+fn uint(value: impl Into<u128>) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[16..].copy_from_slice(&value.into().to_be_bytes());
+	word
+}
+
+/// `hashStruct` of the signing domain.
+pub fn domain_separator() -> [u8; 32] {
+	keccak_256_of(&[
+		&keccak_256(DOMAIN_TYPE),
+		&keccak_256(DOMAIN_NAME),
+		&keccak_256(DOMAIN_VERSION),
+	])
+}
+
+/// The fields of a signed payload, as the `SubstrateTransaction` typed data.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transaction {
+	pub pallet_index: u8,
+	pub call_index: u8,
+	/// SCALE-encoded arguments of the call.
+	pub arguments: Vec<u8>,
+	pub nonce: u32,
+	/// Period of a mortal transaction, zero for an immortal one.
+	pub era_period: u64,
+	/// Phase of a mortal transaction, zero for an immortal one.
+	pub era_phase: u64,
+	pub tip: u128,
+	pub spec_version: u32,
+	pub transaction_version: u32,
+	pub genesis_hash: [u8; 32],
+	/// Hash of the block the era starts at, the genesis hash for an immortal transaction.
+	pub block_hash: [u8; 32],
+}
+
+impl Transaction {
+	/// `hashStruct` of the transaction.
+	pub fn hash_struct(&self) -> [u8; 32] {
+		keccak_256_of(&[
+			&keccak_256(TRANSACTION_TYPE),
+			&uint(self.pallet_index),
+			&uint(self.call_index),
+			&keccak_256(&self.arguments),
+			&uint(self.nonce),
+			&uint(self.era_period),
+			&uint(self.era_phase),
+			&uint(self.tip),
+			&uint(self.spec_version),
+			&uint(self.transaction_version),
+			&self.genesis_hash,
+			&self.block_hash,
+		])
+	}
+
+	/// The hash signed by `eth_signTypedData_v4` for the transaction.
+	pub fn signing_hash(&self) -> [u8; 32] {
+		keccak_256_of(&[b"\x19\x01", &domain_separator(), &self.hash_struct()])
+	}
+}
+
+/// Splits the signed payloads of a runtime that were signed as typed data into their fields.
+pub trait DecodeTransaction {
+	/// The fields of `payload`, or `None` if it is to be signed through its keccak-256 hash.
+	fn decode_transaction(payload: &[u8]) -> Option<Transaction>;
+}
+
+/// Every payload is signed through its keccak-256 hash.
+impl DecodeTransaction for () {
+	fn decode_transaction(_payload: &[u8]) -> Option<Transaction> {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{EthereumSignature, EthereumSigner};
+	use sp_core::{ecdsa, Pair};
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+
+	fn transaction() -> Transaction {
+		Transaction {
+			pallet_index: 10,
+			call_index: 3,
+			arguments: vec![1, 2, 3],
+			nonce: 7,
+			era_period: 64,
+			era_phase: 5,
+			tip: 100,
+			spec_version: 1,
+			transaction_version: 1,
+			genesis_hash: [1; 32],
+			block_hash: [2; 32],
+		}
+	}
+
+	/// Payloads of a runtime where `b"diora"` is signed as the typed data of `transaction()`.
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	struct Diora;
+	impl DecodeTransaction for Diora {
+		fn decode_transaction(payload: &[u8]) -> Option<Transaction> {
+			(payload == b"diora").then(transaction)
+		}
+	}
+
+	#[test]
+	fn domain_separator_matches_reference() {
+		assert_eq!(
+			hex::encode(domain_separator()),
+			"7937f209879fdcd1afa6d7505d2a5b852823b91426c5dd88025520d369121397"
+		);
+	}
+
+	#[test]
+	fn signing_hash_matches_reference() {
+		assert_eq!(
+			hex::encode(transaction().hash_struct()),
+			"54cbb5a830fb83e21293ff2074cdc0b6b3d4d521ff2a6cc428f22511d691590c"
+		);
+		assert_eq!(
+			hex::encode(transaction().signing_hash()),
+			"adca379b3e1792427cec8ebb37ba850a1b4921662c293c7d411786743698772e"
+		);
+	}
+
+	#[test]
+	fn every_field_is_signed() {
+		let reference = transaction().signing_hash();
+		let variants = [
+			Transaction { pallet_index: 11, ..transaction() },
+			Transaction { call_index: 4, ..transaction() },
+			Transaction { arguments: vec![1, 2], ..transaction() },
+			Transaction { nonce: 8, ..transaction() },
+			Transaction { era_period: 0, ..transaction() },
+			Transaction { era_phase: 0, ..transaction() },
+			Transaction { tip: 0, ..transaction() },
+			Transaction { spec_version: 2, ..transaction() },
+			Transaction { transaction_version: 2, ..transaction() },
+			Transaction { genesis_hash: [0; 32], ..transaction() },
+			Transaction { block_hash: [0; 32], ..transaction() },
+		];
+
+		for variant in variants {
+			assert_ne!(variant.signing_hash(), reference, "{:?}", variant);
+		}
+	}
+
+	#[test]
+	fn typed_data_payloads_verify_only_against_their_signing_hash() {
+		let pair = ecdsa::Pair::from_seed_slice(
+			&hex::decode("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133")
+				.unwrap(),
+		)
+		.unwrap();
+		let account = EthereumSigner::from(pair.public()).into_account();
+		let typed = pair.sign_prehashed(&transaction().signing_hash());
+		let typed = EthereumSignature::<Diora>::from(typed);
+		let keccak = pair.sign_prehashed(&sp_io::hashing::keccak_256(b"diora"));
+		let keccak = EthereumSignature::<Diora>::from(keccak);
+
+		assert!(typed.verify(&b"diora"[..], &account));
+		assert!(!typed.verify(&b"other"[..], &account));
+		assert!(!keccak.verify(&b"diora"[..], &account));
+	}
+}
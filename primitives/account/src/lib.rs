@@ -0,0 +1,282 @@
+//! Ethereum-compatible account primitives.
+//!
+//! The Diora runtime identifies accounts by their 20-byte Ethereum address and authorizes
+//! extrinsics with secp256k1 ECDSA signatures over the keccak-256 hash of the payload. This lets
+//! the same private key control an account from MetaMask (through the EVM) and from Substrate
+//! tooling, with no address hashing in between.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+pub use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use sp_core::{ecdsa, H160, H256};
+
+pub mod eip712;
+
+/// A fully Ethereum-compatible `AccountId`.
+///
+/// Conforms to H160 address and ECDSA key standards.
+#[derive(
+	Eq, PartialEq, Copy, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Default, PartialOrd, Ord,
+)]
+pub struct AccountId20(pub [u8; 20]);
+
+#[cfg(feature = "std")]
+impl_serde::impl_fixed_hash_serde!(AccountId20, 20);
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for AccountId20 {
+	/// Formats the address using the EIP-55 mixed-case checksum encoding.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let address = format!("{}", sp_core::hexdisplay::HexDisplay::from(&self.0));
+		let hash = Keccak256::digest(address.as_bytes());
+
+		let checksummed: String = address
+			.chars()
+			.enumerate()
+			.map(|(i, c)| {
+				let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+				if nibble >= 8 {
+					c.to_ascii_uppercase()
+				} else {
+					c
+				}
+			})
+			.collect();
+
+		write!(f, "0x{}", checksummed)
+	}
+}
+
+impl core::fmt::Debug for AccountId20 {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{:?}", H160(self.0))
+	}
+}
+
+impl From<[u8; 20]> for AccountId20 {
+	fn from(bytes: [u8; 20]) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<AccountId20> for [u8; 20] {
+	fn from(value: AccountId20) -> Self {
+		value.0
+	}
+}
+
+impl From<H160> for AccountId20 {
+	fn from(h160: H160) -> Self {
+		Self(h160.0)
+	}
+}
+
+impl From<AccountId20> for H160 {
+	fn from(value: AccountId20) -> Self {
+		H160(value.0)
+	}
+}
+
+impl AsRef<[u8]> for AccountId20 {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::str::FromStr for AccountId20 {
+	type Err = &'static str;
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		H160::from_str(input)
+			.map(Into::into)
+			.map_err(|_| "invalid hex address.")
+	}
+}
+
+/// An ECDSA signature of a signed payload, encoded as its 65 bytes.
+///
+/// Substrate tooling signs the keccak-256 hash of the payload. Wallets that only sign typed data,
+/// like MetaMask, sign the EIP-712 hash of its fields instead, which `P` recognizes and splits
+/// the payloads of the runtime into, see [`eip712`].
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_core::RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[scale_info(skip_type_params(P))]
+pub struct EthereumSignature<P>(ecdsa::Signature, PhantomData<P>);
+
+impl<P> From<ecdsa::Signature> for EthereumSignature<P> {
+	fn from(x: ecdsa::Signature) -> Self {
+		EthereumSignature(x, PhantomData)
+	}
+}
+
+impl<P: eip712::DecodeTransaction> sp_runtime::traits::Verify for EthereumSignature<P> {
+	type Signer = EthereumSigner;
+	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId20) -> bool {
+		let msg = msg.get();
+		let m = match P::decode_transaction(msg) {
+			Some(transaction) => transaction.signing_hash(),
+			None => {
+				let mut m = [0u8; 32];
+				m.copy_from_slice(Keccak256::digest(msg).as_slice());
+				m
+			}
+		};
+		match sp_io::crypto::secp256k1_ecdsa_recover(&self.0 .0, &m) {
+			Ok(pubkey) => {
+				AccountId20(H160::from(H256::from_slice(Keccak256::digest(&pubkey).as_slice())).0)
+					== *signer
+			}
+			Err(sp_io::EcdsaVerifyError::BadRS) => {
+				log::error!(target: "evm", "Error recovering: Incorrect value of R or S");
+				false
+			}
+			Err(sp_io::EcdsaVerifyError::BadV) => {
+				log::error!(target: "evm", "Error recovering: Incorrect value of V");
+				false
+			}
+			Err(sp_io::EcdsaVerifyError::BadSignature) => {
+				log::error!(target: "evm", "Error recovering: Invalid signature");
+				false
+			}
+		}
+	}
+}
+
+/// Public key for an Ethereum-compatible account.
+#[derive(
+	Eq, PartialEq, Ord, PartialOrd, Clone, Encode, Decode, sp_core::RuntimeDebug, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthereumSigner([u8; 20]);
+
+impl sp_runtime::traits::IdentifyAccount for EthereumSigner {
+	type AccountId = AccountId20;
+	fn into_account(self) -> AccountId20 {
+		AccountId20(self.0)
+	}
+}
+
+impl From<[u8; 20]> for EthereumSigner {
+	fn from(x: [u8; 20]) -> Self {
+		EthereumSigner(x)
+	}
+}
+
+impl From<ecdsa::Public> for EthereumSigner {
+	fn from(x: ecdsa::Public) -> Self {
+		let decompressed = libsecp256k1::PublicKey::parse_slice(
+			&x.0,
+			Some(libsecp256k1::PublicKeyFormat::Compressed),
+		)
+		.expect("Wrong compressed public key provided")
+		.serialize();
+		let mut m = [0u8; 64];
+		m.copy_from_slice(&decompressed[1..65]);
+		let account = H160::from(H256::from_slice(Keccak256::digest(&m).as_slice()));
+		EthereumSigner(account.into())
+	}
+}
+
+impl From<libsecp256k1::PublicKey> for EthereumSigner {
+	fn from(x: libsecp256k1::PublicKey) -> Self {
+		let mut m = [0u8; 64];
+		m.copy_from_slice(&x.serialize()[1..65]);
+		let account = H160::from(H256::from_slice(Keccak256::digest(&m).as_slice()));
+		EthereumSigner(account.into())
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for EthereumSigner {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(fmt, "{}", AccountId20(self.0))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::Pair;
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+
+	fn account_from_secret(secret: &str) -> AccountId20 {
+		let secret_key = hex::decode(secret).unwrap();
+		let public_key = ecdsa::Pair::from_seed_slice(&secret_key).unwrap().public();
+		EthereumSigner::from(public_key).into_account()
+	}
+
+	fn account_from_hex(address: &str) -> AccountId20 {
+		let mut bytes = [0u8; 20];
+		hex::decode_to_slice(address, &mut bytes).expect("example data is 20 bytes of valid hex");
+		AccountId20::from(bytes)
+	}
+
+	#[test]
+	fn test_account_derivation_1() {
+		// Well-known development key, as used by Hardhat and Foundry.
+		assert_eq!(
+			account_from_secret("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
+			account_from_hex("f39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+		);
+	}
+
+	#[test]
+	fn test_account_derivation_2() {
+		assert_eq!(
+			account_from_secret("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133"),
+			account_from_hex("f24ff3a9cf04c71dbc94d0b566f7a27b94566cac"),
+		);
+	}
+
+	#[test]
+	fn test_display_is_eip55_checksummed() {
+		let account = account_from_hex("f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+		assert_eq!(
+			account.to_string(),
+			"0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+		);
+	}
+
+	#[test]
+	fn test_signature_verifies_against_derived_account() {
+		let pair = ecdsa::Pair::from_seed_slice(
+			&hex::decode("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133")
+				.unwrap(),
+		)
+		.unwrap();
+		let account = EthereumSigner::from(pair.public()).into_account();
+
+		let message = b"diora";
+		let hash = Keccak256::digest(message);
+		let mut digest = [0u8; 32];
+		digest.copy_from_slice(hash.as_slice());
+		let signature = EthereumSignature::<()>::from(pair.sign_prehashed(&digest));
+
+		assert!(signature.verify(&message[..], &account));
+		assert!(!signature.verify(&b"other"[..], &account));
+		assert!(!signature.verify(&message[..], &AccountId20::default()));
+	}
+
+	#[test]
+	fn test_signature_decodes_from_its_65_bytes() {
+		let pair = ecdsa::Pair::from_seed_slice(
+			&hex::decode("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133")
+				.unwrap(),
+		)
+		.unwrap();
+		let account = EthereumSigner::from(pair.public()).into_account();
+
+		// AccountId20 tooling, like the polkadot.js `ethereum` keyring, sends the bare r, s and v.
+		let message = b"diora";
+		let raw: [u8; 65] = pair.sign_prehashed(&sp_io::hashing::keccak_256(message)).0;
+		let signature = EthereumSignature::<()>::decode(&mut &raw[..]).unwrap();
+
+		assert!(signature.verify(&message[..], &account));
+		assert_eq!(signature.encode(), raw.to_vec());
+	}
+}
@@ -0,0 +1,240 @@
+//! EVM transaction tracing.
+//!
+//! Tracing works by re-executing a block on top of its parent state, up to and including the
+//! traced transaction, while listening to the hooks of the EVM interpreter. This crate provides
+//! the pieces shared by the runtime and the node:
+//!
+//! - the trace formats, serialized as Geth does for its `callTracer` and default struct logger,
+//! - the tracers turning EVM events into those formats (behind the `tracing` feature, which
+//!   compiles the EVM with its hooks),
+//! - the [`DebugRuntimeApi`] the node calls to run a trace.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::Serialize;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Block as BlockT, DispatchError, RuntimeDebug};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+mod opcodes;
+#[cfg(feature = "std")]
+mod serialization;
+#[cfg(feature = "tracing")]
+pub mod tracer;
+
+pub use opcodes::opcode_name;
+
+/// The kind of trace to produce.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum TraceType {
+	/// The tree of calls made by the transaction, as Geth's `callTracer`.
+	CallTracer,
+	/// Every executed opcode, as Geth's default struct logger.
+	StructLogger {
+		disable_storage: bool,
+		disable_memory: bool,
+		disable_stack: bool,
+	},
+}
+
+/// Kind of EVM call frame.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "UPPERCASE"))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+/// A call frame of the `callTracer` output.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CallFrame {
+	#[cfg_attr(feature = "std", serde(rename = "type"))]
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::bytes_0x"))]
+	pub input: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::bytes_0x"))]
+	pub output: Vec<u8>,
+	#[cfg_attr(
+		feature = "std",
+		serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "serialization::option_string"
+		)
+	)]
+	pub error: Option<Vec<u8>>,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Vec::is_empty"))]
+	pub calls: Vec<CallFrame>,
+}
+
+/// One executed opcode in the struct logger output.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct StructLog {
+	pub pc: u64,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::string"))]
+	pub op: Vec<u8>,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u64,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub stack: Option<Vec<H256>>,
+	#[cfg_attr(
+		feature = "std",
+		serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "serialization::option_words"
+		)
+	)]
+	pub memory: Option<Vec<H256>>,
+	#[cfg_attr(
+		feature = "std",
+		serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "serialization::option_storage"
+		)
+	)]
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Output of the struct logger.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct StructLoggerResult {
+	pub gas: u64,
+	pub failed: bool,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::bytes"))]
+	pub return_value: Vec<u8>,
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// The trace of a single transaction.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "std", serde(untagged))]
+pub enum TransactionTrace {
+	CallTracer(CallFrame),
+	StructLogger(StructLoggerResult),
+}
+
+impl TransactionTrace {
+	/// Overrides the gas reported for the whole transaction.
+	///
+	/// The tracers only see the gas used by the EVM execution, the transaction receipt also
+	/// accounts for the intrinsic cost and the refunds.
+	pub fn set_gas_used(&mut self, gas_used: U256) {
+		match self {
+			TransactionTrace::CallTracer(frame) => frame.gas_used = gas_used,
+			TransactionTrace::StructLogger(result) => result.gas = gas_used.low_u64(),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Runtime API re-executing blocks with EVM tracing enabled.
+	///
+	/// Both calls must be made on the state of the parent of the traced block, after
+	/// `Core::initialize_block` has been called with the header of the traced block.
+	pub trait DebugRuntimeApi {
+		/// Applies `extrinsics` in order and traces the Ethereum `transaction` among them.
+		fn trace_transaction(
+			extrinsics: Vec<Block::Extrinsic>,
+			transaction: &ethereum::TransactionV2,
+			trace_type: TraceType,
+		) -> Result<TransactionTrace, DispatchError>;
+
+		/// Applies `extrinsics` in order and traces every Ethereum transaction whose hash is in
+		/// `known_transactions`, in block order.
+		fn trace_block(
+			extrinsics: Vec<Block::Extrinsic>,
+			known_transactions: Vec<H256>,
+			trace_type: TraceType,
+		) -> Result<Vec<TransactionTrace>, DispatchError>;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn call_frame_serializes_as_geth_call_tracer() {
+		let frame = CallFrame {
+			call_type: CallType::Call,
+			from: H160::repeat_byte(0x11),
+			to: H160::repeat_byte(0x22),
+			value: U256::from(1),
+			gas: U256::from(100_000),
+			gas_used: U256::from(21_000),
+			input: vec![0xab, 0xcd],
+			output: vec![],
+			error: Some(b"execution reverted".to_vec()),
+			calls: vec![],
+		};
+
+		assert_eq!(
+			serde_json::to_value(&TransactionTrace::CallTracer(frame)).unwrap(),
+			serde_json::json!({
+				"type": "CALL",
+				"from": "0x1111111111111111111111111111111111111111",
+				"to": "0x2222222222222222222222222222222222222222",
+				"value": "0x1",
+				"gas": "0x186a0",
+				"gasUsed": "0x5208",
+				"input": "0xabcd",
+				"output": "0x",
+				"error": "execution reverted",
+			})
+		);
+	}
+
+	#[test]
+	fn struct_logs_serialize_as_geth_struct_logger() {
+		let mut storage = BTreeMap::new();
+		storage.insert(H256::zero(), H256::from_low_u64_be(1));
+		let result = StructLoggerResult {
+			gas: 21_000,
+			failed: false,
+			return_value: vec![0x01],
+			struct_logs: vec![StructLog {
+				pc: 0,
+				op: b"SSTORE".to_vec(),
+				gas: 100,
+				gas_cost: 20_000,
+				depth: 1,
+				stack: Some(vec![H256::zero()]),
+				memory: Some(vec![]),
+				storage: Some(storage),
+			}],
+		};
+
+		let json = serde_json::to_value(&TransactionTrace::StructLogger(result)).unwrap();
+
+		assert_eq!(json["gas"], 21_000);
+		assert_eq!(json["returnValue"], "01");
+		assert_eq!(json["structLogs"][0]["op"], "SSTORE");
+		assert_eq!(json["structLogs"][0]["gasCost"], 20_000);
+		assert_eq!(
+			json["structLogs"][0]["storage"][
+				"0000000000000000000000000000000000000000000000000000000000000000"
+			],
+			"0000000000000000000000000000000000000000000000000000000000000001"
+		);
+	}
+}
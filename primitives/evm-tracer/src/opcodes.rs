@@ -0,0 +1,151 @@
+//! Mnemonics of the EVM opcodes, as printed by Geth.
+
+/// Mnemonic of `opcode`, or `UNKNOWN` for unassigned opcodes.
+pub fn opcode_name(opcode: u8) -> &'static [u8] {
+	match opcode {
+		0x00 => b"STOP",
+		0x01 => b"ADD",
+		0x02 => b"MUL",
+		0x03 => b"SUB",
+		0x04 => b"DIV",
+		0x05 => b"SDIV",
+		0x06 => b"MOD",
+		0x07 => b"SMOD",
+		0x08 => b"ADDMOD",
+		0x09 => b"MULMOD",
+		0x0a => b"EXP",
+		0x0b => b"SIGNEXTEND",
+		0x10 => b"LT",
+		0x11 => b"GT",
+		0x12 => b"SLT",
+		0x13 => b"SGT",
+		0x14 => b"EQ",
+		0x15 => b"ISZERO",
+		0x16 => b"AND",
+		0x17 => b"OR",
+		0x18 => b"XOR",
+		0x19 => b"NOT",
+		0x1a => b"BYTE",
+		0x1b => b"SHL",
+		0x1c => b"SHR",
+		0x1d => b"SAR",
+		0x20 => b"SHA3",
+		0x30 => b"ADDRESS",
+		0x31 => b"BALANCE",
+		0x32 => b"ORIGIN",
+		0x33 => b"CALLER",
+		0x34 => b"CALLVALUE",
+		0x35 => b"CALLDATALOAD",
+		0x36 => b"CALLDATASIZE",
+		0x37 => b"CALLDATACOPY",
+		0x38 => b"CODESIZE",
+		0x39 => b"CODECOPY",
+		0x3a => b"GASPRICE",
+		0x3b => b"EXTCODESIZE",
+		0x3c => b"EXTCODECOPY",
+		0x3d => b"RETURNDATASIZE",
+		0x3e => b"RETURNDATACOPY",
+		0x3f => b"EXTCODEHASH",
+		0x40 => b"BLOCKHASH",
+		0x41 => b"COINBASE",
+		0x42 => b"TIMESTAMP",
+		0x43 => b"NUMBER",
+		0x44 => b"DIFFICULTY",
+		0x45 => b"GASLIMIT",
+		0x46 => b"CHAINID",
+		0x47 => b"SELFBALANCE",
+		0x48 => b"BASEFEE",
+		0x50 => b"POP",
+		0x51 => b"MLOAD",
+		0x52 => b"MSTORE",
+		0x53 => b"MSTORE8",
+		0x54 => b"SLOAD",
+		0x55 => b"SSTORE",
+		0x56 => b"JUMP",
+		0x57 => b"JUMPI",
+		0x58 => b"PC",
+		0x59 => b"MSIZE",
+		0x5a => b"GAS",
+		0x5b => b"JUMPDEST",
+		0x60 => b"PUSH1",
+		0x61 => b"PUSH2",
+		0x62 => b"PUSH3",
+		0x63 => b"PUSH4",
+		0x64 => b"PUSH5",
+		0x65 => b"PUSH6",
+		0x66 => b"PUSH7",
+		0x67 => b"PUSH8",
+		0x68 => b"PUSH9",
+		0x69 => b"PUSH10",
+		0x6a => b"PUSH11",
+		0x6b => b"PUSH12",
+		0x6c => b"PUSH13",
+		0x6d => b"PUSH14",
+		0x6e => b"PUSH15",
+		0x6f => b"PUSH16",
+		0x70 => b"PUSH17",
+		0x71 => b"PUSH18",
+		0x72 => b"PUSH19",
+		0x73 => b"PUSH20",
+		0x74 => b"PUSH21",
+		0x75 => b"PUSH22",
+		0x76 => b"PUSH23",
+		0x77 => b"PUSH24",
+		0x78 => b"PUSH25",
+		0x79 => b"PUSH26",
+		0x7a => b"PUSH27",
+		0x7b => b"PUSH28",
+		0x7c => b"PUSH29",
+		0x7d => b"PUSH30",
+		0x7e => b"PUSH31",
+		0x7f => b"PUSH32",
+		0x80 => b"DUP1",
+		0x81 => b"DUP2",
+		0x82 => b"DUP3",
+		0x83 => b"DUP4",
+		0x84 => b"DUP5",
+		0x85 => b"DUP6",
+		0x86 => b"DUP7",
+		0x87 => b"DUP8",
+		0x88 => b"DUP9",
+		0x89 => b"DUP10",
+		0x8a => b"DUP11",
+		0x8b => b"DUP12",
+		0x8c => b"DUP13",
+		0x8d => b"DUP14",
+		0x8e => b"DUP15",
+		0x8f => b"DUP16",
+		0x90 => b"SWAP1",
+		0x91 => b"SWAP2",
+		0x92 => b"SWAP3",
+		0x93 => b"SWAP4",
+		0x94 => b"SWAP5",
+		0x95 => b"SWAP6",
+		0x96 => b"SWAP7",
+		0x97 => b"SWAP8",
+		0x98 => b"SWAP9",
+		0x99 => b"SWAP10",
+		0x9a => b"SWAP11",
+		0x9b => b"SWAP12",
+		0x9c => b"SWAP13",
+		0x9d => b"SWAP14",
+		0x9e => b"SWAP15",
+		0x9f => b"SWAP16",
+		0xa0 => b"LOG0",
+		0xa1 => b"LOG1",
+		0xa2 => b"LOG2",
+		0xa3 => b"LOG3",
+		0xa4 => b"LOG4",
+		0xf0 => b"CREATE",
+		0xf1 => b"CALL",
+		0xf2 => b"CALLCODE",
+		0xf3 => b"RETURN",
+		0xf4 => b"DELEGATECALL",
+		0xf5 => b"CREATE2",
+		0xfa => b"STATICCALL",
+		0xfd => b"REVERT",
+		0xfe => b"INVALID",
+		0xff => b"SELFDESTRUCT",
+		_ => b"UNKNOWN",
+	}
+}
@@ -0,0 +1,63 @@
+//! Serde helpers matching the encodings used by Geth in its traces.
+
+use serde::{ser::SerializeMap, ser::SerializeSeq, Serializer};
+use sp_core::H256;
+use std::collections::BTreeMap;
+
+/// Hex without `0x` prefix, as used for return values.
+pub fn bytes<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&hex::encode(data))
+}
+
+/// Hex with `0x` prefix, as used for call input and output.
+pub fn bytes_0x<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&format!("0x{}", hex::encode(data)))
+}
+
+pub fn string<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&String::from_utf8_lossy(data))
+}
+
+pub fn option_string<S: Serializer>(
+	data: &Option<Vec<u8>>,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match data {
+		Some(data) => string(data, serializer),
+		None => serializer.serialize_none(),
+	}
+}
+
+/// 32 bytes words as hex without `0x` prefix, as used for memory.
+pub fn option_words<S: Serializer>(
+	data: &Option<Vec<H256>>,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match data {
+		Some(words) => {
+			let mut seq = serializer.serialize_seq(Some(words.len()))?;
+			for word in words {
+				seq.serialize_element(&hex::encode(word.as_bytes()))?;
+			}
+			seq.end()
+		}
+		None => serializer.serialize_none(),
+	}
+}
+
+/// Storage slots and values as hex without `0x` prefix.
+pub fn option_storage<S: Serializer>(
+	data: &Option<BTreeMap<H256, H256>>,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match data {
+		Some(storage) => {
+			let mut map = serializer.serialize_map(Some(storage.len()))?;
+			for (key, value) in storage {
+				map.serialize_entry(&hex::encode(key.as_bytes()), &hex::encode(value.as_bytes()))?;
+			}
+			map.end()
+		}
+		None => serializer.serialize_none(),
+	}
+}
@@ -0,0 +1,387 @@
+//! Tracers turning the events emitted by the EVM into [`TransactionTrace`]s.
+//!
+//! The EVM emits events from three crates: `evm` for calls and contract creations, `evm-runtime`
+//! for opcode steps and storage accesses, and `evm-gasometer` for gas accounting. A single
+//! [`Tracer`] listens to all of them through a shared reference.
+
+extern crate alloc;
+
+use crate::{
+	opcode_name, CallFrame, CallType, StructLog, StructLoggerResult, TraceType, TransactionTrace,
+};
+use evm::{
+	tracing::{Event as EvmEvent, EventListener as EvmListener},
+	CreateScheme, ExitError, ExitReason,
+};
+use evm_gasometer::tracing::{
+	Event as GasometerEvent, EventListener as GasometerListener, Snapshot,
+};
+use evm_runtime::tracing::{Event as RuntimeEvent, EventListener as RuntimeListener};
+use sp_core::{H160, H256, U256};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, rc::Rc, vec::Vec};
+
+/// Runs `f` with tracing enabled and returns its result along with the trace of the EVM
+/// execution it performed.
+pub fn trace<R>(trace_type: TraceType, f: impl FnOnce() -> R) -> (R, TransactionTrace) {
+	let tracer = Rc::new(RefCell::new(Tracer::new(trace_type)));
+
+	let mut evm_listener = SharedTracer(tracer.clone());
+	let mut runtime_listener = SharedTracer(tracer.clone());
+	let mut gasometer_listener = SharedTracer(tracer.clone());
+
+	let result = evm::tracing::using(&mut evm_listener, || {
+		evm_runtime::tracing::using(&mut runtime_listener, || {
+			evm_gasometer::tracing::using(&mut gasometer_listener, f)
+		})
+	});
+
+	let trace = tracer.borrow_mut().finish();
+	(result, trace)
+}
+
+/// Collects the events of a single transaction.
+pub struct Tracer {
+	trace_type: TraceType,
+	/// Gas limit of the transaction, used for the root call frame.
+	gas_limit: Option<U256>,
+	/// Call frames that have been entered but not exited yet, innermost last.
+	frames: Vec<OpenFrame>,
+	/// The outermost call frame, once exited.
+	root: Option<CallFrame>,
+	/// Opcode being executed, waiting for its gas cost.
+	step: Option<StructLog>,
+	step_cost_recorded: bool,
+	struct_logs: Vec<StructLog>,
+	/// Storage slots read or written so far, per contract.
+	storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+}
+
+struct OpenFrame {
+	frame: CallFrame,
+	gas_used: u64,
+}
+
+impl Tracer {
+	pub fn new(trace_type: TraceType) -> Self {
+		Self {
+			trace_type,
+			gas_limit: None,
+			frames: Vec::new(),
+			root: None,
+			step: None,
+			step_cost_recorded: false,
+			struct_logs: Vec::new(),
+			storage: BTreeMap::new(),
+		}
+	}
+
+	pub fn finish(&mut self) -> TransactionTrace {
+		self.flush_step();
+
+		// A transaction that never reached the EVM, or whose root frame did not exit, still gets
+		// a (possibly empty) trace.
+		while !self.frames.is_empty() {
+			self.exit_frame(&ExitReason::Error(ExitError::Other("not exited".into())), &[]);
+		}
+		let root = self.root.take().unwrap_or_else(|| CallFrame {
+			call_type: CallType::Call,
+			from: H160::zero(),
+			to: H160::zero(),
+			value: U256::zero(),
+			gas: self.gas_limit.unwrap_or_default(),
+			gas_used: U256::zero(),
+			input: Vec::new(),
+			output: Vec::new(),
+			error: None,
+			calls: Vec::new(),
+		});
+
+		match self.trace_type {
+			TraceType::CallTracer => TransactionTrace::CallTracer(root),
+			TraceType::StructLogger { .. } => TransactionTrace::StructLogger(StructLoggerResult {
+				gas: root.gas_used.low_u64(),
+				failed: root.error.is_some(),
+				return_value: root.output,
+				struct_logs: sp_std::mem::take(&mut self.struct_logs),
+			}),
+		}
+	}
+
+	fn enter_frame(&mut self, frame: CallFrame) {
+		let mut frame = frame;
+		if self.frames.is_empty() {
+			if let Some(gas_limit) = self.gas_limit {
+				frame.gas = gas_limit;
+			}
+		}
+		self.frames.push(OpenFrame {
+			frame,
+			gas_used: 0,
+		});
+	}
+
+	fn exit_frame(&mut self, reason: &ExitReason, return_value: &[u8]) {
+		let OpenFrame {
+			mut frame,
+			gas_used,
+		} = match self.frames.pop() {
+			Some(open) => open,
+			None => return,
+		};
+
+		frame.gas_used = gas_used.into();
+		frame.output = return_value.to_vec();
+		frame.error = match reason {
+			ExitReason::Succeed(_) => None,
+			ExitReason::Revert(_) => Some(b"execution reverted".to_vec()),
+			ExitReason::Error(ExitError::OutOfGas) => Some(b"out of gas".to_vec()),
+			ExitReason::Error(e) => Some(alloc::format!("{:?}", e).into_bytes()),
+			ExitReason::Fatal(e) => Some(alloc::format!("{:?}", e).into_bytes()),
+		};
+
+		match self.frames.last_mut() {
+			Some(parent) => parent.frame.calls.push(frame),
+			None => self.root = Some(frame),
+		}
+	}
+
+	fn flush_step(&mut self) {
+		if let Some(step) = self.step.take() {
+			self.struct_logs.push(step);
+		}
+	}
+
+	fn record_gas(&mut self, snapshot: Option<Snapshot>, cost: u64, memory_gas: Option<u64>) {
+		let snapshot = match snapshot {
+			Some(snapshot) => snapshot,
+			None => return,
+		};
+		let memory_gas = memory_gas.unwrap_or(snapshot.memory_gas).max(snapshot.memory_gas);
+
+		if let Some(open) = self.frames.last_mut() {
+			open.gas_used = snapshot.used_gas.saturating_add(cost).saturating_add(memory_gas);
+		}
+
+		if !self.step_cost_recorded {
+			if let Some(step) = self.step.as_mut() {
+				step.gas = snapshot
+					.gas_limit
+					.saturating_sub(snapshot.used_gas)
+					.saturating_sub(snapshot.memory_gas);
+				step.gas_cost = cost.saturating_add(memory_gas - snapshot.memory_gas);
+				self.step_cost_recorded = true;
+			}
+		}
+	}
+
+	fn record_storage(&mut self, address: H160, index: H256, value: H256) {
+		let storage = self.storage.entry(address).or_default();
+		storage.insert(index, value);
+
+		if let TraceType::StructLogger {
+			disable_storage: false,
+			..
+		} = self.trace_type
+		{
+			if let Some(step) = self.step.as_mut() {
+				step.storage = Some(storage.clone());
+			}
+		}
+	}
+}
+
+impl EvmListener for Tracer {
+	fn event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { gas_limit, .. }
+			| EvmEvent::TransactCreate { gas_limit, .. }
+			| EvmEvent::TransactCreate2 { gas_limit, .. } => {
+				self.gas_limit = Some(gas_limit);
+			}
+			EvmEvent::Call {
+				code_address,
+				transfer,
+				input,
+				target_gas,
+				is_static,
+				context,
+			} => {
+				let (call_type, from) = if is_static {
+					(CallType::StaticCall, context.caller)
+				} else if context.address == code_address {
+					(CallType::Call, context.caller)
+				} else if context.caller == context.address {
+					(CallType::CallCode, context.address)
+				} else {
+					(CallType::DelegateCall, context.address)
+				};
+				let value = match call_type {
+					CallType::DelegateCall => context.apparent_value,
+					_ => transfer.as_ref().map(|t| t.value).unwrap_or_default(),
+				};
+
+				self.enter_frame(CallFrame {
+					call_type,
+					from,
+					to: code_address,
+					value,
+					gas: target_gas.unwrap_or_default().into(),
+					gas_used: U256::zero(),
+					input: input.to_vec(),
+					output: Vec::new(),
+					error: None,
+					calls: Vec::new(),
+				});
+			}
+			EvmEvent::Create {
+				caller,
+				address,
+				scheme,
+				value,
+				init_code,
+				target_gas,
+			} => {
+				let call_type = match scheme {
+					CreateScheme::Create2 { .. } => CallType::Create2,
+					_ => CallType::Create,
+				};
+
+				self.enter_frame(CallFrame {
+					call_type,
+					from: caller,
+					to: address,
+					value,
+					gas: target_gas.unwrap_or_default().into(),
+					gas_used: U256::zero(),
+					input: init_code.to_vec(),
+					output: Vec::new(),
+					error: None,
+					calls: Vec::new(),
+				});
+			}
+			EvmEvent::Suicide {
+				address,
+				target,
+				balance,
+			} => {
+				if let Some(parent) = self.frames.last_mut() {
+					parent.frame.calls.push(CallFrame {
+						call_type: CallType::SelfDestruct,
+						from: address,
+						to: target,
+						value: balance,
+						gas: U256::zero(),
+						gas_used: U256::zero(),
+						input: Vec::new(),
+						output: Vec::new(),
+						error: None,
+						calls: Vec::new(),
+					});
+				}
+			}
+			EvmEvent::Exit {
+				reason,
+				return_value,
+			} => {
+				// The step that caused the exit belongs to the frame being exited.
+				self.flush_step();
+				self.exit_frame(reason, return_value);
+			}
+			_ => {}
+		}
+	}
+}
+
+impl RuntimeListener for Tracer {
+	fn event(&mut self, event: RuntimeEvent) {
+		let (disable_stack, disable_memory) = match self.trace_type {
+			TraceType::StructLogger {
+				disable_stack,
+				disable_memory,
+				..
+			} => (disable_stack, disable_memory),
+			// Opcode steps and storage accesses are only needed for the struct logger.
+			TraceType::CallTracer => return,
+		};
+
+		match event {
+			RuntimeEvent::Step {
+				opcode,
+				position,
+				stack,
+				memory,
+				..
+			} => {
+				self.flush_step();
+				self.step_cost_recorded = false;
+				self.step = Some(StructLog {
+					pc: position.as_ref().map(|pc| *pc as u64).unwrap_or_default(),
+					op: opcode_name(opcode.0).to_vec(),
+					gas: 0,
+					gas_cost: 0,
+					depth: self.frames.len() as u64,
+					stack: (!disable_stack).then(|| stack.data().clone()),
+					memory: (!disable_memory).then(|| {
+						memory
+							.data()
+							.chunks(32)
+							.map(|chunk| {
+								let mut word = [0u8; 32];
+								word[..chunk.len()].copy_from_slice(chunk);
+								H256(word)
+							})
+							.collect()
+					}),
+					storage: None,
+				});
+			}
+			RuntimeEvent::SLoad {
+				address,
+				index,
+				value,
+			}
+			| RuntimeEvent::SStore {
+				address,
+				index,
+				value,
+			} => self.record_storage(address, index, value),
+			_ => {}
+		}
+	}
+}
+
+impl GasometerListener for Tracer {
+	fn event(&mut self, event: GasometerEvent) {
+		match event {
+			GasometerEvent::RecordCost { cost, snapshot } => self.record_gas(snapshot, cost, None),
+			GasometerEvent::RecordDynamicCost {
+				gas_cost,
+				memory_gas,
+				snapshot,
+				..
+			} => self.record_gas(snapshot, gas_cost, Some(memory_gas)),
+			_ => {}
+		}
+	}
+}
+
+/// Forwards the events of every EVM crate to the same tracer.
+struct SharedTracer(Rc<RefCell<Tracer>>);
+
+impl EvmListener for SharedTracer {
+	fn event(&mut self, event: EvmEvent) {
+		EvmListener::event(&mut *self.0.borrow_mut(), event)
+	}
+}
+
+impl RuntimeListener for SharedTracer {
+	fn event(&mut self, event: RuntimeEvent) {
+		RuntimeListener::event(&mut *self.0.borrow_mut(), event)
+	}
+}
+
+impl GasometerListener for SharedTracer {
+	fn event(&mut self, event: GasometerEvent) {
+		GasometerListener::event(&mut *self.0.borrow_mut(), event)
+	}
+}
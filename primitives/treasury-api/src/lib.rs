@@ -0,0 +1,45 @@
+//! Runtime API reporting the state of the treasury: the funds it can spend and the spend
+//! proposals waiting on them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A spend proposal of the treasury that has not been paid out yet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingSpend<AccountId, Balance> {
+	/// Index of the proposal.
+	pub index: u32,
+	/// Account that made the proposal and put up its bond.
+	pub proposer: AccountId,
+	/// Amount to pay out.
+	pub value: Balance,
+	/// Account to pay it out to.
+	pub beneficiary: AccountId,
+	/// Bond reserved from the proposer, slashed if the proposal is rejected.
+	pub bond: Balance,
+	/// Whether governance approved the proposal, which is then paid out at the next spend
+	/// period the treasury can afford it.
+	pub approved: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Runtime API reporting the state of the treasury.
+	pub trait TreasuryApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Funds the treasury can spend, its existential deposit excluded.
+		fn balance() -> Balance;
+
+		/// Spend proposals not paid out yet, approved or not, by increasing index.
+		fn pending_spends() -> Vec<PendingSpend<AccountId, Balance>>;
+	}
+}
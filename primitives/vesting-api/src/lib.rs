@@ -0,0 +1,30 @@
+//! Runtime API reporting how much of the balance of an account its vesting schedules lock.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// The balance of an account under its vesting schedules, at the current block.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingBalance<Balance> {
+	/// Amount the schedules still lock.
+	pub locked: Balance,
+	/// Amount the schedules released that is still locked until the account vests.
+	pub vested: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Runtime API reporting the vesting schedules of accounts.
+	pub trait VestingApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The balance of `who` under its vesting schedules, all zero if it has none.
+		fn vesting_balance(who: AccountId) -> VestingBalance<Balance>;
+	}
+}
@@ -12,34 +12,42 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+	traits::{
+		AccountIdConversion, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount,
+		IdentityLookup, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity,TransactionValidityError},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult,
 };
 
 pub use nimbus_primitives::NimbusId;
 pub use pallet_author_slot_filter::EligibilityValue;
 
-use sp_std::prelude::*;
+use sp_std::{borrow::Borrow, prelude::*};
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
 use frame_support::{
 	construct_runtime, match_types, parameter_types,
-	traits::{ConstBool,Everything, OnInitialize},
+	traits::{
+		ConstBool, ConstU32, Contains, Currency, EnsureOneOf, EnsureOrigin, EqualPrivilegeOnly,
+		Everything, Get, Imbalance, InstanceFilter, LockIdentifier, Nothing, OnInitialize,
+		OnUnbalanced, OriginTrait, VestingSchedule,
+	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		ConstantMultiplier, DispatchClass, IdentityFee, Weight, WeightToFeeCoefficient,
 		WeightToFeeCoefficients, WeightToFeePolynomial,
 	},
+	PalletId,
 };
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
 	EnsureRoot,
 };
 
-pub use sp_runtime::{MultiAddress, Perbill, Permill};
+pub use sp_runtime::{Perbill, Permill};
 
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -52,15 +60,20 @@ use polkadot_runtime_common::{BlockHashCount, SlowAdjustingFeeUpdate};
 // XCM Imports
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter,
-	EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+	AccountKey20Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, ConvertedConcreteAssetId,
+	CurrencyAdapter, EnsureXcmOrigin, FungiblesAdapter, IsConcrete, LocationInverter, NativeAsset,
 	ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
-	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	SiblingParachainConvertsVia, SignedAccountKey20AsNative, SovereignSignedViaLocation,
+	TakeWeightCredit, UsingComponents, WeightInfoBounds,
 };
+use xcm_executor::traits::JustTry;
 use xcm_executor::{Config, XcmExecutor};
 
-mod pallet_account_set;
+pub mod pallet_account_set;
+mod weights;
+
+pub use weights::xcm::DioraXcmWeight;
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -69,24 +82,26 @@ pub use pallet_template;
 use fp_rpc::TransactionStatus;
 use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
 use pallet_evm::{
-	Account as EVMAccount, EnsureAddressNever, EnsureAddressRoot, FeeCalculator,
-	HashedAddressMapping, Runner,
+	Account as EVMAccount, EnsureAddressNever, EnsureAddressRoot, FeeCalculator, Runner,
 };
 use sp_core::{H160, U256,H256};
 use sp_runtime::traits::{Dispatchable,DispatchInfoOf, PostDispatchInfoOf};
 mod precompiles;
-pub use precompiles::DioraPrecompiles;
+pub use precompiles::{Batch, DioraPrecompiles, Dispatch, Vest, XcmTransfer};
 
-/// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
-pub type Signature = MultiSignature;
+/// Ethereum-style ECDSA signature, so that the same key signs both EVM and Substrate transactions.
+pub type Signature = account::EthereumSignature<SignedPayloadFields>;
 
 /// Some way of identifying an account on the chain. We intentionally make it equivalent
-/// to the public key of our transaction signing scheme.
+/// to the public key of our transaction signing scheme, which is a 20-byte Ethereum address.
 pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a foreign asset in `pallet_assets`.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -97,7 +112,7 @@ pub type Hash = sp_core::H256;
 pub type BlockNumber = u32;
 
 /// The address format for describing accounts.
-pub type Address = MultiAddress<AccountId, ()>;
+pub type Address = AccountId;
 
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
@@ -120,8 +135,94 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	CheckSignatureScheme,
 );
 
+/// How the signature of an extrinsic was produced.
+#[derive(
+	Clone, Copy, Eq, PartialEq, Encode, Decode, sp_runtime::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub enum SignatureScheme {
+	/// The keccak-256 hash of the payload is signed.
+	Keccak,
+	/// The EIP-712 typed data of the payload is signed, see [`SignedPayloadFields`].
+	Eip712,
+}
+
+/// Signed extension holding the [`SignatureScheme`] of the extrinsic.
+///
+/// It checks nothing itself: being part of the signed payload, it tells which hash the signature
+/// is verified against, while the signature keeps its plain 65-byte encoding.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_runtime::RuntimeDebug, scale_info::TypeInfo)]
+pub struct CheckSignatureScheme(pub SignatureScheme);
+
+impl sp_runtime::traits::SignedExtension for CheckSignatureScheme {
+	const IDENTIFIER: &'static str = "CheckSignatureScheme";
+	type AccountId = AccountId;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &AccountId,
+		call: &Call,
+		info: &DispatchInfoOf<Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
+
+/// Splits the payloads signed with [`SignatureScheme::Eip712`] into the fields of their EIP-712
+/// typed data.
+#[derive(Clone, Eq, PartialEq, sp_runtime::RuntimeDebug, scale_info::TypeInfo)]
+pub struct SignedPayloadFields;
+impl account::eip712::DecodeTransaction for SignedPayloadFields {
+	fn decode_transaction(payload: &[u8]) -> Option<account::eip712::Transaction> {
+		let mut input = payload;
+		Call::decode(&mut input).ok()?;
+		let call = &payload[..payload.len() - input.len()];
+
+		// The extra and additional signed data of `SignedExtra`, the other extensions encoding
+		// nothing.
+		let (era, nonce, tip, scheme) = <(
+			generic::Era,
+			codec::Compact<Index>,
+			codec::Compact<Balance>,
+			SignatureScheme,
+		)>::decode(&mut input)
+		.ok()?;
+		let (spec_version, transaction_version, genesis_hash, block_hash) =
+			<(u32, u32, Hash, Hash)>::decode(&mut input).ok()?;
+		if !input.is_empty() || scheme != SignatureScheme::Eip712 {
+			return None;
+		}
+
+		let (era_period, era_phase) = match era {
+			generic::Era::Immortal => (0, 0),
+			generic::Era::Mortal(period, phase) => (period, phase),
+		};
+		Some(account::eip712::Transaction {
+			pallet_index: call[0],
+			call_index: call[1],
+			arguments: call[2..].to_vec(),
+			nonce: nonce.0,
+			era_period,
+			era_phase,
+			tip: tip.0,
+			spec_version,
+			transaction_version,
+			genesis_hash: genesis_hash.0,
+			block_hash: block_hash.0,
+		})
+	}
+}
+
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = fp_self_contained::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 
@@ -186,7 +287,12 @@ impl fp_self_contained::SelfContainedCall for Call {
 		len: usize,
 	) -> Option<TransactionValidity> {
 		match self {
-			Call::Ethereum(call) => call.validate_self_contained(info, dispatch_info, len),
+			Call::Ethereum(call) => {
+				if let Err(e) = ensure_can_deploy(call, info) {
+					return Some(Err(e));
+				}
+				call.validate_self_contained(info, dispatch_info, len)
+			}
 			_ => None,
 		}
 	}
@@ -196,7 +302,12 @@ impl fp_self_contained::SelfContainedCall for Call {
 		info: &Self::SignedInfo,
 	) -> Option<Result<(), TransactionValidityError>> {
 		match self {
-			Call::Ethereum(call) => call.pre_dispatch_self_contained(info),
+			Call::Ethereum(call) => {
+				if let Err(e) = ensure_can_deploy(call, info) {
+					return Some(Err(e));
+				}
+				call.pre_dispatch_self_contained(info)
+			}
 			_ => None,
 		}
 	}
@@ -214,12 +325,52 @@ impl fp_self_contained::SelfContainedCall for Call {
 	}
 }
 
+/// Rejects Ethereum contract-creation transactions whose sender may not deploy contracts.
+fn ensure_can_deploy(
+	call: &pallet_ethereum::Call<Runtime>,
+	from: &H160,
+) -> Result<(), TransactionValidityError> {
+	let action = match call {
+		pallet_ethereum::Call::transact { transaction } => match transaction {
+			EthereumTransaction::Legacy(t) => t.action,
+			EthereumTransaction::EIP2930(t) => t.action,
+			EthereumTransaction::EIP1559(t) => t.action,
+		},
+		_ => return Ok(()),
+	};
+
+	match action {
+		pallet_ethereum::TransactionAction::Create => DeploymentAllowlist::ensure_can_deploy(from),
+		pallet_ethereum::TransactionAction::Call(_) => Ok(()),
+	}
+}
+
+/// Pallet migrations that have not yet been applied to every live Diora network.
+///
+/// `EligibleRatioToEligiblityCount` sets the number of eligible authors from the deprecated ratio
+/// on every upgrade, so it must be removed once it has run.
+type Migrations = (
+	pallet_base_fee_params::migration::ActivateBaseFee<Runtime>,
+	pallet_author_slot_filter::migration::EligibleRatioToEligiblityCount<Runtime>,
+);
+
 pub struct OnRuntimeUpgrade;
 impl frame_support::traits::OnRuntimeUpgrade for OnRuntimeUpgrade {
 	fn on_runtime_upgrade() -> u64 {
 		frame_support::migrations::migrate_from_pallet_version_to_storage_version::<
 			AllPalletsWithSystem,
 		>(&RocksDbWeight::get())
+		.saturating_add(<Migrations as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		<Migrations as frame_support::traits::OnRuntimeUpgrade>::pre_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		<Migrations as frame_support::traits::OnRuntimeUpgrade>::post_upgrade()
 	}
 }
 
@@ -312,6 +463,11 @@ pub const MICROUNIT: Balance = 1_000_000;
 /// The existential deposit. Set to 1/10 of the Rococo Relay Chain.
 pub const EXISTENTIAL_DEPOSIT: Balance = MILLIUNIT;
 
+/// Deposit for keeping `items` storage items of `bytes` bytes in total.
+pub const fn deposit(items: u32, bytes: u32) -> Balance {
+	items as Balance * 20 * UNIT + (bytes as Balance) * 100 * MICROUNIT
+}
+
 // 1 in 4 blocks (on average, not counting collisions) will be primary babe blocks.
 pub const PRIMARY_PROBABILITY: (u64, u64) = (1, 4);
 
@@ -367,13 +523,29 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 
+/// Calls that origins other than root may dispatch.
+///
+/// Assets are only created by the asset manager, which registers them with their metadata. An
+/// account creating an asset would take an ID the asset manager may register later, and could
+/// give it the name and symbol of another asset.
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		!matches!(
+			call,
+			Call::Assets(pallet_assets::Call::create { .. }) |
+				Call::Assets(pallet_assets::Call::set_metadata { .. })
+		)
+	}
+}
+
 impl frame_system::Config for Runtime {
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = AccountIdLookup<AccountId, ()>;
+	type Lookup = IdentityLookup<AccountId>;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -403,7 +575,7 @@ impl frame_system::Config for Runtime {
 	/// The weight of database operations that the runtime can invoke.
 	type DbWeight = RocksDbWeight;
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseFilter;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = ();
 	/// Block & extrinsics weights: base values and limits.
@@ -441,7 +613,8 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	/// Balances reaped for falling below the existential deposit go to the treasury.
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
@@ -453,16 +626,70 @@ parameter_types! {
 	/// Relay Chain `TransactionByteFee` / 10
 	pub const TransactionByteFee: Balance = 10 * MICROUNIT;
 	pub const OperationalFeeMultiplier: u8 = 5;
+	/// Share of the fees and tips paid for Substrate extrinsics and EVM transactions that goes
+	/// to the treasury. The rest is burned.
+	pub const TreasuryFeeShare: Perbill = Perbill::from_percent(20);
+}
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Gives `TreasuryFeeShare` of the fees it is handed to the treasury and burns the rest.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		let to_treasury = TreasuryFeeShare::get() * amount.peek();
+		let (to_treasury, _burned) = amount.split(to_treasury);
+		Treasury::on_unbalanced(to_treasury);
+	}
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees>;
 	type WeightToFee = WeightToFee;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 10 * UNIT;
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const AssetsStringLimit: u32 = 50;
+	// Foreign assets are created by the asset manager, whose account holds no funds to reserve a
+	// metadata deposit from.
+	pub const MetadataDepositBase: Balance = 0;
+	pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const AssetManagerPalletId: PalletId = PalletId(*b"dio/astm");
+}
+
+impl pallet_asset_manager::Config for Runtime {
+	type Event = Event;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type Assets = Assets;
+	type PalletId = AssetManagerPalletId;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
@@ -486,7 +713,6 @@ impl parachain_info::Config for Runtime {}
 parameter_types! {
 	pub const RocLocation: MultiLocation = MultiLocation::parent();
 	pub const RelayNetwork: NetworkId = NetworkId::Any;
-	pub RelayChainOrigin: Origin = cumulus_pallet_xcm::Origin::Relay.into();
 	pub Ancestry: MultiLocation = Parachain(ParachainInfo::parachain_id().into()).into();
 }
 
@@ -498,17 +724,38 @@ pub type LocationToAccountId = (
 	ParentIsPreset<AccountId>,
 	// Sibling parachain origins convert to AccountId via the `ParaId::into`.
 	SiblingParachainConvertsVia<Sibling, AccountId>,
-	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
-	AccountId32Aliases<RelayNetwork, AccountId>,
+	// Straight up local `AccountKey20` origins just alias directly to `AccountId`.
+	AccountKey20Aliases<RelayNetwork, AccountId>,
 );
 
+/// The origin of the relay chain in the runtime `R`.
+pub struct RelayChainOrigin<R>(sp_std::marker::PhantomData<R>);
+impl<R: frame_system::Config> Get<R::Origin> for RelayChainOrigin<R>
+where
+	R::Origin: From<cumulus_pallet_xcm::Origin>,
+{
+	fn get() -> R::Origin {
+		cumulus_pallet_xcm::Origin::Relay.into()
+	}
+}
+
+/// The account `pallet_xcm` of the runtime `R` tracks teleported assets in.
+pub struct CheckingAccount<R>(sp_std::marker::PhantomData<R>);
+impl<R: pallet_xcm::Config + frame_system::Config<AccountId = AccountId>> Get<AccountId>
+	for CheckingAccount<R>
+{
+	fn get() -> AccountId {
+		pallet_xcm::Pallet::<R>::check_account()
+	}
+}
+
 /// Means for transacting assets on this chain.
-pub type LocalAssetTransactor = CurrencyAdapter<
+pub type LocalAssetTransactor<R> = CurrencyAdapter<
 	// Use this currency:
-	Balances,
+	pallet_balances::Pallet<R>,
 	// Use this currency when it is a fungible asset matching the given location or name:
 	IsConcrete<RocLocation>,
-	// Do a simple punn to convert an AccountId32 MultiLocation into a native chain account ID:
+	// Do a simple punn to convert an AccountKey20 MultiLocation into a native chain account ID:
 	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
 	AccountId,
@@ -516,48 +763,134 @@ pub type LocalAssetTransactor = CurrencyAdapter<
 	(),
 >;
 
+parameter_types! {
+	pub const XcmFeesPalletId: PalletId = PalletId(*b"dio/xfee");
+	/// Account receiving the fees paid for XCM execution, in the relay token or foreign assets.
+	pub XcmFeesAccount: AccountId = XcmFeesPalletId::get().into_account();
+}
+
+/// Matches the foreign assets registered in the asset manager.
+pub type ForeignAssetMatcher<R> =
+	ConvertedConcreteAssetId<AssetId, Balance, pallet_asset_manager::AsAssetId<R>, JustTry>;
+
+/// Means for transacting the foreign assets registered in the asset manager, whose balances are
+/// held in `pallet_assets`.
+pub type ForeignAssetTransactor<R> = FungiblesAdapter<
+	// Use these assets:
+	pallet_assets::Pallet<R>,
+	// Use the asset registered at the location of a concrete fungible asset:
+	ForeignAssetMatcher<R>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// Foreign assets are never teleported, so we don't track any teleports:
+	Nothing,
+	CheckingAccount<R>,
+>;
+
+/// Means for transacting assets on this chain, tried in order: the relay token, then the foreign
+/// assets. Generic over the runtime so that it can be reused with another runtime, as
+/// [`XcmBarrier`].
+pub type AssetTransactors<R> = (LocalAssetTransactor<R>, ForeignAssetTransactor<R>);
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
-/// biases the kind of local `Origin` it will become.
-pub type XcmOriginToTransactDispatchOrigin = (
+/// biases the kind of local `Origin` it will become. Generic over the runtime, as
+/// [`AssetTransactors`].
+pub type XcmOriginToTransactDispatchOrigin<R> = (
 	// Sovereign account converter; this attempts to derive an `AccountId` from the origin location
 	// using `LocationToAccountId` and then turn that into the usual `Signed` origin. Useful for
 	// foreign chains who want to have a local sovereign account on this chain which they control.
-	SovereignSignedViaLocation<LocationToAccountId, Origin>,
+	SovereignSignedViaLocation<LocationToAccountId, <R as frame_system::Config>::Origin>,
 	// Native converter for Relay-chain (Parent) location; will converts to a `Relay` origin when
 	// recognised.
-	RelayChainAsNative<RelayChainOrigin, Origin>,
+	RelayChainAsNative<RelayChainOrigin<R>, <R as frame_system::Config>::Origin>,
 	// Native converter for sibling Parachains; will convert to a `SiblingPara` origin when
 	// recognised.
-	SiblingParachainAsNative<cumulus_pallet_xcm::Origin, Origin>,
+	SiblingParachainAsNative<cumulus_pallet_xcm::Origin, <R as frame_system::Config>::Origin>,
 	// Superuser converter for the Relay-chain (Parent) location. This will allow it to issue a
 	// transaction from the Root origin.
-	ParentAsSuperuser<Origin>,
-	// Native signed account converter; this just converts an `AccountId32` origin into a normal
-	// `Origin::Signed` origin of the same 32-byte value.
-	SignedAccountId32AsNative<RelayNetwork, Origin>,
+	ParentAsSuperuser<<R as frame_system::Config>::Origin>,
+	// Native signed account converter; this just converts an `AccountKey20` origin into a normal
+	// `Origin::Signed` origin of the same 20-byte value.
+	SignedAccountKey20AsNative<RelayNetwork, <R as frame_system::Config>::Origin>,
 	// Xcm origins can be represented natively under the Xcm pallet's Xcm origin.
-	XcmPassthrough<Origin>,
+	XcmPassthrough<<R as frame_system::Config>::Origin>,
 );
 
 parameter_types! {
-	// One XCM operation is 1_000_000_000 weight - almost certainly a conservative estimate.
-	pub UnitWeightCost: Weight = 1_000_000_000;
 	pub const MaxInstructions: u32 = 100;
+	/// Number of assets in holding a wildcard is weighed for, when its instruction doesn't bound
+	/// it. The XCM v2 executor does not cap holding, so this is the limit XCM v3 executors
+	/// enforce: a wildcard matching more assets is undercharged, but each of them was charged
+	/// when it was put in holding.
+	pub const MaxAssetsIntoHolding: u32 = 64;
 }
 
+/// Weighs XCM programs instruction by instruction, with the weights benchmarked by
+/// `pallet_xcm_benchmarks`. `Transact` is charged its benchmarked overhead on top of the weight
+/// its call requires.
+pub type XcmWeigher = WeightInfoBounds<DioraXcmWeight<Runtime, Call>, Call, MaxInstructions>;
+
 match_types! {
 	pub type ParentOrParentsExecutivePlurality: impl Contains<MultiLocation> = {
 		MultiLocation { parents: 1, interior: Here } |
 		MultiLocation { parents: 1, interior: X1(Plurality { id: BodyId::Executive, .. }) }
 	};
+	pub type ParentOrSiblings: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: Here } |
+		MultiLocation { parents: 1, interior: X1(Parachain(_)) }
+	};
 }
 
-pub type Barrier = (
+/// The barrier of Diora, generic over the handler of the responses it expects so that it can be
+/// reused with another runtime.
+pub type XcmBarrier<ResponseHandler> = (
+	// Execution prepaid through the weight credit, i.e. local execution by `pallet_xcm`.
 	TakeWeightCredit,
+	// Anyone may execute, provided it buys the weight of the whole message upfront.
 	AllowTopLevelPaidExecutionFrom<Everything>,
+	// Parent and its exec plurality get free execution.
 	AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
-	// ^^^ Parent and its exec plurality get free execution
+	// Responses to the queries we sent, and only from the location they were sent to.
+	AllowKnownQueryResponses<ResponseHandler>,
+	// XCM version subscriptions, from the chains we exchange messages with.
+	AllowSubscriptionsFrom<ParentOrSiblings>,
+);
+
+pub type Barrier = XcmBarrier<PolkadotXcm>;
+
+/// Deposits the relay token paid for XCM execution to the `Receiver` account.
+pub struct DepositToAccount<C, Receiver>(sp_std::marker::PhantomData<(C, Receiver)>);
+impl<C: Currency<AccountId>, Receiver: Get<AccountId>> OnUnbalanced<C::NegativeImbalance>
+	for DepositToAccount<C, Receiver>
+{
+	fn on_nonzero_unbalanced(amount: C::NegativeImbalance) {
+		C::resolve_creating(&Receiver::get(), amount);
+	}
+}
+
+/// Means for paying for XCM execution, tried in order: the relay token at the weight of the
+/// execution, then the foreign assets governance has set a price in units per second for.
+/// Generic over the runtime, as [`AssetTransactors`].
+pub type Traders<R> = (
+	UsingComponents<
+		IdentityFee<Balance>,
+		RocLocation,
+		AccountId,
+		pallet_balances::Pallet<R>,
+		DepositToAccount<pallet_balances::Pallet<R>, XcmFeesAccount>,
+	>,
+	pallet_asset_manager::FirstAssetTrader<
+		R,
+		pallet_asset_manager::XcmFeesToAccount<
+			pallet_assets::Pallet<R>,
+			ForeignAssetMatcher<R>,
+			AccountId,
+			XcmFeesAccount,
+		>,
+	>,
 );
 
 pub struct XcmConfig;
@@ -565,14 +898,17 @@ impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
-	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	type IsReserve = NativeAsset;
-	type IsTeleporter = NativeAsset; // Should be enough to allow teleportation of ROC
+	type AssetTransactor = AssetTransactors<Runtime>;
+	type OriginConverter = XcmOriginToTransactDispatchOrigin<Runtime>;
+	// Native assets come from their own chain, foreign assets from the chain they are located in.
+	type IsReserve = (NativeAsset, pallet_asset_manager::IsReserveChain<Runtime>);
+	// No asset is teleported to us. The relay token is backed by our sovereign account on the
+	// relay chain, so also minting it on teleports would leave part of it unbacked.
+	type IsTeleporter = ();
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
-	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
-	type Trader = UsingComponents<IdentityFee<Balance>, RocLocation, AccountId, Balances, ()>;
+	type Weigher = XcmWeigher;
+	type Trader = Traders<Runtime>;
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
 	type AssetClaims = PolkadotXcm;
@@ -583,8 +919,33 @@ parameter_types! {
 	pub const MaxDownwardMessageWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 10;
 }
 
-/// No local origins on this chain are allowed to dispatch XCM sends/executions.
-pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+/// Converts a local signed origin into an `AccountKey20` XCM location, so that it can send and
+/// execute XCM messages.
+pub type LocalOriginToLocation = SignedToAccountId20<Origin, AccountId, RelayNetwork>;
+
+/// Like `xcm_builder::SignedToAccountId32`, but for 20-byte accounts.
+pub struct SignedToAccountId20<Origin, AccountId, Network>(
+	sp_std::marker::PhantomData<(Origin, AccountId, Network)>,
+);
+impl<Origin: OriginTrait + Clone, AccountId: Into<H160>, Network: Get<NetworkId>>
+	xcm_executor::traits::Convert<Origin, MultiLocation>
+	for SignedToAccountId20<Origin, AccountId, Network>
+where
+	Origin::PalletsOrigin: From<frame_system::RawOrigin<AccountId>>
+		+ TryInto<frame_system::RawOrigin<AccountId>, Error = Origin::PalletsOrigin>,
+{
+	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
+		o.try_with_caller(|caller| match caller.try_into() {
+			Ok(frame_system::RawOrigin::Signed(who)) => Ok(AccountKey20 {
+				key: who.into().into(),
+				network: Network::get(),
+			}
+			.into()),
+			Ok(other) => Err(other.into()),
+			Err(other) => Err(other),
+		})
+	}
+}
 
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
@@ -604,7 +965,7 @@ impl pallet_xcm::Config for Runtime {
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Everything;
-	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Origin = Origin;
 	type Call = Call;
@@ -623,16 +984,16 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type ChannelInfo = ParachainSystem;
 	type VersionWrapper = ();
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
-	type ControllerOrigin = EnsureRoot<AccountId>;
-	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
-	type WeightInfo = ();
+	type ExecuteOverweightOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type ControllerOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin<Runtime>;
+	type WeightInfo = weights::cumulus_pallet_xcmp_queue::WeightInfo<Runtime>;
 }
 
 impl cumulus_pallet_dmp_queue::Config for Runtime {
 	type Event = Event;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type ExecuteOverweightOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 }
 
 impl pallet_author_inherent::Config for Runtime {
@@ -641,17 +1002,20 @@ impl pallet_author_inherent::Config for Runtime {
 	type AccountLookup = PotentialAuthorSet;
 	type EventHandler = ();
 	type CanAuthor = AuthorFilter;
-	type WeightInfo = ();
+	type WeightInfo = weights::pallet_author_inherent::WeightInfo<Runtime>;
 }
 
 impl pallet_author_slot_filter::Config for Runtime {
 	type Event = Event;
 	type RandomnessSource = RandomnessCollectiveFlip;
 	type PotentialAuthors = PotentialAuthorSet;
-	type WeightInfo = ();
+	type UpdateOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type WeightInfo = weights::pallet_author_slot_filter::WeightInfo<Runtime>;
 }
 
-impl pallet_account_set::Config for Runtime {}
+impl pallet_account_set::Config for Runtime {
+	type Event = Event;
+}
 
 /// Configure the pallet template in pallets/template.
 impl pallet_template::Config for Runtime {
@@ -686,20 +1050,100 @@ impl pallet_evm::GasWeightMapping for DioraGasWeightMapping {
 	}
 }
 
+/// Substrate accounts are Ethereum addresses, so an EVM address maps to the account with the
+/// same 20 bytes.
+pub struct IdentityAddressMapping;
+impl pallet_evm::AddressMapping<AccountId> for IdentityAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		address.into()
+	}
+}
+
+/// Re-keys the balances exported from a chain with 32-byte accounts, where the EVM address `h`
+/// was backed by the hashed account `blake2_256("evm:" ++ h)`, into `balances` genesis entries.
+///
+/// A hashed account cannot be turned back into its address, so the EVM addresses known to the
+/// old chain are given in `addresses`. Balances of the other legacy accounts have no Ethereum
+/// key to be re-keyed to and are returned apart, to be settled outside of the genesis.
+pub fn rekey_legacy_balances(
+	legacy_balances: Vec<(sp_runtime::AccountId32, Balance)>,
+	addresses: &[H160],
+) -> (Vec<(AccountId, Balance)>, Vec<(sp_runtime::AccountId32, Balance)>) {
+	use pallet_evm::AddressMapping;
+	use sp_std::collections::btree_map::BTreeMap;
+
+	let hashed: BTreeMap<_, _> = addresses
+		.iter()
+		.map(|address| {
+			(pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(*address), *address)
+		})
+		.collect();
+
+	let mut rekeyed = BTreeMap::<AccountId, Balance>::new();
+	let mut unmapped = Vec::new();
+	for (legacy, balance) in legacy_balances {
+		match hashed.get(&legacy) {
+			Some(address) => {
+				let total = rekeyed.entry((*address).into()).or_default();
+				*total = total.saturating_add(balance);
+			},
+			None => unmapped.push((legacy, balance)),
+		}
+	}
+
+	(rekeyed.into_iter().collect(), unmapped)
+}
+
+/// Runtime calls that contracts may make through the dispatch precompile.
+///
+/// Calls back into the EVM are never allowed, neither are calls that only make sense for a
+/// privileged or inherent origin. Token holders and collective members can take part in
+/// governance from a contract account.
+pub struct DispatchPrecompileFilter;
+impl Contains<Call> for DispatchPrecompileFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Balances(pallet_balances::Call::transfer { .. })
+				| Call::Balances(pallet_balances::Call::transfer_keep_alive { .. })
+				| Call::Balances(pallet_balances::Call::transfer_all { .. })
+				| Call::PolkadotXcm(pallet_xcm::Call::reserve_transfer_assets { .. })
+				| Call::PolkadotXcm(pallet_xcm::Call::limited_reserve_transfer_assets { .. })
+				| Call::PolkadotXcm(pallet_xcm::Call::teleport_assets { .. })
+				| Call::PolkadotXcm(pallet_xcm::Call::limited_teleport_assets { .. })
+				| Call::Democracy(pallet_democracy::Call::propose { .. })
+				| Call::Democracy(pallet_democracy::Call::second { .. })
+				| Call::Democracy(pallet_democracy::Call::vote { .. })
+				| Call::Democracy(pallet_democracy::Call::remove_vote { .. })
+				| Call::Democracy(pallet_democracy::Call::delegate { .. })
+				| Call::Democracy(pallet_democracy::Call::undelegate { .. })
+				| Call::Democracy(pallet_democracy::Call::unlock { .. })
+				| Call::Democracy(pallet_democracy::Call::note_preimage { .. })
+				| Call::Council(pallet_collective::Call::propose { .. })
+				| Call::Council(pallet_collective::Call::vote { .. })
+				| Call::Council(pallet_collective::Call::close { .. })
+				| Call::TechnicalCommittee(pallet_collective::Call::propose { .. })
+				| Call::TechnicalCommittee(pallet_collective::Call::vote { .. })
+				| Call::TechnicalCommittee(pallet_collective::Call::close { .. })
+		)
+	}
+}
+
 impl pallet_evm::Config for Runtime {
 	type FeeCalculator = BaseFee;
 	type GasWeightMapping = DioraGasWeightMapping;
 	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressRoot<AccountId>;
 	type WithdrawOrigin = EnsureAddressNever<AccountId>;
-	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+	// Substrate and EVM accounts are the same 20-byte address.
+	type AddressMapping = IdentityAddressMapping;
 	type Currency = Balances;
 	type Event = Event;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type PrecompilesType = DioraPrecompiles<Runtime>;
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = ChainId;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, DealWithFees>;
 	type BlockGasLimit = BlockGasLimit;
 	type FindAuthor = ();
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
@@ -714,27 +1158,458 @@ parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = U256::from(450_000_000_000u128);
 }
 
-pub struct BaseFeeThreshold;
-impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
-	fn lower() -> Permill {
-		Permill::zero()
+impl pallet_base_fee::Config for Runtime {
+	type Event = Event;
+	// Block fullness thresholds are stored on-chain and tuned by governance.
+	type Threshold = BaseFeeParams;
+	// Tells `pallet_base_fee` whether to calculate a new BaseFee `on_finalize` or not.
+	type IsActive = ConstBool<true>;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+}
+
+impl pallet_base_fee_params::Config for Runtime {
+	type Event = Event;
+	type SetOrigin = EnsureRootOrHalfCouncil;
+}
+
+impl pallet_deployment_allowlist::Config for Runtime {
+	type Event = Event;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+}
+
+/// Derives an account from the hash of any XCM location, as `xcm_builder::Account32Hash` does
+/// for 32-byte accounts.
+pub struct Account20Hash<AccountId>(sp_std::marker::PhantomData<AccountId>);
+impl<AccountId: From<H160> + Clone> xcm_executor::traits::Convert<MultiLocation, AccountId>
+	for Account20Hash<AccountId>
+{
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		let hash = ("multiloc", location.borrow()).using_encoded(sp_io::hashing::blake2_256);
+		Ok(H160::from_slice(&hash[..20]).into())
+	}
+
+	fn reverse_ref(_: impl Borrow<AccountId>) -> Result<MultiLocation, ()> {
+		Err(())
 	}
-	fn ideal() -> Permill {
-		Permill::from_parts(500_000)
+}
+
+/// The accounts XCM locations send Ethereum transactions from: the account of the location when
+/// `LocationToAccountId` converts it, the hash of the location otherwise. Accounts of other
+/// chains thereby get an address of their own, which they fund to pay for their transactions.
+pub type LocationToEthereumAccount = (LocationToAccountId, Account20Hash<AccountId>);
+
+/// Ensures the origin is an XCM location, as `Transact` dispatches with `OriginKind::Xcm`, and
+/// resolves it to the address of its Ethereum account.
+pub struct EnsureXcmEthereumAccount;
+impl EnsureOrigin<Origin> for EnsureXcmEthereumAccount {
+	type Success = H160;
+
+	fn try_origin(o: Origin) -> Result<H160, Origin> {
+		let location = pallet_xcm::EnsureXcm::<Everything>::try_origin(o)?;
+		<LocationToEthereumAccount as xcm_executor::traits::Convert<_, AccountId>>::convert(
+			location,
+		)
+		.map(Into::into)
+		.map_err(|location| pallet_xcm::Origin::Xcm(location).into())
 	}
-	fn upper() -> Permill {
-		Permill::from_parts(1_000_000)
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		pallet_xcm::Origin::Xcm(MultiLocation::parent()).into()
 	}
 }
 
-impl pallet_base_fee::Config for Runtime {
+/// Lets an account send Ethereum transactions through XCM on behalf of the accounts it is an
+/// `Any` proxy of without delay.
+pub struct EthereumXcmProxies;
+impl pallet_ethereum_xcm::ProxyCheck<AccountId> for EthereumXcmProxies {
+	fn is_proxy(real: &AccountId, delegate: &AccountId) -> bool {
+		Proxy::find_proxy(real, delegate, Some(ProxyType::Any))
+			.map_or(false, |proxy| proxy.delay == 0)
+	}
+}
+
+impl pallet_ethereum_xcm::Config for Runtime {
+	type XcmEthereumOrigin = EnsureXcmEthereumAccount;
+	type Proxies = EthereumXcmProxies;
+	// Contracts deployed through XCM are subject to the same allowlist as signed transactions.
+	type Deployers = DeploymentAllowlist;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
 	type Event = Event;
-	type Threshold = BaseFeeThreshold;
-	// Tells `pallet_base_fee` whether to calculate a new BaseFee `on_finalize` or not.
-	type IsActive = ConstBool<false>;
-	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096 * 1024;
+	pub const PreimageBaseDeposit: Balance = UNIT;
+	pub const PreimageByteDeposit: Balance = MICROUNIT;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+/// The council, deciding on proposals to the chain and its policies.
+pub type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+/// The technical committee, fast-tracking urgent upgrades and in charge of the operation of the
+/// chain: block authoring and the XCM queues.
+pub type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
+/// Root, or at least two thirds of the technical committee.
+pub type EnsureRootOrTwoThirdsTechnicalCommittee = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>,
+>;
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const EnactmentPeriod: BlockNumber = DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const MinimumDeposit: Balance = 100 * UNIT;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
 }
 
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// Half of the council can put a proposal to a super-majority-approve referendum.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
+	/// Three quarters of the council can put a proposal to a majority-carries referendum.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>;
+	/// The whole council can put a proposal to a super-majority-against referendum.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	/// Two thirds of the technical committee can shorten the voting period of an external
+	/// proposal.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+	/// The whole technical committee can shorten it below `FastTrackVotingPeriod`.
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>;
+	type InstantAllowed = ConstBool<true>;
+	/// Two thirds of the council can cancel a referendum.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	/// The whole technical committee can cancel a proposal before it is tabled.
+	type CancelProposalOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any member of the technical committee can veto an external proposal once.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const UpgradeMinimumDelay: BlockNumber = 10 * MINUTES;
+}
+
+/// Upgrades are scheduled by referendum, and the technical committee can stop them before they
+/// are enacted. Once due, any account enacts them through the parachain system.
+impl pallet_upgrade_scheduler::Config for Runtime {
+	type Event = Event;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type CancelOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type MinimumDelay = UpgradeMinimumDelay;
+	type WeightInfo = pallet_upgrade_scheduler::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * UNIT;
+	pub const SpendPeriod: BlockNumber = 6 * DAYS;
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	/// Three fifths of the council can approve a spend proposal...
+	type ApproveOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	>;
+	/// ...and more than half of it can reject one.
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	/// Funds left after a spend period are kept for the next ones.
+	type Burn = ();
+	type BurnDestination = ();
+	type SpendFunds = Bounties;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
+/// The fields of `pallet_treasury::Proposal`, in the order they are encoded.
+#[derive(Decode)]
+struct ProposalFields {
+	proposer: AccountId,
+	value: Balance,
+	beneficiary: AccountId,
+	bond: Balance,
+}
+
+parameter_types! {
+	pub const BountyDepositBase: Balance = UNIT;
+	pub const BountyDepositPayoutDelay: BlockNumber = 4 * DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 90 * DAYS;
+	pub const CuratorDepositMultiplier: Permill = Permill::from_percent(50);
+	pub const CuratorDepositMin: Option<Balance> = Some(UNIT);
+	pub const CuratorDepositMax: Option<Balance> = Some(100 * UNIT);
+	pub const BountyValueMinimum: Balance = 10 * UNIT;
+	pub const DataDepositPerByte: Balance = 10 * MILLIUNIT;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+
+/// Bounties are approved, given a curator and closed with the origins of treasury spends.
+impl pallet_bounties::Config for Runtime {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type CuratorDepositMultiplier = CuratorDepositMultiplier;
+	type CuratorDepositMin = CuratorDepositMin;
+	type CuratorDepositMax = CuratorDepositMax;
+	type BountyValueMinimum = BountyValueMinimum;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
+	type ChildBountyManager = ();
+}
+
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+20 bytes = 44 bytes.
+	pub const MultisigDepositBase: Balance = deposit(1, 76);
+	// Additional storage item size of 20 bytes.
+	pub const MultisigDepositFactor: Balance = deposit(0, 20);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+/// The calls a proxy account may make on behalf of the account it is a proxy for.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	sp_runtime::RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call that cannot move funds out of the account.
+	NonTransfer,
+	/// Governance calls: referenda, collective motions and treasury proposals.
+	Governance,
+	/// Rotating the `NimbusId` the account authors blocks with.
+	AuthorMapping,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				Call::Balances(..) |
+					Call::Assets(..) |
+					Call::PolkadotXcm(..) |
+					Call::Evm(..) |
+					Call::Ethereum(..) |
+					Call::EthereumXcm(..) |
+					Call::Treasury(..) |
+					Call::Bounties(..) |
+					Call::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					Call::Vesting(pallet_vesting::Call::force_vested_transfer { .. })
+			),
+			ProxyType::Governance => matches!(
+				c,
+				Call::Democracy(..) |
+					Call::Preimage(..) |
+					Call::Council(..) |
+					Call::TechnicalCommittee(..) |
+					Call::Treasury(..) |
+					Call::Bounties(..) |
+					Call::Utility(..)
+			),
+			ProxyType::AuthorMapping =>
+				matches!(c, Call::PotentialAuthorSet(..) | Call::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(ProxyType::NonTransfer, ProxyType::AuthorMapping) => true,
+			// Governance proxies spend from the treasury and bounties, which non-transfer proxies
+			// cannot.
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 21 bytes (20 bytes AccountId + 1 byte ProxyType).
+	pub const ProxyDepositFactor: Balance = deposit(0, 21);
+	pub const MaxProxies: u32 = 32;
+	// One storage item; key size 32, value size 16
+	pub const AnnouncementDepositBase: Balance = deposit(1, 16);
+	// Additional storage item size of 52 bytes (20 bytes AccountId + 32 bytes Hash).
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 52);
+	pub const MaxPending: u32 = 32;
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = UNIT;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+/// Identifier of the lock `pallet_vesting` puts on vesting accounts, which it does not export.
+const VESTING_ID: LockIdentifier = *b"vesting ";
+
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
@@ -755,17 +1630,19 @@ construct_runtime!(
 		// Monetary stuff.
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 12,
 
 		// Nimbus support. The order of these are important and shall not change.
 		AuthorInherent: pallet_author_inherent::{Pallet, Call, Storage, Inherent} = 20,
-		AuthorFilter: pallet_author_slot_filter::{Pallet, Storage, Event, Config} = 21,
-		PotentialAuthorSet: pallet_account_set::{Pallet, Storage, Config<T>} = 22,
+		AuthorFilter: pallet_author_slot_filter::{Pallet, Call, Storage, Event, Config} = 21,
+		PotentialAuthorSet: pallet_account_set::{Pallet, Call, Storage, Event<T>, Config<T>} = 22,
 
 		// XCM helpers.
 		XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 30,
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 33,
+		AssetManager: pallet_asset_manager::{Pallet, Call, Storage, Event<T>} = 34,
 
 		// Template
 		TemplatePallet: pallet_template::{Pallet, Call, Storage, Event<T>}  = 40,
@@ -774,9 +1651,62 @@ construct_runtime!(
         Evm: pallet_evm::{Pallet, Config, Call, Storage, Event<T>} = 51,
         Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin} = 52,
         BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event} = 54,
+        BaseFeeParams: pallet_base_fee_params::{Pallet, Call, Storage, Config, Event} = 55,
+        DeploymentAllowlist: pallet_deployment_allowlist::{Pallet, Call, Storage, Config, Event} = 56,
+        EthereumXcm: pallet_ethereum_xcm::{Pallet, Call} = 57,
+
+		// Governance
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 60,
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 61,
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>} = 62,
+		Council: pallet_collective::<Instance1>::{
+			Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>,
+		} = 63,
+		TechnicalCommittee: pallet_collective::<Instance2>::{
+			Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>,
+		} = 64,
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 65,
+		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>} = 66,
+		UpgradeScheduler: pallet_upgrade_scheduler::{Pallet, Call, Storage, Event<T>} = 67,
+
+		// Account management
+		Utility: pallet_utility::{Pallet, Call, Event} = 70,
+		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 71,
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 72,
+		// Its genesis must be built after the one of `Evm`, which funds the vesting accounts.
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Config<T>, Event<T>} = 73,
 	}
 );
 
+/// Applies an Ethereum transaction while tracing its EVM execution.
+#[cfg(feature = "evm-tracing")]
+fn trace_ethereum_transaction(
+	extrinsic: <Block as BlockT>::Extrinsic,
+	trace_type: evm_tracer::TraceType,
+) -> evm_tracer::TransactionTrace {
+	// The gas used by the transaction, intrinsic cost and refunds included, is only known from
+	// its receipt. Receipts of the block being built are kept in `pallet_ethereum`'s `Pending`.
+	let cumulative_gas_used = || {
+		frame_support::storage::migration::get_storage_value::<
+			Vec<(EthereumTransaction, TransactionStatus, ethereum::ReceiptV3)>,
+		>(b"Ethereum", b"Pending", &[])
+		.and_then(|pending| pending.last().cloned())
+		.map(|(_, _, receipt)| match receipt {
+			ethereum::ReceiptV3::Legacy(r)
+			| ethereum::ReceiptV3::EIP2930(r)
+			| ethereum::ReceiptV3::EIP1559(r) => r.used_gas,
+		})
+		.unwrap_or_default()
+	};
+
+	let gas_before = cumulative_gas_used();
+	let (_, mut trace) =
+		evm_tracer::tracer::trace(trace_type, || Executive::apply_extrinsic(extrinsic));
+	trace.set_gas_used(cumulative_gas_used().saturating_sub(gas_before));
+
+	trace
+}
+
 impl_runtime_apis! {
 	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn chain_id() -> u64 {
@@ -922,6 +1852,71 @@ impl_runtime_apis! {
 			)
 		}
 	}
+
+	impl evm_tracer::DebugRuntimeApi<Block> for Runtime {
+		fn trace_transaction(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction: &EthereumTransaction,
+			trace_type: evm_tracer::TraceType,
+		) -> Result<evm_tracer::TransactionTrace, sp_runtime::DispatchError> {
+			#[cfg(feature = "evm-tracing")]
+			{
+				for extrinsic in extrinsics {
+					match &extrinsic.0.function {
+						Call::Ethereum(transact { transaction: t }) if t == transaction => {
+							return Ok(trace_ethereum_transaction(extrinsic, trace_type));
+						}
+						_ => {
+							let _ = Executive::apply_extrinsic(extrinsic);
+						}
+					}
+				}
+
+				Err(sp_runtime::DispatchError::Other(
+					"Failed to find Ethereum transaction among the extrinsics.",
+				))
+			}
+			#[cfg(not(feature = "evm-tracing"))]
+			{
+				let _ = (extrinsics, transaction, trace_type);
+				Err(sp_runtime::DispatchError::Other(
+					"Missing `evm-tracing` compile time feature flag.",
+				))
+			}
+		}
+
+		fn trace_block(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			known_transactions: Vec<H256>,
+			trace_type: evm_tracer::TraceType,
+		) -> Result<Vec<evm_tracer::TransactionTrace>, sp_runtime::DispatchError> {
+			#[cfg(feature = "evm-tracing")]
+			{
+				let mut traces = Vec::new();
+				for extrinsic in extrinsics {
+					match &extrinsic.0.function {
+						Call::Ethereum(transact { transaction })
+							if known_transactions.contains(&transaction.hash()) =>
+						{
+							traces.push(trace_ethereum_transaction(extrinsic, trace_type));
+						}
+						_ => {
+							let _ = Executive::apply_extrinsic(extrinsic);
+						}
+					}
+				}
+
+				Ok(traces)
+			}
+			#[cfg(not(feature = "evm-tracing"))]
+			{
+				let _ = (extrinsics, known_transactions, trace_type);
+				Err(sp_runtime::DispatchError::Other(
+					"Missing `evm-tracing` compile time feature flag.",
+				))
+			}
+		}
+	}
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
 			VERSION
@@ -1018,6 +2013,48 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl treasury_runtime_api::TreasuryApi<Block, AccountId, Balance> for Runtime {
+		fn balance() -> Balance {
+			Treasury::pot()
+		}
+
+		fn pending_spends() -> Vec<treasury_runtime_api::PendingSpend<AccountId, Balance>> {
+			let approvals = Treasury::approvals();
+			let mut spends = pallet_treasury::Proposals::<Runtime>::iter()
+				.map(|(index, proposal)| {
+					// The fields of `pallet_treasury::Proposal` are private, it is read through
+					// its encoding, which `ProposalFields` shares.
+					let ProposalFields { proposer, value, beneficiary, bond } =
+						Decode::decode(&mut &proposal.encode()[..])
+							.expect("same encoding as `pallet_treasury::Proposal`; qed");
+					treasury_runtime_api::PendingSpend {
+						index,
+						proposer,
+						value,
+						beneficiary,
+						bond,
+						approved: approvals.contains(&index),
+					}
+				})
+				.collect::<Vec<_>>();
+			spends.sort_by_key(|spend| spend.index);
+			spends
+		}
+	}
+
+	impl vesting_runtime_api::VestingApi<Block, AccountId, Balance> for Runtime {
+		fn vesting_balance(who: AccountId) -> vesting_runtime_api::VestingBalance<Balance> {
+			let locked =
+				<Vesting as VestingSchedule<AccountId>>::vesting_balance(&who).unwrap_or_default();
+			// The lock is only lowered to what the schedules still lock when the account vests.
+			let lock = Balances::locks(&who)
+				.iter()
+				.find(|lock| lock.id == VESTING_ID)
+				.map_or(0, |lock| lock.amount);
+			vesting_runtime_api::VestingBalance { locked, vested: lock.saturating_sub(locked) }
+		}
+	}
+
 	impl nimbus_primitives::NimbusApi<Block> for Runtime {
 		fn can_author(author: NimbusId, slot: u32, parent_header: &<Block as BlockT>::Header) -> bool {
 			// This runtime uses an entropy source that is updated during block initialization
@@ -1039,6 +2076,19 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> (Weight, Weight) {
+			log::info!("try-runtime::on_runtime_upgrade diora.");
+			let weight = Executive::try_runtime_upgrade().unwrap();
+			(weight, RuntimeBlockWeights::get().max_block)
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1048,14 +2098,22 @@ impl_runtime_apis! {
 			use frame_benchmarking::{list_benchmark, Benchmarking, BenchmarkList};
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
-			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
+			type XcmFungible = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
 
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
-			list_benchmark!(list, extra, pallet_collator_selection, CollatorSelection);
+			list_benchmark!(list, extra, pallet_author_inherent, AuthorInherent);
+			list_benchmark!(list, extra, pallet_author_slot_filter, AuthorFilter);
+			list_benchmark!(list, extra, pallet_account_set, PotentialAuthorSet);
+			list_benchmark!(list, extra, pallet_evm, Evm);
+			list_benchmark!(list, extra, pallet_upgrade_scheduler, UpgradeScheduler);
+			list_benchmark!(list, extra, cumulus_pallet_xcmp_queue, XcmpQueue);
+			list_benchmark!(list, extra, pallet_xcm_benchmarks::fungible, XcmFungible);
+			list_benchmark!(list, extra, pallet_xcm_benchmarks::generic, XcmGeneric);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1067,11 +2125,64 @@ impl_runtime_apis! {
 		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
 			use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark, TrackedStorageKey};
 
+			use frame_benchmarking::BenchmarkError;
+
 			use frame_system_benchmarking::Pallet as SystemBench;
 			impl frame_system_benchmarking::Config for Runtime {}
 
-			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
-			impl cumulus_pallet_session_benchmarking::Config for Runtime {}
+			fn relay_token(amount: Balance) -> MultiAsset {
+				MultiAsset { id: Concrete(RocLocation::get()), fun: Fungible(amount) }
+			}
+
+			parameter_types! {
+				// The relay token is only reserved from the relay chain, so teleports are not
+				// benchmarked.
+				pub const TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = None;
+				pub TrustedReserve: Option<(MultiLocation, MultiAsset)> =
+					Some((RocLocation::get(), relay_token(UNIT)));
+				// Teleports of the relay token are not tracked.
+				pub const NoCheckedAccount: Option<AccountId> = None;
+			}
+
+			impl pallet_xcm_benchmarks::Config for Runtime {
+				type XcmConfig = XcmConfig;
+				type AccountIdConverter = LocationToAccountId;
+				fn valid_destination() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RocLocation::get())
+				}
+				fn worst_case_holding() -> MultiAssets {
+					vec![relay_token(1_000_000 * UNIT)].into()
+				}
+			}
+
+			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
+				type TransactAsset = Balances;
+				type CheckedAccount = NoCheckedAccount;
+				type TrustedTeleporter = TrustedTeleporter;
+				type TrustedReserve = TrustedReserve;
+				fn get_multi_asset() -> MultiAsset {
+					relay_token(UNIT)
+				}
+			}
+
+			impl pallet_xcm_benchmarks::generic::Config for Runtime {
+				type Call = Call;
+				fn worst_case_response() -> (u64, Response) {
+					(0u64, Response::Version(Default::default()))
+				}
+				fn transact_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RocLocation::get())
+				}
+				fn subscribe_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RocLocation::get())
+				}
+				fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError> {
+					Ok((RocLocation::get(), Here.into(), vec![relay_token(UNIT)].into()))
+				}
+			}
+
+			type XcmFungible = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
@@ -1091,9 +2202,15 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
-			add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
-			add_benchmark!(params, batches, pallet_collator_selection, CollatorSelection);
+			add_benchmark!(params, batches, pallet_author_inherent, AuthorInherent);
+			add_benchmark!(params, batches, pallet_author_slot_filter, AuthorFilter);
+			add_benchmark!(params, batches, pallet_account_set, PotentialAuthorSet);
+			add_benchmark!(params, batches, pallet_evm, Evm);
+			add_benchmark!(params, batches, pallet_upgrade_scheduler, UpgradeScheduler);
+			add_benchmark!(params, batches, cumulus_pallet_xcmp_queue, XcmpQueue);
+			add_benchmark!(params, batches, pallet_xcm_benchmarks::fungible, XcmFungible);
+			add_benchmark!(params, batches, pallet_xcm_benchmarks::generic, XcmGeneric);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
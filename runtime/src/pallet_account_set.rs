@@ -1,6 +1,7 @@
 //! Small pallet responsible for storing a set of accounts, and their associated session keys.
 //! This is a minimal solution where staking would be used in practice.
-//! The accounts are set and genesis and never change.
+//! The accounts are set at genesis and never change, though each of them can rotate the
+//! `NimbusId` it authors blocks with.
 //!
 //! The Substrate ecosystem has a wide variety of real-world solutions and examples of what this
 //! pallet could be replaced with.
@@ -13,10 +14,50 @@ use frame_support::pallet;
 
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarks {
+	use super::{Config, Mapping, Pallet, StoredAccounts};
+	use frame_benchmarking::{account, benchmarks};
+	use nimbus_primitives::{AccountLookup, CanAuthor, NimbusId};
+	use sp_core::sr25519;
+	use sp_std::vec::Vec;
+
+	/// Largest author set the benchmarks are run against.
+	const MAX_AUTHORS: u32 = 100;
+
+	fn nimbus_id(seed: u32) -> NimbusId {
+		let mut raw = [0u8; 32];
+		raw[..4].copy_from_slice(&seed.to_le_bytes());
+		sr25519::Public::from_raw(raw).into()
+	}
+
+	benchmarks! {
+		// Authors are looked up by their `NimbusId` once per block when the pre-runtime digest
+		// is read by the author inherent.
+		lookup_account {
+			let author: T::AccountId = account("author", 0, 0);
+			Mapping::<T>::insert(nimbus_id(0), author.clone());
+		}: {
+			assert_eq!(Pallet::<T>::lookup_account(&nimbus_id(0)), Some(author));
+		}
+
+		// The eligibility check scans the whole stored set, so it is the one that grows with it.
+		can_author {
+			let a in 1 .. MAX_AUTHORS;
+			let authors: Vec<T::AccountId> = (0..a).map(|i| account("author", i, 0)).collect();
+			let last = authors.last().cloned().expect("at least one author; qed");
+			StoredAccounts::<T>::put(authors);
+		}: {
+			assert!(Pallet::<T>::can_author(&last, &0));
+		}
+	}
+}
+
 #[pallet]
 pub mod pallet {
 
 	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
 	#[cfg(feature = "std")]
 	use log::warn;
 	use nimbus_primitives::{AccountLookup, CanAuthor, NimbusId};
@@ -29,7 +70,52 @@ pub mod pallet {
 
 	/// Configuration trait of this pallet.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An author replaced the `NimbusId` it authors blocks with.
+		AssociationUpdated { account: T::AccountId, old: NimbusId, new: NimbusId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The `NimbusId` is not associated with the caller.
+		NotYourAssociation,
+		/// The `NimbusId` is already associated with an author.
+		AlreadyAssociated,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replace the `NimbusId` the caller authors blocks with by `new`.
+		///
+		/// Only the account `old` is associated with can rotate it, so session keys can be
+		/// changed without touching the author set itself.
+		// One read and one write for each of the two `Mapping` entries.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn update_association(
+			origin: OriginFor<T>,
+			old: NimbusId,
+			new: NimbusId,
+		) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			ensure!(
+				Mapping::<T>::get(&old).as_ref() == Some(&account),
+				Error::<T>::NotYourAssociation
+			);
+			ensure!(!Mapping::<T>::contains_key(&new), Error::<T>::AlreadyAssociated);
+
+			Mapping::<T>::remove(&old);
+			Mapping::<T>::insert(&new, &account);
+			Self::deposit_event(Event::AssociationUpdated { account, old, new });
+			Ok(())
+		}
+	}
 
 	/// The set of accounts that is stored in this pallet.
 	#[pallet::storage]
@@ -45,7 +131,8 @@ pub mod pallet {
 	#[pallet::getter(fn account_id_of)]
 	/// A mapping from the AuthorIds used in the consensus layer
 	/// to the AccountIds runtime.
-	type Mapping<T: Config> = StorageMap<_, Twox64Concat, NimbusId, T::AccountId, OptionQuery>;
+	pub(super) type Mapping<T: Config> =
+		StorageMap<_, Twox64Concat, NimbusId, T::AccountId, OptionQuery>;
 
 	#[pallet::genesis_config]
 	/// Genesis config for author mapping pallet
@@ -6,6 +6,18 @@ use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 
+use crate::DispatchPrecompileFilter;
+
+mod abi;
+mod batch;
+mod dispatch;
+mod vest;
+mod xcm_transfer;
+pub use batch::Batch;
+pub use dispatch::Dispatch;
+pub use vest::Vest;
+pub use xcm_transfer::XcmTransfer;
+
 pub struct DioraPrecompiles<R>(PhantomData<R>);
 
 impl<R> DioraPrecompiles<R>
@@ -16,7 +28,7 @@ impl<R> DioraPrecompiles<R>
         Self(Default::default())
     }
     pub fn used_addresses() -> sp_std::vec::Vec<H160> {
-        sp_std::vec![1, 2, 3, 4, 5, 1024, 1025]
+        sp_std::vec![1, 2, 3, 4, 5, 1024, 1025, 1026, 1027, 1028, 1029]
             .into_iter()
             .map(hash)
             .collect()
@@ -25,6 +37,9 @@ impl<R> DioraPrecompiles<R>
 impl<R> PrecompileSet for DioraPrecompiles<R>
     where
         R: pallet_evm::Config,
+        Dispatch<R, DispatchPrecompileFilter>: Precompile,
+        XcmTransfer<R>: Precompile,
+        Vest<R>: Precompile,
 {
     fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
         match handle.code_address() {
@@ -37,6 +52,10 @@ impl<R> PrecompileSet for DioraPrecompiles<R>
             // Non-Frontier specific nor Ethereum precompiles :
             a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
             a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+            a if a == hash(1026) => Some(Dispatch::<R, DispatchPrecompileFilter>::execute(handle)),
+            a if a == hash(1027) => Some(Batch::<R>::execute(handle)),
+            a if a == hash(1028) => Some(XcmTransfer::<R>::execute(handle)),
+            a if a == hash(1029) => Some(Vest::<R>::execute(handle)),
             _ => None,
         }
     }
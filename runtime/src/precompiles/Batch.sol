@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Batch precompile address.
+address constant BATCH_ADDRESS = 0x0000000000000000000000000000000000000403;
+
+/// @dev The Batch precompile instance.
+Batch constant BATCH_CONTRACT = Batch(BATCH_ADDRESS);
+
+/// @title Batch precompile
+/// @notice Performs several calls in a single transaction, with the transaction sender as
+/// `msg.sender` of every subcall. `value`, `callData` and `gasLimit` may be shorter than `to`,
+/// missing entries default to no value, no data and all the remaining gas. A gas limit of zero
+/// also forwards all the remaining gas.
+interface Batch {
+    /// @dev Performs all the subcalls, skipping the ones that fail.
+    /// @custom:selector 79df4b9c
+    function batchSome(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// @dev Performs the subcalls in order and stops at the first one that fails. The subcalls
+    /// that succeeded before it are kept.
+    /// @custom:selector cf0491c7
+    function batchSomeUntilFailure(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// @dev Performs all the subcalls, reverting the whole batch if any of them fails.
+    /// @custom:selector 96e292b8
+    function batchAll(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// @dev Emitted for each subcall that succeeded.
+    /// @custom:selector bf855484633929c3d6688eb3caf8eff910fb4bef030a8d7dbc9390d26759714d
+    event SubcallSucceeded(uint256 index);
+
+    /// @dev Emitted for each subcall that failed.
+    /// @custom:selector dbc5d06f4f877f959b1ff12d2161cdd693fa8e442ee53f1790b2804b24881f05
+    event SubcallFailed(uint256 index);
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Vest precompile address.
+address constant VEST_ADDRESS = 0x0000000000000000000000000000000000000405;
+
+/// @dev The Vest precompile instance.
+Vest constant VEST_CONTRACT = Vest(VEST_ADDRESS);
+
+/// @title Vest precompile
+/// @notice Releases the funds vesting schedules unlocked, which stay locked until `vest` or
+/// `vestOther` is called.
+interface Vest {
+    /// @dev Releases the unlocked funds of `msg.sender`. Reverts if it has no vesting schedule.
+    /// @custom:selector 458efde3
+    function vest() external;
+
+    /// @dev Releases the unlocked funds of `target`. Reverts if it has no vesting schedule.
+    /// @custom:selector 055e60c8
+    function vestOther(address target) external;
+
+    /// @dev The part of the balance of `who` its vesting schedules still lock at this block.
+    /// @custom:selector 9ae697bf
+    function lockedBalance(address who) external view returns (uint256);
+}
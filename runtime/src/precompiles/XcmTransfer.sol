@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The XcmTransfer precompile address.
+address constant XCM_TRANSFER_ADDRESS = 0x0000000000000000000000000000000000000404;
+
+/// @dev The XcmTransfer precompile instance.
+XcmTransfer constant XCM_TRANSFER_CONTRACT = XcmTransfer(XCM_TRANSFER_ADDRESS);
+
+/// @title XcmTransfer precompile
+/// @notice Sends assets of `msg.sender` to other chains through `pallet_xcm`.
+interface XcmTransfer {
+    /// @dev A location relative to this chain. Each junction is a type byte followed by its
+    /// big-endian fields:
+    /// 0x00 Parachain (uint32 id), 0x01 AccountId32 (32 bytes id, network),
+    /// 0x03 AccountKey20 (20 bytes key, network), 0x04 PalletInstance (uint8 index),
+    /// 0x05 GeneralIndex (uint128 index), 0x06 GeneralKey (key bytes).
+    /// A network is 0x00 for Any, 0x01 followed by the name for Named, 0x02 for Polkadot and
+    /// 0x03 for Kusama.
+    struct Multilocation {
+        uint8 parents;
+        bytes[] interior;
+    }
+
+    /// @dev Sends `amount` of `asset` to `destination`, this chain being the reserve of the asset.
+    /// The last junction of `destination` is the beneficiary on the chain the others point to.
+    /// @param weight The weight bought on the destination, 0 buying as much as the fees allow.
+    /// @custom:selector d6df14a2
+    function transferReserve(
+        Multilocation memory asset,
+        uint256 amount,
+        Multilocation memory destination,
+        uint64 weight
+    ) external;
+
+    /// @dev Teleports `amount` of `asset` to `destination`, which must trust this chain as a
+    /// teleporter of the asset. The last junction of `destination` is the beneficiary on the
+    /// chain the others point to.
+    /// @param weight The weight bought on the destination, 0 buying as much as the fees allow.
+    /// @custom:selector f7d5a09d
+    function transferTeleport(
+        Multilocation memory asset,
+        uint256 amount,
+        Multilocation memory destination,
+        uint64 weight
+    ) external;
+}
@@ -0,0 +1,101 @@
+//! Readers of ABI-encoded precompile arguments.
+//!
+//! `pos` is the position of a word in `args`, the input without its selector. Every reader
+//! reverts rather than panics on malformed input.
+
+use fp_evm::{ExitRevert, PrecompileFailure};
+use sp_core::{H160, U256};
+use sp_std::vec::Vec;
+
+pub fn read_word(args: &[u8], pos: usize) -> Result<&[u8], PrecompileFailure> {
+	let end = pos.checked_add(32).ok_or_else(|| revert("input too short"))?;
+	args.get(pos..end).ok_or_else(|| revert("input too short"))
+}
+
+pub fn read_u256(args: &[u8], pos: usize) -> Result<U256, PrecompileFailure> {
+	Ok(U256::from_big_endian(read_word(args, pos)?))
+}
+
+pub fn read_usize(args: &[u8], pos: usize) -> Result<usize, PrecompileFailure> {
+	let value = read_u256(args, pos)?;
+	if value > U256::from(u32::MAX) {
+		return Err(revert("offset or length out of bounds"));
+	}
+	Ok(value.low_u32() as usize)
+}
+
+pub fn read_u8(args: &[u8], pos: usize) -> Result<u8, PrecompileFailure> {
+	let value = read_u256(args, pos)?;
+	if value > U256::from(u8::MAX) {
+		return Err(revert("value is too large for uint8"));
+	}
+	Ok(value.low_u32() as u8)
+}
+
+pub fn read_u64(args: &[u8], pos: usize) -> Result<u64, PrecompileFailure> {
+	let value = read_u256(args, pos)?;
+	if value > U256::from(u64::MAX) {
+		return Err(revert("value is too large for uint64"));
+	}
+	Ok(value.low_u64())
+}
+
+pub fn read_u128(args: &[u8], pos: usize) -> Result<u128, PrecompileFailure> {
+	let value = read_u256(args, pos)?;
+	if value > U256::from(u128::MAX) {
+		return Err(revert("value is too large for uint128"));
+	}
+	Ok(value.low_u128())
+}
+
+pub fn read_address(args: &[u8], pos: usize) -> Result<H160, PrecompileFailure> {
+	let word = read_word(args, pos)?;
+	if word[..12].iter().any(|b| *b != 0) {
+		return Err(revert("invalid address"));
+	}
+	Ok(H160::from_slice(&word[12..]))
+}
+
+pub fn read_bytes(args: &[u8], pos: usize) -> Result<Vec<u8>, PrecompileFailure> {
+	let len = read_usize(args, pos)?;
+	let start = pos.saturating_add(32);
+	args.get(start..start.saturating_add(len))
+		.map(|bytes| bytes.to_vec())
+		.ok_or_else(|| revert("input too short"))
+}
+
+/// Reads the dynamic array whose offset is stored at `head`, decoding each 32 bytes element
+/// with `read`.
+pub fn read_array<T>(
+	args: &[u8],
+	head: usize,
+	read: impl Fn(&[u8], usize) -> Result<T, PrecompileFailure>,
+) -> Result<Vec<T>, PrecompileFailure> {
+	let offset = read_usize(args, head)?;
+	let len = read_usize(args, offset)?;
+	let start = offset.saturating_add(32);
+
+	// Every element takes at least a word, which bounds the length by the input size.
+	if len > args.len().saturating_sub(start) / 32 {
+		return Err(revert("input too short"));
+	}
+
+	(0..len).map(|i| read(args, start + 32 * i)).collect()
+}
+
+/// Reads the `bytes[]` whose offset is stored at `head`.
+pub fn read_bytes_array(args: &[u8], head: usize) -> Result<Vec<Vec<u8>>, PrecompileFailure> {
+	// Elements of a `bytes[]` are offsets relative to the start of the array content.
+	let start = read_usize(args, head)?.saturating_add(32);
+	read_array(args, head, |args, pos| {
+		let offset = read_usize(args, pos)?;
+		read_bytes(args, start.saturating_add(offset))
+	})
+}
+
+pub fn revert(message: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: message.as_bytes().to_vec(),
+	}
+}
@@ -0,0 +1,221 @@
+//! Precompile that performs several EVM calls in a single transaction.
+//!
+//! Each subcall is made with the original `msg.sender` as caller, so a wallet can bundle e.g. an
+//! approval and the action that uses it. The Solidity interface is in `Batch.sol`. Three modes are
+//! offered:
+//!
+//! - `batchSome` skips subcalls that fail and carries on with the next one,
+//! - `batchSomeUntilFailure` stops at the first failing subcall, keeping the previous ones,
+//! - `batchAll` reverts the whole batch if any subcall fails.
+//!
+//! A `SubcallSucceeded(index)` or `SubcallFailed(index)` log is emitted for every subcall made.
+
+use fp_evm::{
+	Context, ExitError, ExitReason, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileHandle,
+	PrecompileOutput, PrecompileResult, Transfer,
+};
+use pallet_evm::Precompile;
+use sp_core::{H160, H256, U256};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+use super::abi::{read_address, read_array, read_bytes_array, read_u256, read_u64, revert};
+
+/// `batchSome(address[],uint256[],bytes[],uint64[])`
+pub const SELECTOR_BATCH_SOME: [u8; 4] = [0x79, 0xdf, 0x4b, 0x9c];
+/// `batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])`
+pub const SELECTOR_BATCH_SOME_UNTIL_FAILURE: [u8; 4] = [0xcf, 0x04, 0x91, 0xc7];
+/// `batchAll(address[],uint256[],bytes[],uint64[])`
+pub const SELECTOR_BATCH_ALL: [u8; 4] = [0x96, 0xe2, 0x92, 0xb8];
+
+/// `SubcallSucceeded(uint256)`
+pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = [
+	0xbf, 0x85, 0x54, 0x84, 0x63, 0x39, 0x29, 0xc3, 0xd6, 0x68, 0x8e, 0xb3, 0xca, 0xf8, 0xef, 0xf9,
+	0x10, 0xfb, 0x4b, 0xef, 0x03, 0x0a, 0x8d, 0x7d, 0xbc, 0x93, 0x90, 0xc3, 0xd6, 0x75, 0x97, 0x14,
+];
+/// `SubcallFailed(uint256)`
+pub const LOG_SUBCALL_FAILED: [u8; 32] = [
+	0xdb, 0xc5, 0xd0, 0x6f, 0x4f, 0x87, 0x7f, 0x95, 0x9b, 0x1f, 0xf1, 0x2d, 0x21, 0x61, 0xcd, 0xd6,
+	0x93, 0xfa, 0x8e, 0x44, 0x2e, 0xe5, 0x3f, 0x17, 0x90, 0xb2, 0x80, 0x4b, 0x24, 0x88, 0x1f, 0x05,
+];
+
+/// Gas charged for a log with one topic and a 32 bytes payload (`LOG1`).
+const LOG_COST: u64 = 375 + 375 + 8 * 32;
+/// Gas charged for each subcall, as for a `CALL` to a cold account.
+const SUBCALL_COST: u64 = 2_600;
+/// Additional gas charged for subcalls transferring value.
+const SUBCALL_VALUE_COST: u64 = 9_000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+	BatchSome,
+	BatchSomeUntilFailure,
+	BatchAll,
+}
+
+struct Subcall {
+	to: H160,
+	value: U256,
+	call_data: Vec<u8>,
+	/// Zero forwards all the remaining gas.
+	gas_limit: u64,
+}
+
+pub struct Batch<R>(PhantomData<R>);
+
+impl<R> Precompile for Batch<R>
+where
+	R: pallet_evm::Config,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = handle.input();
+		if input.len() < 4 {
+			return Err(revert("missing selector"));
+		}
+		let mode = match [input[0], input[1], input[2], input[3]] {
+			SELECTOR_BATCH_SOME => Mode::BatchSome,
+			SELECTOR_BATCH_SOME_UNTIL_FAILURE => Mode::BatchSomeUntilFailure,
+			SELECTOR_BATCH_ALL => Mode::BatchAll,
+			_ => return Err(revert("unknown selector")),
+		};
+
+		if handle.is_static() {
+			return Err(revert("cannot batch in static context"));
+		}
+		// Subcalls are made on behalf of the caller, which must not be anybody else than the
+		// account that called the precompile.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		let subcalls = decode_subcalls(&input[4..])?;
+		Self::batch(handle, mode, subcalls)
+	}
+}
+
+impl<R> Batch<R>
+where
+	R: pallet_evm::Config,
+{
+	fn batch(
+		handle: &mut impl PrecompileHandle,
+		mode: Mode,
+		subcalls: Vec<Subcall>,
+	) -> PrecompileResult {
+		let caller = handle.context().caller;
+
+		for (index, subcall) in subcalls.into_iter().enumerate() {
+			let cost = if subcall.value.is_zero() {
+				SUBCALL_COST
+			} else {
+				SUBCALL_COST + SUBCALL_VALUE_COST
+			};
+			handle.record_cost(cost)?;
+
+			let remaining = handle.remaining_gas();
+			let forwarded = if subcall.gas_limit == 0 {
+				// Keep 1/64th of the gas, as for `CALL` since EIP-150.
+				remaining - remaining / 64
+			} else if subcall.gas_limit > remaining {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			} else {
+				subcall.gas_limit
+			};
+
+			let transfer = if subcall.value.is_zero() {
+				None
+			} else {
+				Some(Transfer {
+					source: caller,
+					target: subcall.to,
+					value: subcall.value,
+				})
+			};
+			let context = Context {
+				address: subcall.to,
+				caller,
+				apparent_value: subcall.value,
+			};
+
+			let (reason, output) = handle.call(
+				subcall.to,
+				transfer,
+				subcall.call_data,
+				Some(forwarded),
+				false,
+				&context,
+			);
+
+			match reason {
+				ExitReason::Succeed(_) => {
+					Self::log_subcall(handle, LOG_SUBCALL_SUCCEEDED, index)?;
+				}
+				ExitReason::Fatal(exit_status) => {
+					return Err(PrecompileFailure::Fatal { exit_status });
+				}
+				ExitReason::Revert(_) | ExitReason::Error(_) => match mode {
+					Mode::BatchAll => {
+						return Err(PrecompileFailure::Revert {
+							exit_status: ExitRevert::Reverted,
+							output,
+						});
+					}
+					Mode::BatchSomeUntilFailure => {
+						Self::log_subcall(handle, LOG_SUBCALL_FAILED, index)?;
+						break;
+					}
+					Mode::BatchSome => {
+						Self::log_subcall(handle, LOG_SUBCALL_FAILED, index)?;
+					}
+				},
+			}
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: Default::default(),
+		})
+	}
+
+	fn log_subcall(
+		handle: &mut impl PrecompileHandle,
+		topic: [u8; 32],
+		index: usize,
+	) -> Result<(), PrecompileFailure> {
+		handle.record_cost(LOG_COST)?;
+
+		let mut data = [0u8; 32];
+		U256::from(index).to_big_endian(&mut data);
+		let address = handle.code_address();
+		handle.log(address, vec![H256(topic)], data.to_vec())?;
+
+		Ok(())
+	}
+}
+
+/// Decodes the ABI-encoded `(address[], uint256[], bytes[], uint64[])` arguments.
+///
+/// The number of subcalls is the number of addresses. Missing values, call data and gas limits
+/// default to zero, empty and "all the remaining gas" respectively.
+fn decode_subcalls(args: &[u8]) -> Result<Vec<Subcall>, PrecompileFailure> {
+	let to = read_array(args, 0, read_address)?;
+	let value = read_array(args, 32, read_u256)?;
+	let call_data = read_bytes_array(args, 64)?;
+	let gas_limit = read_array(args, 96, read_u64)?;
+
+	if value.len() > to.len() || call_data.len() > to.len() || gas_limit.len() > to.len() {
+		return Err(revert("more values, call data or gas limits than addresses"));
+	}
+
+	Ok(to
+		.into_iter()
+		.enumerate()
+		.map(|(i, to)| Subcall {
+			to,
+			value: value.get(i).copied().unwrap_or_default(),
+			call_data: call_data.get(i).cloned().unwrap_or_default(),
+			gas_limit: gas_limit.get(i).copied().unwrap_or_default(),
+		})
+		.collect())
+}
@@ -0,0 +1,109 @@
+//! Precompile that lets EVM contracts dispatch runtime calls.
+//!
+//! The input is a SCALE-encoded `Call`. It is dispatched with a signed origin for the account the
+//! EVM caller maps to, so a contract acts on its own account and an externally owned account on
+//! the account controlled by the same key. Only calls accepted by the `Filter` are allowed, and the
+//! precompile cannot be reached through `DELEGATECALL` or `CALLCODE`, with which a contract would
+//! dispatch on behalf of whoever called it.
+//!
+//! Gas is charged from the call weight through `GasWeightMapping`: the declared weight must fit
+//! in the remaining gas, but only the actual weight reported by the call is charged.
+
+use codec::{Decode, DecodeLimit};
+use fp_evm::{
+	ExitError, ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+	PrecompileResult,
+};
+use frame_support::{
+	dispatch::Dispatchable,
+	storage::{with_transaction, TransactionOutcome},
+	traits::Contains,
+	weights::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_std::marker::PhantomData;
+
+use super::abi::revert;
+
+/// Maximum nesting depth accepted when decoding the call.
+pub const DISPATCH_DECODE_LIMIT: u32 = 8;
+
+pub struct Dispatch<R, Filter>(PhantomData<(R, Filter)>);
+
+impl<R, Filter> Dispatch<R, Filter>
+where
+	R: pallet_evm::Config,
+{
+	/// Gas charged for a call that declared `info` and reported `post_info` once dispatched.
+	pub fn dispatch_gas(info: &DispatchInfo, post_info: &PostDispatchInfo) -> u64 {
+		R::GasWeightMapping::weight_to_gas(post_info.calc_actual_weight(info))
+	}
+}
+
+impl<R, Filter> Precompile for Dispatch<R, Filter>
+where
+	R: pallet_evm::Config,
+	R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	Filter: Contains<R::Call>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		if handle.is_static() {
+			return Err(error("cannot dispatch in static context"));
+		}
+		// The call is dispatched on behalf of the caller, which must not be anybody else than the
+		// account that called the precompile.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		let call = R::Call::decode_with_depth_limit(DISPATCH_DECODE_LIMIT, &mut handle.input())
+			.map_err(|_| error("decode failed"))?;
+
+		if !Filter::contains(&call) {
+			return Err(error("call filtered"));
+		}
+
+		let info = call.get_dispatch_info();
+		// Operational calls, such as the votes of collective members, are accepted: their priority
+		// in the transaction pool means nothing inside an EVM transaction.
+		if info.pays_fee != Pays::Yes || info.class == DispatchClass::Mandatory {
+			return Err(error("invalid call"));
+		}
+		if info.weight > R::GasWeightMapping::gas_to_weight(handle.remaining_gas()) {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas,
+			});
+		}
+
+		let origin = R::AddressMapping::into_account_id(handle.context().caller);
+		let result = with_transaction(|| {
+			let result = call.dispatch(Some(origin).into());
+			if result.is_ok() {
+				TransactionOutcome::Commit(result)
+			} else {
+				TransactionOutcome::Rollback(result)
+			}
+		});
+
+		match result {
+			Ok(post_info) => {
+				handle.record_cost(Self::dispatch_gas(&info, &post_info))?;
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					output: Default::default(),
+				})
+			}
+			Err(e) => {
+				handle.record_cost(Self::dispatch_gas(&info, &e.post_info))?;
+				Err(error("dispatch execution failed"))
+			}
+		}
+	}
+}
+
+fn error(message: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error {
+		exit_status: ExitError::Other(message.into()),
+	}
+}
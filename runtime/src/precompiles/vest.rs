@@ -0,0 +1,129 @@
+//! Precompile that releases the vested funds of an account through `pallet_vesting`.
+//!
+//! `pallet_vesting` only lifts its lock on the funds a schedule released when `vest` is called,
+//! which EVM accounts cannot do with a signed Substrate origin. This precompile calls `vest` or
+//! `vest_other` on behalf of the account the EVM caller maps to, and reports how much of an
+//! account's balance its schedules still lock. The Solidity interface is in `Vest.sol`.
+
+use fp_evm::{
+	ExitError, ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+	PrecompileResult,
+};
+use frame_support::{
+	dispatch::Dispatchable,
+	traits::{Currency, Get, VestingSchedule},
+	weights::{GetDispatchInfo, PostDispatchInfo},
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_core::U256;
+use sp_runtime::traits::StaticLookup;
+use sp_std::marker::PhantomData;
+
+use super::abi::{read_address, revert};
+
+/// `vest()`
+pub const SELECTOR_VEST: [u8; 4] = [0x45, 0x8e, 0xfd, 0xe3];
+/// `vestOther(address)`
+pub const SELECTOR_VEST_OTHER: [u8; 4] = [0x05, 0x5e, 0x60, 0xc8];
+/// `lockedBalance(address)`
+pub const SELECTOR_LOCKED_BALANCE: [u8; 4] = [0x9a, 0xe6, 0x97, 0xbf];
+
+type CallOf<R> = <R as frame_system::Config>::Call;
+type BalanceOf<R> = <<R as pallet_vesting::Config>::Currency as Currency<
+	<R as frame_system::Config>::AccountId,
+>>::Balance;
+
+pub struct Vest<R>(PhantomData<R>);
+
+impl<R> Precompile for Vest<R>
+where
+	R: pallet_evm::Config + pallet_vesting::Config,
+	R::Lookup: StaticLookup<Source = R::AccountId>,
+	CallOf<R>: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_vesting::Call<R>>,
+	<CallOf<R> as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	BalanceOf<R>: Into<U256>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = handle.input();
+		if input.len() < 4 {
+			return Err(revert("missing selector"));
+		}
+		let selector = [input[0], input[1], input[2], input[3]];
+		let args = &input[4..];
+
+		let call = match selector {
+			SELECTOR_LOCKED_BALANCE => {
+				let who = R::AddressMapping::into_account_id(read_address(args, 0)?);
+				return Self::locked_balance(handle, who);
+			},
+			SELECTOR_VEST => pallet_vesting::Call::<R>::vest {},
+			SELECTOR_VEST_OTHER => pallet_vesting::Call::<R>::vest_other {
+				target: R::AddressMapping::into_account_id(read_address(args, 0)?),
+			},
+			_ => return Err(revert("unknown selector")),
+		};
+
+		if handle.is_static() {
+			return Err(revert("cannot vest in static context"));
+		}
+		// `vest` releases the funds of the caller's account, which must not be anybody else
+		// than the account that called the precompile.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		Self::dispatch(handle, call.into())
+	}
+}
+
+impl<R> Vest<R>
+where
+	R: pallet_evm::Config + pallet_vesting::Config,
+	CallOf<R>: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<CallOf<R> as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	BalanceOf<R>: Into<U256>,
+{
+	/// Returns the part of the balance of `who` its schedules still lock at this block, including
+	/// what `vest` would release.
+	fn locked_balance(handle: &mut impl PrecompileHandle, who: R::AccountId) -> PrecompileResult {
+		handle.record_cost(R::GasWeightMapping::weight_to_gas(
+			<R as frame_system::Config>::DbWeight::get().reads(1),
+		))?;
+
+		let locked = pallet_vesting::Pallet::<R>::vesting_balance(&who).unwrap_or_default();
+		let mut output = [0u8; 32];
+		locked.into().to_big_endian(&mut output);
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: output.to_vec(),
+		})
+	}
+
+	fn dispatch(handle: &mut impl PrecompileHandle, call: CallOf<R>) -> PrecompileResult {
+		let info = call.get_dispatch_info();
+		if info.weight > R::GasWeightMapping::gas_to_weight(handle.remaining_gas()) {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas,
+			});
+		}
+
+		let origin = R::AddressMapping::into_account_id(handle.context().caller);
+		let (post_info, succeeded) = match call.dispatch(Some(origin).into()) {
+			Ok(post_info) => (post_info, true),
+			Err(e) => (e.post_info, false),
+		};
+		handle.record_cost(R::GasWeightMapping::weight_to_gas(
+			post_info.calc_actual_weight(&info),
+		))?;
+
+		if !succeeded {
+			return Err(revert("nothing to vest"));
+		}
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: Default::default(),
+		})
+	}
+}
@@ -0,0 +1,241 @@
+//! Precompile that sends assets to other chains over XCM.
+//!
+//! EVM accounts have no signed Substrate origin to call `pallet_xcm` with, so this precompile
+//! executes the local message of `limited_reserve_transfer_assets` or `limited_teleport_assets`
+//! on behalf of the account the EVM caller maps to, with the origin conversion and transfer
+//! filters of `pallet_xcm`. The Solidity interface is in `XcmTransfer.sol`.
+//!
+//! Locations are passed as a `Multilocation { uint8 parents; bytes[] interior; }` struct, each
+//! junction being encoded as a type byte followed by its fields:
+//!
+//! | type   | junction          | fields                                    |
+//! |--------|-------------------|-------------------------------------------|
+//! | `0x00` | `Parachain`       | `uint32` id                               |
+//! | `0x01` | `AccountId32`     | 32 bytes id, network                      |
+//! | `0x03` | `AccountKey20`    | 20 bytes key, network                     |
+//! | `0x04` | `PalletInstance`  | `uint8` index                             |
+//! | `0x05` | `GeneralIndex`    | `uint128` index                           |
+//! | `0x06` | `GeneralKey`      | key bytes                                 |
+//!
+//! Integers are big-endian. A network is `0x00` for `Any`, `0x01` followed by the name for
+//! `Named`, `0x02` for `Polkadot` and `0x03` for `Kusama`.
+//!
+//! The destination is the location of the beneficiary seen from this chain, e.g.
+//! `(1, [Parachain(2000), AccountKey20])`: its last junction is the beneficiary on the chain its
+//! other junctions point to.
+//!
+//! Gas is charged from the weight of the local message given by the XCM `Weigher`. The
+//! precompile reverts if the message is not fully executed, so that no assets are lost when e.g.
+//! the destination cannot be reached.
+
+use fp_evm::{ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Contains, EnsureOrigin},
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+use xcm::latest::{prelude::*, ExecuteXcm};
+use xcm_executor::traits::{InvertLocation, WeightBounds};
+
+use super::abi::{read_bytes_array, read_u128, read_u64, read_u8, read_usize, revert};
+
+/// `transferReserve((uint8,bytes[]),uint256,(uint8,bytes[]),uint64)`
+pub const SELECTOR_TRANSFER_RESERVE: [u8; 4] = [0xd6, 0xdf, 0x14, 0xa2];
+/// `transferTeleport((uint8,bytes[]),uint256,(uint8,bytes[]),uint64)`
+pub const SELECTOR_TRANSFER_TELEPORT: [u8; 4] = [0xf7, 0xd5, 0xa0, 0x9d];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	Reserve,
+	Teleport,
+}
+
+/// Arguments of a transfer.
+struct Transfer {
+	asset: MultiAsset,
+	dest: MultiLocation,
+	beneficiary: MultiLocation,
+	weight_limit: WeightLimit,
+}
+
+pub struct XcmTransfer<R>(PhantomData<R>);
+
+impl<R> Precompile for XcmTransfer<R>
+where
+	R: pallet_evm::Config + pallet_xcm::Config,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = handle.input();
+		if input.len() < 4 {
+			return Err(revert("missing selector"));
+		}
+		let kind = match [input[0], input[1], input[2], input[3]] {
+			SELECTOR_TRANSFER_RESERVE => Kind::Reserve,
+			SELECTOR_TRANSFER_TELEPORT => Kind::Teleport,
+			_ => return Err(revert("unknown selector")),
+		};
+
+		if handle.is_static() {
+			return Err(revert("cannot transfer in static context"));
+		}
+		// Assets are sent from the caller's account, which must not be anybody else than the
+		// account that called the precompile.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		let transfer = decode_transfer(&input[4..])?;
+		Self::transfer(handle, kind, transfer)
+	}
+}
+
+impl<R> XcmTransfer<R>
+where
+	R: pallet_evm::Config + pallet_xcm::Config,
+{
+	/// The local message `pallet_xcm` executes for `transfer`.
+	fn message(
+		kind: Kind,
+		transfer: Transfer,
+	) -> Result<Xcm<<R as frame_system::Config>::Call>, PrecompileFailure> {
+		let ancestry = <R as pallet_xcm::Config>::LocationInverter::ancestry();
+		let fees = transfer
+			.asset
+			.clone()
+			.reanchored(&transfer.dest, &ancestry)
+			.map_err(|_| revert("cannot reanchor asset"))?;
+		let xcm = Xcm(vec![
+			BuyExecution { fees, weight_limit: transfer.weight_limit },
+			DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: transfer.beneficiary },
+		]);
+
+		let assets = MultiAssets::from(transfer.asset);
+		let dest = transfer.dest;
+		Ok(Xcm(match kind {
+			Kind::Reserve => vec![TransferReserveAsset { assets, dest, xcm }],
+			Kind::Teleport => {
+				vec![WithdrawAsset(assets), InitiateTeleport { assets: Wild(All), dest, xcm }]
+			}
+		}))
+	}
+
+	fn transfer(
+		handle: &mut impl PrecompileHandle,
+		kind: Kind,
+		transfer: Transfer,
+	) -> PrecompileResult {
+		let account = R::AddressMapping::into_account_id(handle.context().caller);
+		let origin = <R as pallet_xcm::Config>::ExecuteXcmOrigin::ensure_origin(
+			frame_system::RawOrigin::Signed(account).into(),
+		)
+		.map_err(|_| revert("caller cannot execute XCM"))?;
+
+		let value = (origin.clone(), vec![transfer.asset.clone()]);
+		let allowed = match kind {
+			Kind::Reserve => <R as pallet_xcm::Config>::XcmReserveTransferFilter::contains(&value),
+			Kind::Teleport => <R as pallet_xcm::Config>::XcmTeleportFilter::contains(&value),
+		};
+		if !allowed {
+			return Err(revert("transfer filtered"));
+		}
+
+		let mut message = Self::message(kind, transfer)?;
+		let weight = <R as pallet_xcm::Config>::Weigher::weight(&mut message)
+			.map_err(|()| revert("cannot weigh transfer"))?;
+		handle.record_cost(R::GasWeightMapping::weight_to_gas(weight))?;
+
+		// `pallet_xcm` succeeds even when the execution stops halfway, e.g. after the assets were
+		// withdrawn but could not be sent, so the outcome is checked here.
+		let completed = with_transaction(|| {
+			let outcome = <R as pallet_xcm::Config>::XcmExecutor::execute_xcm_in_credit(
+				origin, message, weight, weight,
+			);
+			match outcome {
+				Outcome::Complete(_) => TransactionOutcome::Commit(true),
+				Outcome::Incomplete(..) | Outcome::Error(_) => TransactionOutcome::Rollback(false),
+			}
+		});
+
+		if !completed {
+			return Err(revert("transfer failed"));
+		}
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: Default::default(),
+		})
+	}
+}
+
+/// Decodes the `(Multilocation, uint256, Multilocation, uint64)` arguments of a transfer of
+/// `amount` of `asset` to `destination`.
+fn decode_transfer(args: &[u8]) -> Result<Transfer, PrecompileFailure> {
+	let asset = read_location(args, 0)?;
+	let amount = read_u128(args, 32)?;
+	let (dest, beneficiary) = match read_location(args, 64)?.split_last_interior() {
+		(dest, Some(beneficiary)) => (dest, MultiLocation::from(beneficiary)),
+		(_, None) => return Err(revert("destination has no beneficiary")),
+	};
+	// The weight bought on the destination, zero buying as much as the fees allow.
+	let weight_limit = match read_u64(args, 96)? {
+		0 => Unlimited,
+		weight => Limited(weight),
+	};
+
+	Ok(Transfer {
+		asset: MultiAsset::from((asset, amount)),
+		dest,
+		beneficiary,
+		weight_limit,
+	})
+}
+
+/// Reads the `Multilocation` struct whose offset is stored at `head`.
+fn read_location(args: &[u8], head: usize) -> Result<MultiLocation, PrecompileFailure> {
+	// Offsets inside the struct are relative to its start.
+	let start = read_usize(args, head)?;
+	let location = args.get(start..).ok_or_else(|| revert("input too short"))?;
+
+	let parents = read_u8(location, 0)?;
+	let junctions = read_bytes_array(location, 32)?
+		.iter()
+		.map(|junction| decode_junction(junction))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut interior = Junctions::Here;
+	for junction in junctions {
+		interior.push(junction).map_err(|_| revert("too many junctions"))?;
+	}
+	Ok(MultiLocation::new(parents, interior))
+}
+
+fn decode_junction(encoded: &[u8]) -> Result<Junction, PrecompileFailure> {
+	let (kind, data) = encoded.split_first().ok_or_else(|| revert("empty junction"))?;
+	let invalid = || revert("invalid junction");
+
+	Ok(match kind {
+		0x00 => Parachain(u32::from_be_bytes(data.try_into().map_err(|_| invalid())?)),
+		0x01 if data.len() > 32 => AccountId32 {
+			id: data[..32].try_into().map_err(|_| invalid())?,
+			network: decode_network(&data[32..])?,
+		},
+		0x03 if data.len() > 20 => AccountKey20 {
+			key: data[..20].try_into().map_err(|_| invalid())?,
+			network: decode_network(&data[20..])?,
+		},
+		0x04 => PalletInstance(u8::from_be_bytes(data.try_into().map_err(|_| invalid())?)),
+		0x05 => GeneralIndex(u128::from_be_bytes(data.try_into().map_err(|_| invalid())?)),
+		0x06 => GeneralKey(data.to_vec()),
+		_ => return Err(invalid()),
+	})
+}
+
+fn decode_network(encoded: &[u8]) -> Result<NetworkId, PrecompileFailure> {
+	match encoded.split_first() {
+		Some((0x00, [])) => Ok(NetworkId::Any),
+		Some((0x01, name)) => Ok(NetworkId::Named(name.to_vec())),
+		Some((0x02, [])) => Ok(NetworkId::Polkadot),
+		Some((0x03, [])) => Ok(NetworkId::Kusama),
+		_ => Err(revert("invalid network")),
+	}
+}
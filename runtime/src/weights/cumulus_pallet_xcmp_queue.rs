@@ -0,0 +1,29 @@
+//! Weights for `cumulus_pallet_xcmp_queue` in the Diora runtime.
+//!
+//! These weights were not measured on Diora: they are copied from the Cumulus parachain
+//! runtimes, where they were benchmarked with `--chain dev`, 32 steps and 64 repeats.
+//! `script/benchmark.sh` replaces this file with the output of the Diora benchmarks.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `cumulus_pallet_xcmp_queue`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> cumulus_pallet_xcmp_queue::WeightInfo for WeightInfo<T> {
+	// Storage: XcmpQueue QueueConfig (r:1 w:1)
+	fn set_config_with_u32() -> Weight {
+		(2_717_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: XcmpQueue QueueConfig (r:1 w:1)
+	fn set_config_with_weight() -> Weight {
+		(2_676_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,9 @@
+//! Weights of the pallets benchmarked against the Diora runtime.
+//!
+//! Regenerate them with `script/benchmark.sh` on reference hardware whenever the benchmarks or
+//! the runtime configuration of one of these pallets change.
+
+pub mod cumulus_pallet_xcmp_queue;
+pub mod pallet_author_inherent;
+pub mod pallet_author_slot_filter;
+pub mod xcm;
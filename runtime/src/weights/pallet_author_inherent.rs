@@ -0,0 +1,28 @@
+//! Weights for `pallet_author_inherent` in the Diora runtime.
+//!
+//! These weights were not measured on Diora: they are copied from the Moonbeam runtime, where they
+//! were benchmarked with `--chain dev`, 32 steps and 64 repeats. `script/benchmark.sh`
+//! replaces this file with the output of the Diora benchmarks.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_author_inherent`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_author_inherent::WeightInfo for WeightInfo<T> {
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: AuthorInherent HighestSlotSeen (r:1 w:1)
+	// Storage: AuthorInherent Author (r:1 w:0)
+	// Storage: PotentialAuthorSet StoredAccounts (r:1 w:0)
+	// Storage: AuthorFilter EligibleCount (r:1 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	fn kick_off_authorship_validation() -> Weight {
+		(20_862_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
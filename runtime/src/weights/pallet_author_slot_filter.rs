@@ -0,0 +1,22 @@
+//! Weights for `pallet_author_slot_filter` in the Diora runtime.
+//!
+//! These weights were not measured on Diora: they are copied from the Moonbeam runtime, where they
+//! were benchmarked with `--chain dev`, 32 steps and 64 repeats. `script/benchmark.sh`
+//! replaces this file with the output of the Diora benchmarks.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_author_slot_filter`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_author_slot_filter::WeightInfo for WeightInfo<T> {
+	// Storage: AuthorFilter EligibleCount (r:0 w:1)
+	fn set_eligible() -> Weight {
+		(13_250_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,204 @@
+//! Weights of the XCM instructions, benchmarked with `pallet_xcm_benchmarks`.
+
+mod pallet_xcm_benchmarks_fungible;
+mod pallet_xcm_benchmarks_generic;
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+use xcm::{
+	latest::{prelude::*, XcmWeightInfo},
+	DoubleEncoded,
+};
+
+use pallet_xcm_benchmarks_fungible::WeightInfo as XcmFungibleWeight;
+use pallet_xcm_benchmarks_generic::WeightInfo as XcmGeneric;
+
+
+/// Kinds of assets the asset transactors of Diora handle.
+enum AssetTypes {
+	/// The relay token, held in `pallet_balances`.
+	Balances,
+	/// A foreign asset registered in the asset manager, held in `pallet_assets`.
+	Assets,
+	/// An asset no transactor handles.
+	Unknown,
+}
+
+impl From<&MultiAsset> for AssetTypes {
+	fn from(asset: &MultiAsset) -> Self {
+		match asset {
+			MultiAsset {
+				id: Concrete(MultiLocation { parents: 1, interior: Here }),
+				fun: Fungible(_),
+			} => AssetTypes::Balances,
+			MultiAsset { id: Concrete(_), fun: Fungible(_) } => AssetTypes::Assets,
+			_ => AssetTypes::Unknown,
+		}
+	}
+}
+
+trait WeighMultiAssets {
+	fn weigh_multi_assets(&self, weight: Weight) -> Weight;
+}
+
+impl WeighMultiAssets for MultiAssets {
+	fn weigh_multi_assets(&self, weight: Weight) -> Weight {
+		self.inner()
+			.iter()
+			.map(AssetTypes::from)
+			.map(|kind| match kind {
+				// Only the fungible instructions of `pallet_balances` are benchmarked. Those of
+				// `pallet_assets` read and write as many items, so they are charged the same.
+				AssetTypes::Balances | AssetTypes::Assets => weight,
+				// Makes the whole program unweighable, so it is never executed.
+				AssetTypes::Unknown => Weight::MAX,
+			})
+			.fold(0, |acc, weight| acc.saturating_add(weight))
+	}
+}
+
+/// Wildcards the instruction doesn't bound may match every asset in holding.
+impl WeighMultiAssets for MultiAssetFilter {
+	fn weigh_multi_assets(&self, weight: Weight) -> Weight {
+		weigh_up_to(self, crate::MaxAssetsIntoHolding::get(), weight)
+	}
+}
+
+/// Weighs a filter of at most `max_assets` assets.
+fn weigh_up_to(assets: &MultiAssetFilter, max_assets: u32, weight: Weight) -> Weight {
+	match assets {
+		MultiAssetFilter::Definite(assets) => assets.weigh_multi_assets(weight),
+		// Holding has a single item per fungible asset.
+		MultiAssetFilter::Wild(AllOf { fun: WildFungible, .. }) => {
+			(max_assets.min(1) as Weight).saturating_mul(weight)
+		},
+		MultiAssetFilter::Wild(_) => (max_assets as Weight).saturating_mul(weight),
+	}
+}
+
+/// Weights of the XCM instructions executed by Diora.
+///
+/// Instructions the executor doesn't support weigh `Weight::MAX`.
+pub struct DioraXcmWeight<T, Call>(PhantomData<(T, Call)>);
+impl<T: frame_system::Config, Call> XcmWeightInfo<Call> for DioraXcmWeight<T, Call> {
+	fn withdraw_asset(assets: &MultiAssets) -> Weight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<T>::withdraw_asset())
+	}
+	fn reserve_asset_deposited(assets: &MultiAssets) -> Weight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<T>::reserve_asset_deposited())
+	}
+	// No asset is teleported to Diora.
+	fn receive_teleported_asset(_assets: &MultiAssets) -> Weight {
+		Weight::MAX
+	}
+	fn query_response(_query_id: &u64, _response: &Response, _max_weight: &u64) -> Weight {
+		XcmGeneric::<T>::query_response()
+	}
+	fn transfer_asset(assets: &MultiAssets, _beneficiary: &MultiLocation) -> Weight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<T>::transfer_asset())
+	}
+	fn transfer_reserve_asset(
+		assets: &MultiAssets,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> Weight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<T>::transfer_reserve_asset())
+	}
+	// The weight of the call itself is `require_weight_at_most`, which the weigher adds to this.
+	fn transact(
+		_origin_type: &OriginKind,
+		_require_weight_at_most: &u64,
+		_call: &DoubleEncoded<Call>,
+	) -> Weight {
+		XcmGeneric::<T>::transact()
+	}
+	fn hrmp_new_channel_open_request(
+		_sender: &u32,
+		_max_message_size: &u32,
+		_max_capacity: &u32,
+	) -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_channel_accepted(_recipient: &u32) -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_channel_closing(_initiator: &u32, _sender: &u32, _recipient: &u32) -> Weight {
+		Weight::MAX
+	}
+	fn clear_origin() -> Weight {
+		XcmGeneric::<T>::clear_origin()
+	}
+	fn descend_origin(_who: &InteriorMultiLocation) -> Weight {
+		XcmGeneric::<T>::descend_origin()
+	}
+	fn report_error(_query_id: &u64, _dest: &MultiLocation, _max_response_weight: &u64) -> Weight {
+		XcmGeneric::<T>::report_error()
+	}
+	fn deposit_asset(
+		assets: &MultiAssetFilter,
+		max_assets: &u32,
+		_beneficiary: &MultiLocation,
+	) -> Weight {
+		weigh_up_to(assets, *max_assets, XcmFungibleWeight::<T>::deposit_asset())
+	}
+	fn deposit_reserve_asset(
+		assets: &MultiAssetFilter,
+		max_assets: &u32,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> Weight {
+		weigh_up_to(assets, *max_assets, XcmFungibleWeight::<T>::deposit_reserve_asset())
+	}
+	fn exchange_asset(_give: &MultiAssetFilter, _receive: &MultiAssets) -> Weight {
+		Weight::MAX
+	}
+	fn initiate_reserve_withdraw(
+		assets: &MultiAssetFilter,
+		_reserve: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> Weight {
+		assets.weigh_multi_assets(XcmGeneric::<T>::initiate_reserve_withdraw())
+	}
+	fn initiate_teleport(
+		assets: &MultiAssetFilter,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> Weight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<T>::initiate_teleport())
+	}
+	fn query_holding(
+		_query_id: &u64,
+		_dest: &MultiLocation,
+		_assets: &MultiAssetFilter,
+		_max_response_weight: &u64,
+	) -> Weight {
+		XcmGeneric::<T>::query_holding()
+	}
+	fn buy_execution(_fees: &MultiAsset, _weight_limit: &WeightLimit) -> Weight {
+		XcmGeneric::<T>::buy_execution()
+	}
+	fn refund_surplus() -> Weight {
+		XcmGeneric::<T>::refund_surplus()
+	}
+	fn set_error_handler(_xcm: &Xcm<Call>) -> Weight {
+		XcmGeneric::<T>::set_error_handler()
+	}
+	fn set_appendix(_xcm: &Xcm<Call>) -> Weight {
+		XcmGeneric::<T>::set_appendix()
+	}
+	fn clear_error() -> Weight {
+		XcmGeneric::<T>::clear_error()
+	}
+	fn claim_asset(_assets: &MultiAssets, _ticket: &MultiLocation) -> Weight {
+		XcmGeneric::<T>::claim_asset()
+	}
+	fn trap(_code: &u64) -> Weight {
+		XcmGeneric::<T>::trap()
+	}
+	fn subscribe_version(_query_id: &u64, _max_response_weight: &u64) -> Weight {
+		XcmGeneric::<T>::subscribe_version()
+	}
+	fn unsubscribe_version() -> Weight {
+		XcmGeneric::<T>::unsubscribe_version()
+	}
+}
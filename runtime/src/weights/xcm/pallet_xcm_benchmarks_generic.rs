@@ -0,0 +1,103 @@
+//! Weights for `pallet_xcm_benchmarks::generic` in the Diora runtime.
+//!
+//! These weights were not measured on Diora: they are copied from the XCM benchmarks of the
+//! Statemine runtime of Cumulus, which hold its assets in the same pallets. `script/benchmark.sh`
+//! replaces this file with the output of the Diora benchmarks, generated from
+//! `script/xcm-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weights for `pallet_xcm_benchmarks::generic`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo<T> {
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn query_holding() -> Weight {
+		(21_822_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	pub(crate) fn buy_execution() -> Weight {
+		(5_922_000 as Weight)
+	}
+	// Storage: PolkadotXcm Queries (r:1 w:1)
+	pub(crate) fn query_response() -> Weight {
+		(17_657_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	pub(crate) fn transact() -> Weight {
+		(20_625_000 as Weight)
+	}
+	pub(crate) fn refund_surplus() -> Weight {
+		(5_752_000 as Weight)
+	}
+	pub(crate) fn set_error_handler() -> Weight {
+		(5_703_000 as Weight)
+	}
+	pub(crate) fn set_appendix() -> Weight {
+		(5_636_000 as Weight)
+	}
+	pub(crate) fn clear_error() -> Weight {
+		(5_700_000 as Weight)
+	}
+	pub(crate) fn descend_origin() -> Weight {
+		(6_948_000 as Weight)
+	}
+	pub(crate) fn clear_origin() -> Weight {
+		(5_692_000 as Weight)
+	}
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn report_error() -> Weight {
+		(17_965_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PolkadotXcm AssetTraps (r:1 w:1)
+	pub(crate) fn claim_asset() -> Weight {
+		(11_270_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	pub(crate) fn trap() -> Weight {
+		(5_806_000 as Weight)
+	}
+	// Storage: PolkadotXcm VersionNotifyTargets (r:1 w:1)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn subscribe_version() -> Weight {
+		(21_154_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PolkadotXcm VersionNotifyTargets (r:0 w:1)
+	pub(crate) fn unsubscribe_version() -> Weight {
+		(8_578_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn initiate_reserve_withdraw() -> Weight {
+		(21_486_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,119 @@
+//! Tests for the unified 20-byte account model.
+//!
+//! Diora accounts are Ethereum addresses: the `AccountId` of a Substrate extrinsic signer and the
+//! `msg.sender` of an EVM transaction signed with the same secp256k1 key are the same 20 bytes,
+//! so there is a single balance per key.
+//!
+//! Migration story: previously the runtime used 32-byte `MultiSignature` accounts and the EVM
+//! address `h` was backed by the account `blake2_256("evm:" ++ h)` (`HashedAddressMapping`).
+//! No key controls that hashed account and sr25519/ed25519 accounts have no Ethereum address,
+//! so state cannot be converted in place by a runtime migration. Networks are relaunched from a
+//! new genesis instead, where every legacy balance held by a hashed EVM account is re-keyed to
+//! the plain address `h` by `rekey_legacy_balances`. The `legacy_*` tests below pin down that
+//! conversion.
+
+mod common;
+use common::*;
+
+use account::{EthereumSignature, EthereumSigner};
+use diora_runtime::{rekey_legacy_balances, AccountId, Balances, Evm, Origin, Signature, UNIT};
+use frame_support::assert_ok;
+use pallet_evm::AddressMapping;
+use sp_core::{ecdsa, Pair, H160, U256};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentifyAccount, Verify},
+	AccountId32,
+};
+
+/// The 32-byte account that used to back the EVM address `address`.
+fn legacy_evm_account(address: H160) -> AccountId32 {
+	pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(address)
+}
+
+#[test]
+fn evm_address_maps_to_identical_account() {
+	let address = H160::from(ALICE);
+	assert_eq!(
+		<diora_runtime::Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address),
+		AccountId::from(ALICE),
+	);
+}
+
+#[test]
+fn evm_balance_is_substrate_balance() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let (account, _) = Evm::account_basic(&H160::from(ALICE));
+			assert_eq!(account.balance, U256::from(1_000 * UNIT));
+		});
+}
+
+#[test]
+fn substrate_transfer_is_visible_to_evm() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::transfer(
+				Origin::signed(AccountId::from(ALICE)),
+				AccountId::from(BOB),
+				10 * UNIT,
+			));
+
+			let (account, _) = Evm::account_basic(&H160::from(BOB));
+			assert_eq!(account.balance, U256::from(10 * UNIT));
+		});
+}
+
+#[test]
+fn one_key_signs_for_the_account() {
+	let pair = ecdsa::Pair::from_string("//Alice", None).unwrap();
+	let account: AccountId = EthereumSigner::from(pair.public()).into_account();
+
+	let payload = b"transfer 10 DIR";
+	let digest = sp_io::hashing::keccak_256(payload);
+	let signature: Signature = EthereumSignature::from(pair.sign_prehashed(&digest));
+
+	assert!(signature.verify(&payload[..], &account));
+	assert!(!signature.verify(&payload[..], &AccountId::from(BOB)));
+}
+
+#[test]
+fn legacy_evm_account_is_hashed_address() {
+	let address = H160::from(ALICE);
+
+	let mut data = [0u8; 24];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(&address[..]);
+
+	assert_eq!(
+		legacy_evm_account(address),
+		AccountId32::from(sp_io::hashing::blake2_256(&data))
+	);
+}
+
+#[test]
+fn legacy_balances_rekeyed_at_genesis_are_spendable_from_the_evm() {
+	let address = H160::from(ALICE);
+	let substrate_account = AccountId32::new([7; 32]);
+	// Balances exported from the old chain, keyed by 32-byte accounts.
+	let legacy_balances = vec![
+		(legacy_evm_account(address), 40 * UNIT),
+		(substrate_account.clone(), 5 * UNIT),
+		(legacy_evm_account(address), 2 * UNIT),
+	];
+
+	let (balances, unmapped) = rekey_legacy_balances(legacy_balances, &[address, H160::from(BOB)]);
+
+	assert_eq!(balances, vec![(AccountId::from(address), 42 * UNIT)]);
+	assert_eq!(unmapped, vec![(substrate_account, 5 * UNIT)]);
+
+	ExtBuilder::default().with_balances(balances).build().execute_with(|| {
+		let (account, _) = Evm::account_basic(&address);
+		assert_eq!(account.balance, U256::from(42 * UNIT));
+		assert_eq!(Balances::free_balance(AccountId::from(address)), 42 * UNIT);
+		assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+	});
+}
@@ -0,0 +1,111 @@
+//! Tests for the EIP-1559 base fee adjustment.
+
+mod common;
+use common::*;
+
+use diora_runtime::{BaseFee, BaseFeeParams, Origin, Runtime, System};
+use frame_support::{assert_ok, traits::OnFinalize};
+use sp_core::U256;
+use sp_runtime::{Perbill, Permill};
+
+fn finalize_block() {
+	BaseFee::on_finalize(System::block_number());
+}
+
+#[test]
+fn base_fee_starts_at_default() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(BaseFee::is_active());
+		assert_eq!(
+			BaseFee::base_fee_per_gas(),
+			<Runtime as pallet_base_fee::Config>::DefaultBaseFeePerGas::get()
+		);
+	});
+}
+
+#[test]
+fn base_fee_rises_with_full_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let before = BaseFee::base_fee_per_gas();
+
+		fill_block(Perbill::one());
+		finalize_block();
+
+		// A completely full block increases the fee by the full elasticity (12.5%).
+		let expected = before + before * U256::from(125_000) / U256::from(1_000_000);
+		assert_eq!(BaseFee::base_fee_per_gas(), expected);
+	});
+}
+
+#[test]
+fn base_fee_falls_with_empty_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let before = BaseFee::base_fee_per_gas();
+
+		finalize_block();
+
+		let expected = before - before * U256::from(125_000) / U256::from(1_000_000);
+		assert_eq!(BaseFee::base_fee_per_gas(), expected);
+	});
+}
+
+#[test]
+fn base_fee_is_stable_at_ideal_fullness() {
+	ExtBuilder::default().build().execute_with(|| {
+		let before = BaseFee::base_fee_per_gas();
+
+		fill_block(Perbill::from_percent(50));
+		finalize_block();
+
+		assert_eq!(BaseFee::base_fee_per_gas(), before);
+	});
+}
+
+#[test]
+fn base_fee_follows_governance_parameters() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(BaseFeeParams::set_elasticity(
+			Origin::root(),
+			Permill::from_percent(5)
+		));
+		// Only consider the 0% - 60% fullness range, so a 60% full block counts as full.
+		assert_ok!(BaseFeeParams::set_threshold(
+			Origin::root(),
+			Permill::zero(),
+			Permill::from_percent(50),
+			Permill::from_percent(60),
+		));
+
+		let before = BaseFee::base_fee_per_gas();
+		fill_block(Perbill::from_percent(60));
+		finalize_block();
+
+		let expected = before + before * U256::from(50_000) / U256::from(1_000_000);
+		assert_eq!(BaseFee::base_fee_per_gas(), expected);
+	});
+}
+
+#[test]
+fn base_fee_keeps_rising_and_falling_over_several_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let start = BaseFee::base_fee_per_gas();
+
+		for _ in 0..3 {
+			fill_block(Perbill::one());
+			finalize_block();
+			System::reset_events();
+			System::set_block_number(System::block_number() + 1);
+			// `register_extra_weight_unchecked` accumulates, start each block from scratch.
+			frame_system::BlockWeight::<Runtime>::kill();
+		}
+		let peak = BaseFee::base_fee_per_gas();
+		assert!(peak > start);
+
+		for _ in 0..6 {
+			finalize_block();
+			System::set_block_number(System::block_number() + 1);
+		}
+		assert!(BaseFee::base_fee_per_gas() < peak);
+		assert!(BaseFee::base_fee_per_gas() < start);
+	});
+}
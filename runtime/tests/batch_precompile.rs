@@ -0,0 +1,226 @@
+//! Tests for the batch precompile.
+
+mod common;
+use common::*;
+
+use diora_runtime::{Balances, Runtime};
+use fp_evm::{ExitReason, ExitRevert, ExitSucceed};
+use pallet_evm::{CallInfo, Runner};
+use sp_core::{H160, H256, U256};
+
+const UNIT: u128 = 1_000_000_000_000_000_000;
+const GAS_LIMIT: u64 = 1_000_000;
+
+const BATCH_SOME: [u8; 4] = [0x79, 0xdf, 0x4b, 0x9c];
+const BATCH_SOME_UNTIL_FAILURE: [u8; 4] = [0xcf, 0x04, 0x91, 0xc7];
+const BATCH_ALL: [u8; 4] = [0x96, 0xe2, 0x92, 0xb8];
+
+fn batch_precompile() -> H160 {
+	H160::from_low_u64_be(1027)
+}
+
+/// Target whose call always fails: the dispatch precompile cannot decode empty input.
+fn failing_target() -> H160 {
+	H160::from_low_u64_be(1026)
+}
+
+fn word(value: U256) -> Vec<u8> {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	word.to_vec()
+}
+
+fn encode_array(elements: Vec<Vec<u8>>) -> Vec<u8> {
+	let mut encoded = word(elements.len().into());
+	for element in elements {
+		encoded.extend(element);
+	}
+	encoded
+}
+
+fn encode_batch(
+	selector: [u8; 4],
+	to: Vec<H160>,
+	value: Vec<u128>,
+	call_data: Vec<Vec<u8>>,
+	gas_limit: Vec<u64>,
+) -> Vec<u8> {
+	let to = encode_array(to.into_iter().map(|a| word(U256::from(a.as_bytes()))).collect());
+	let value = encode_array(value.into_iter().map(|v| word(v.into())).collect());
+	let gas_limit = encode_array(gas_limit.into_iter().map(|g| word(g.into())).collect());
+
+	let mut heads = Vec::new();
+	let mut tails = Vec::new();
+	let mut offset = 32 * call_data.len();
+	for data in &call_data {
+		heads.extend(word(offset.into()));
+		let mut tail = word(data.len().into());
+		tail.extend(data);
+		tail.resize(32 + (data.len() + 31) / 32 * 32, 0);
+		offset += tail.len();
+		tails.extend(tail);
+	}
+	let mut call_data_encoded = word(call_data.len().into());
+	call_data_encoded.extend(heads);
+	call_data_encoded.extend(tails);
+
+	let mut input = selector.to_vec();
+	let mut offset = 4 * 32;
+	for arg in [&to, &value, &call_data_encoded, &gas_limit] {
+		input.extend(word(offset.into()));
+		offset += arg.len();
+	}
+	for arg in [to, value, call_data_encoded, gas_limit] {
+		input.extend(arg);
+	}
+	input
+}
+
+fn call_batch(input: Vec<u8>) -> CallInfo {
+	<Runtime as pallet_evm::Config>::Runner::call(
+		H160(ALICE),
+		batch_precompile(),
+		input,
+		U256::zero(),
+		GAS_LIMIT,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false,
+		<Runtime as pallet_evm::Config>::config(),
+	)
+	.expect("runner accepts the call")
+}
+
+/// Two transfers to Bob around a failing subcall.
+fn batch_with_failure(selector: [u8; 4]) -> Vec<u8> {
+	encode_batch(
+		selector,
+		vec![H160(BOB), failing_target(), H160(BOB)],
+		vec![UNIT, 0, UNIT],
+		vec![],
+		vec![],
+	)
+}
+
+/// `(succeeded, index)` for each log emitted by the batch precompile.
+fn outcomes(info: &CallInfo) -> Vec<(bool, u64)> {
+	let succeeded = H256::from_slice(&[
+		0xbf, 0x85, 0x54, 0x84, 0x63, 0x39, 0x29, 0xc3, 0xd6, 0x68, 0x8e, 0xb3, 0xca, 0xf8, 0xef,
+		0xf9, 0x10, 0xfb, 0x4b, 0xef, 0x03, 0x0a, 0x8d, 0x7d, 0xbc, 0x93, 0x90, 0xc3, 0xd6, 0x75,
+		0x97, 0x14,
+	]);
+	info.logs
+		.iter()
+		.filter(|log| log.address == batch_precompile())
+		.map(|log| {
+			(
+				log.topics[0] == succeeded,
+				U256::from_big_endian(&log.data).low_u64(),
+			)
+		})
+		.collect()
+}
+
+fn ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT), (BOB.into(), UNIT)])
+		.build()
+}
+
+#[test]
+fn batch_some_skips_failed_subcalls() {
+	ext().execute_with(|| {
+		let info = call_batch(batch_with_failure(BATCH_SOME));
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(outcomes(&info), vec![(true, 0), (false, 1), (true, 2)]);
+		assert_eq!(Balances::free_balance(&BOB.into()), 3 * UNIT);
+	});
+}
+
+#[test]
+fn batch_some_until_failure_stops_at_first_failure() {
+	ext().execute_with(|| {
+		let info = call_batch(batch_with_failure(BATCH_SOME_UNTIL_FAILURE));
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(outcomes(&info), vec![(true, 0), (false, 1)]);
+		assert_eq!(Balances::free_balance(&BOB.into()), 2 * UNIT);
+	});
+}
+
+#[test]
+fn batch_all_reverts_everything_on_failure() {
+	ext().execute_with(|| {
+		let info = call_batch(batch_with_failure(BATCH_ALL));
+
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert!(info.logs.is_empty());
+		assert_eq!(Balances::free_balance(&BOB.into()), UNIT);
+		assert_eq!(Balances::free_balance(&ALICE.into()), 10 * UNIT);
+	});
+}
+
+#[test]
+fn batch_all_performs_every_subcall_on_success() {
+	ext().execute_with(|| {
+		let info = call_batch(encode_batch(
+			BATCH_ALL,
+			vec![H160(BOB), H160(BOB)],
+			vec![UNIT, 2 * UNIT],
+			vec![],
+			vec![],
+		));
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(outcomes(&info), vec![(true, 0), (true, 1)]);
+		assert_eq!(Balances::free_balance(&BOB.into()), 4 * UNIT);
+	});
+}
+
+#[test]
+fn subcall_gas_limit_is_enforced() {
+	ext().execute_with(|| {
+		// Sha3FIPS256 needs more than 10 gas for any input.
+		let info = call_batch(encode_batch(
+			BATCH_SOME,
+			vec![H160::from_low_u64_be(1024), H160::from_low_u64_be(1024)],
+			vec![],
+			vec![b"diora".to_vec(), b"diora".to_vec()],
+			vec![10, 0],
+		));
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(outcomes(&info), vec![(false, 0), (true, 1)]);
+	});
+}
+
+#[test]
+fn subcall_gas_limit_above_remaining_gas_fails() {
+	ext().execute_with(|| {
+		let info = call_batch(encode_batch(
+			BATCH_SOME,
+			vec![H160(BOB)],
+			vec![UNIT],
+			vec![],
+			vec![GAS_LIMIT * 2],
+		));
+
+		assert!(matches!(info.exit_reason, ExitReason::Error(_)));
+		assert_eq!(Balances::free_balance(&BOB.into()), UNIT);
+	});
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+	ext().execute_with(|| {
+		let mut input = batch_with_failure(BATCH_SOME);
+		input.truncate(100);
+
+		let info = call_batch(input);
+
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+	});
+}
@@ -0,0 +1,117 @@
+//! Test utilities shared by the Diora runtime integration tests.
+
+#![allow(dead_code)]
+
+use diora_runtime::{AccountId, Balance, Runtime, System};
+use frame_support::{
+	traits::{GenesisBuild, Get},
+	weights::{DispatchClass, Weight},
+};
+use nimbus_primitives::NimbusId;
+use pallet_evm::{CallInfo, Runner};
+use sp_core::{H160, U256};
+use sp_runtime::Perbill;
+
+pub const ALICE: [u8; 20] = [4u8; 20];
+pub const BOB: [u8; 20] = [5u8; 20];
+
+pub struct ExtBuilder {
+	// endowed accounts with balances
+	balances: Vec<(AccountId, Balance)>,
+	// block authors and the `NimbusId`s they author with
+	authors: Vec<(AccountId, NimbusId)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> ExtBuilder {
+		ExtBuilder { balances: vec![], authors: vec![] }
+	}
+}
+
+impl ExtBuilder {
+	pub fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	pub fn with_authors(mut self, authors: Vec<(AccountId, NimbusId)>) -> Self {
+		self.authors = authors;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		diora_runtime::pallet_account_set::GenesisConfig::<Runtime> { mapping: self.authors }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		<pallet_base_fee::GenesisConfig<Runtime> as GenesisBuild<Runtime>>::assimilate_storage(
+			&Default::default(),
+			&mut t,
+		)
+		.unwrap();
+
+		<pallet_base_fee_params::GenesisConfig as GenesisBuild<Runtime>>::assimilate_storage(
+			&Default::default(),
+			&mut t,
+		)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+/// Mark the given share of the maximum block weight as consumed by normal extrinsics.
+pub fn fill_block(fullness: Perbill) {
+	let max_block: Weight =
+		<Runtime as frame_system::Config>::BlockWeights::get().max_block;
+	System::register_extra_weight_unchecked(fullness * max_block, DispatchClass::Normal);
+}
+
+/// Installs at `address` a contract that forwards its input to the precompile at `precompile`
+/// with `DELEGATECALL`, and reverts if the precompile fails:
+///
+/// ```text
+/// CALLDATACOPY(0, 0, CALLDATASIZE)
+/// DELEGATECALL(GAS, precompile, 0, CALLDATASIZE, 0, 0)
+/// JUMPI(26, success)
+/// REVERT(0, 0)
+/// 26: JUMPDEST STOP
+/// ```
+pub fn install_delegating_contract(address: H160, precompile: u16) {
+	let [high, low] = precompile.to_be_bytes();
+	let code = vec![
+		0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x60, 0x00, 0x60, 0x00, 0x36, 0x60, 0x00, 0x61, high,
+		low, 0x5a, 0xf4, 0x60, 0x1a, 0x57, 0x60, 0x00, 0x60, 0x00, 0xfd, 0x5b, 0x00,
+	];
+	pallet_evm::AccountCodes::<Runtime>::insert(address, code);
+}
+
+/// Calls `to` from `from` with `input` through the EVM runner.
+pub fn evm_call(from: [u8; 20], to: H160, input: Vec<u8>, gas_limit: u64) -> CallInfo {
+	<Runtime as pallet_evm::Config>::Runner::call(
+		H160(from),
+		to,
+		input,
+		U256::zero(),
+		gas_limit,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false,
+		<Runtime as pallet_evm::Config>::config(),
+	)
+	.expect("runner accepts the call")
+}
@@ -0,0 +1,103 @@
+//! Tests for the contract-creation allowlist enforced on Ethereum transactions.
+
+mod common;
+use common::*;
+
+use diora_runtime::{Call, DeploymentAllowlist, Origin};
+use fp_self_contained::SelfContainedCall;
+use frame_support::{assert_ok, weights::GetDispatchInfo};
+use pallet_deployment_allowlist::{DeploymentMode, DeploymentValidityError};
+use sp_core::{H160, H256, U256};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+
+fn deployer() -> H160 {
+	H160::repeat_byte(0xaa)
+}
+
+fn transact(action: ethereum::TransactionAction) -> Call {
+	let transaction = ethereum::TransactionV2::Legacy(ethereum::LegacyTransaction {
+		nonce: U256::zero(),
+		gas_price: U256::from(1_000_000_000u64),
+		gas_limit: U256::from(1_000_000u64),
+		action,
+		value: U256::zero(),
+		input: vec![0x60, 0x00],
+		signature: ethereum::TransactionSignature::new(
+			27,
+			H256::repeat_byte(0x01),
+			H256::repeat_byte(0x02),
+		)
+		.unwrap(),
+	});
+	pallet_ethereum::Call::transact { transaction }.into()
+}
+
+fn not_allowed() -> TransactionValidityError {
+	TransactionValidityError::Invalid(InvalidTransaction::Custom(
+		DeploymentValidityError::DeployerNotAllowed as u8,
+	))
+}
+
+fn validate(call: &Call, from: &H160) -> Option<Result<(), TransactionValidityError>> {
+	call.validate_self_contained(from, &call.get_dispatch_info(), 0)
+		.map(|validity| validity.map(|_| ()))
+}
+
+#[test]
+fn create_is_rejected_when_closed_and_not_allowlisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::set_mode(
+			Origin::root(),
+			DeploymentMode::Closed
+		));
+		let call = transact(ethereum::TransactionAction::Create);
+
+		assert_eq!(validate(&call, &deployer()), Some(Err(not_allowed())));
+		assert_eq!(
+			call.pre_dispatch_self_contained(&deployer()),
+			Some(Err(not_allowed()))
+		);
+	});
+}
+
+#[test]
+fn create_passes_allowlist_check_when_allowlisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::set_mode(
+			Origin::root(),
+			DeploymentMode::Closed
+		));
+		assert_ok!(DeploymentAllowlist::add_deployer(Origin::root(), deployer()));
+		let call = transact(ethereum::TransactionAction::Create);
+
+		// The transaction may still be invalid for other reasons (e.g. the sender has no funds),
+		// but not because of the allowlist.
+		assert_ne!(validate(&call, &deployer()), Some(Err(not_allowed())));
+		assert_ne!(
+			call.pre_dispatch_self_contained(&deployer()),
+			Some(Err(not_allowed()))
+		);
+	});
+}
+
+#[test]
+fn calls_are_never_restricted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeploymentAllowlist::set_mode(
+			Origin::root(),
+			DeploymentMode::Closed
+		));
+		let call = transact(ethereum::TransactionAction::Call(H160::repeat_byte(0xcc)));
+
+		assert_ne!(validate(&call, &deployer()), Some(Err(not_allowed())));
+	});
+}
+
+#[test]
+fn create_is_unrestricted_when_open() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = transact(ethereum::TransactionAction::Create);
+
+		assert_ne!(validate(&call, &deployer()), Some(Err(not_allowed())));
+	});
+}
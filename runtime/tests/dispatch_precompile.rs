@@ -0,0 +1,167 @@
+//! Tests for the precompile that dispatches runtime calls from the EVM.
+
+mod common;
+use common::*;
+
+use codec::Encode;
+use diora_runtime::{
+	Balances, Call, DioraGasWeightMapping, Dispatch, DispatchPrecompileFilter, Runtime, UNIT,
+};
+use fp_evm::{ExitError, ExitReason, ExitRevert, ExitSucceed};
+use frame_support::weights::{DispatchInfo, GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::{CallInfo, GasWeightMapping};
+use sp_core::{H160, U256};
+
+const GAS_LIMIT: u64 = 1_000_000;
+
+fn dispatch_precompile() -> H160 {
+	H160::from_low_u64_be(1026)
+}
+
+fn call_precompile(from: [u8; 20], call: Call, gas_limit: u64) -> CallInfo {
+	evm_call(from, dispatch_precompile(), call.encode(), gas_limit)
+}
+
+fn transfer(dest: [u8; 20], value: u128) -> Call {
+	pallet_balances::Call::transfer {
+		dest: dest.into(),
+		value,
+	}
+	.into()
+}
+
+#[test]
+fn transfer_is_dispatched_with_caller_origin() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let info = call_precompile(ALICE, transfer(BOB, UNIT), GAS_LIMIT);
+
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+			assert_eq!(Balances::free_balance(&ALICE.into()), 9 * UNIT);
+			assert_eq!(Balances::free_balance(&BOB.into()), UNIT);
+		});
+}
+
+#[test]
+fn delegatecalled_dispatch_is_rejected() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let contract = H160::from_low_u64_be(0xdead);
+			install_delegating_contract(contract, 1026);
+
+			let info = evm_call(ALICE, contract, transfer(BOB, UNIT).encode(), GAS_LIMIT);
+
+			assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			assert_eq!(Balances::free_balance(&ALICE.into()), 10 * UNIT);
+			assert_eq!(Balances::free_balance(&BOB.into()), 0);
+		});
+}
+
+#[test]
+fn dispatch_weight_is_charged_as_gas() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let call = transfer(BOB, UNIT);
+			let dispatch_gas =
+				DioraGasWeightMapping::weight_to_gas(call.get_dispatch_info().weight);
+
+			let info = call_precompile(ALICE, call, GAS_LIMIT);
+
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+			assert!(info.used_gas > U256::from(dispatch_gas));
+			// Gas that was not needed by the dispatch is left to the caller.
+			assert!(info.used_gas < U256::from(GAS_LIMIT));
+		});
+}
+
+#[test]
+fn filtered_call_is_rejected() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let call: Call = frame_system::Call::remark_with_event {
+				remark: b"diora".to_vec(),
+			}
+			.into();
+
+			let info = call_precompile(ALICE, call, GAS_LIMIT);
+
+			assert_eq!(
+				info.exit_reason,
+				ExitReason::Error(ExitError::Other("call filtered".into()))
+			);
+		});
+}
+
+#[test]
+fn calls_into_the_evm_are_filtered() {
+	let call: Call = pallet_evm::Call::withdraw {
+		address: H160(ALICE),
+		value: UNIT,
+	}
+	.into();
+
+	assert!(!<DispatchPrecompileFilter as frame_support::traits::Contains<Call>>::contains(&call));
+}
+
+#[test]
+fn failed_dispatch_reverts_its_changes() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let info = call_precompile(ALICE, transfer(BOB, 20 * UNIT), GAS_LIMIT);
+
+			assert_eq!(
+				info.exit_reason,
+				ExitReason::Error(ExitError::Other("dispatch execution failed".into()))
+			);
+			assert_eq!(Balances::free_balance(&ALICE.into()), 10 * UNIT);
+			assert_eq!(Balances::free_balance(&BOB.into()), 0);
+		});
+}
+
+#[test]
+fn call_heavier_than_gas_limit_runs_out_of_gas() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 10 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let call = transfer(BOB, UNIT);
+			let dispatch_gas =
+				DioraGasWeightMapping::weight_to_gas(call.get_dispatch_info().weight);
+
+			let info = call_precompile(ALICE, call, dispatch_gas);
+
+			assert_eq!(info.exit_reason, ExitReason::Error(ExitError::OutOfGas));
+			assert_eq!(Balances::free_balance(&BOB.into()), 0);
+		});
+}
+
+#[test]
+fn unused_weight_is_refunded() {
+	let info = DispatchInfo {
+		weight: 10_000 * diora_runtime::WEIGHT_PER_GAS,
+		..Default::default()
+	};
+	let post_info = PostDispatchInfo {
+		actual_weight: Some(4_000 * diora_runtime::WEIGHT_PER_GAS),
+		pays_fee: Default::default(),
+	};
+
+	assert_eq!(
+		Dispatch::<Runtime, DispatchPrecompileFilter>::dispatch_gas(&info, &post_info),
+		4_000
+	);
+	assert_eq!(
+		Dispatch::<Runtime, DispatchPrecompileFilter>::dispatch_gas(&info, &().into()),
+		10_000
+	);
+}
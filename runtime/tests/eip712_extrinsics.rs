@@ -0,0 +1,149 @@
+//! End-to-end tests of Substrate extrinsics signed as EIP-712 typed data.
+
+mod common;
+use common::*;
+
+use account::{eip712::DecodeTransaction, EthereumSigner};
+use codec::Encode;
+use diora_runtime::{
+	AccountId, Balances, Call, CheckSignatureScheme, Executive, Runtime, Signature,
+	SignatureScheme, SignedExtra, SignedPayloadFields, System, UncheckedExtrinsic, UNIT,
+};
+use frame_support::assert_ok;
+use sp_core::{ecdsa, Pair};
+use sp_runtime::{
+	generic::{Era, SignedPayload},
+	traits::IdentifyAccount,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+fn alice() -> ecdsa::Pair {
+	ecdsa::Pair::from_string("//Alice", None).unwrap()
+}
+
+fn account_of(pair: &ecdsa::Pair) -> AccountId {
+	EthereumSigner::from(pair.public()).into_account()
+}
+
+fn signed_extra(nonce: u32, scheme: SignatureScheme) -> SignedExtra {
+	(
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		CheckSignatureScheme(scheme),
+	)
+}
+
+/// Signs `call` the way `eth_signTypedData_v4` would for `SignatureScheme::Eip712`, and the way
+/// Substrate tooling would otherwise.
+fn sign(pair: &ecdsa::Pair, call: Call, nonce: u32, scheme: SignatureScheme) -> UncheckedExtrinsic {
+	let extra = signed_extra(nonce, scheme);
+	let payload = SignedPayload::new(call.clone(), extra.clone()).expect("extra is valid");
+	// Payloads longer than 256 bytes have no typed data, their keccak-256 hash is signed.
+	let signing_hash = payload.using_encoded(|payload| {
+		SignedPayloadFields::decode_transaction(payload)
+			.map(|transaction| transaction.signing_hash())
+			.unwrap_or_else(|| sp_io::hashing::keccak_256(payload))
+	});
+	let signature = Signature::from(pair.sign_prehashed(&signing_hash));
+
+	UncheckedExtrinsic::new_signed(call, account_of(pair), signature, extra)
+}
+
+fn transfer(value: u128) -> Call {
+	Call::Balances(pallet_balances::Call::transfer {
+		dest: AccountId::from(BOB),
+		value,
+	})
+}
+
+fn ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(account_of(&alice()), 1_000 * UNIT)])
+		.build()
+}
+
+#[test]
+fn eip712_signed_transfer_is_applied() {
+	ext().execute_with(|| {
+		let xt = sign(&alice(), transfer(10 * UNIT), 0, SignatureScheme::Eip712);
+
+		assert_ok!(Executive::apply_extrinsic(xt).expect("extrinsic is valid"));
+		assert_eq!(Balances::free_balance(&AccountId::from(BOB)), 10 * UNIT);
+		assert_eq!(System::account_nonce(&account_of(&alice())), 1);
+	});
+}
+
+#[test]
+fn keccak_signed_transfer_is_applied() {
+	ext().execute_with(|| {
+		let xt = sign(&alice(), transfer(10 * UNIT), 0, SignatureScheme::Keccak);
+
+		assert_ok!(Executive::apply_extrinsic(xt).expect("extrinsic is valid"));
+		assert_eq!(Balances::free_balance(&AccountId::from(BOB)), 10 * UNIT);
+	});
+}
+
+#[test]
+fn typed_data_holds_the_call_and_extra_fields() {
+	ext().execute_with(|| {
+		let payload = |scheme| {
+			SignedPayload::new(transfer(10 * UNIT), signed_extra(3, scheme))
+				.expect("extra is valid")
+				.using_encoded(SignedPayloadFields::decode_transaction)
+		};
+		let transaction = payload(SignatureScheme::Eip712).expect("payload has typed data");
+		let call = transfer(10 * UNIT).encode();
+
+		assert_eq!((transaction.pallet_index, transaction.call_index), (call[0], call[1]));
+		assert_eq!(transaction.arguments, call[2..]);
+		assert_eq!(transaction.nonce, 3);
+		assert_eq!((transaction.era_period, transaction.era_phase), (0, 0));
+		assert_eq!(transaction.tip, 0);
+		assert_eq!(transaction.spec_version, diora_runtime::VERSION.spec_version);
+		assert_eq!(transaction.genesis_hash, System::block_hash(0).0);
+		assert_eq!(payload(SignatureScheme::Keccak), None);
+	});
+}
+
+#[test]
+fn long_payloads_are_signed_through_their_keccak_hash() {
+	ext().execute_with(|| {
+		// Payloads longer than 256 bytes reach the signature as their blake2-256 hash only.
+		let call = Call::System(frame_system::Call::remark { remark: vec![0u8; 512] });
+		let xt = sign(&alice(), call, 0, SignatureScheme::Eip712);
+
+		assert_ok!(Executive::apply_extrinsic(xt).expect("extrinsic is valid"));
+	});
+}
+
+#[test]
+fn eip712_signature_of_another_call_is_rejected() {
+	ext().execute_with(|| {
+		let mut xt = sign(&alice(), transfer(10 * UNIT), 0, SignatureScheme::Eip712);
+		xt.0.function = transfer(100 * UNIT);
+
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+		);
+	});
+}
+
+#[test]
+fn eip712_signature_is_not_valid_for_the_keccak_scheme() {
+	ext().execute_with(|| {
+		let mut xt = sign(&alice(), transfer(10 * UNIT), 0, SignatureScheme::Eip712);
+		let (_, _, extra) = xt.0.signature.as_mut().expect("extrinsic is signed");
+		extra.7 = CheckSignatureScheme(SignatureScheme::Keccak);
+
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+		);
+	});
+}
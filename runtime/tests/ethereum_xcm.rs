@@ -0,0 +1,150 @@
+//! Tests for the Ethereum transactions other chains send through XCM `Transact`, from the
+//! address their XCM origin derives to.
+
+mod common;
+use common::*;
+
+use diora_runtime::{
+	AccountId, EnsureXcmEthereumAccount, Ethereum, EthereumXcm, Evm, LocationToAccountId, Origin,
+	Proxy, ProxyType, UNIT,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{EnsureOrigin, Hooks},
+};
+use pallet_ethereum_xcm::{EthereumXcmTransaction, EthereumXcmTransactionV1};
+use sp_core::{H160, U256};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::Convert;
+
+const FUNDS: u128 = 100_000 * UNIT;
+
+fn sibling() -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(2000)))
+}
+
+/// An account of the sibling chain, which has no account of its own on Diora.
+fn sibling_account(id: [u8; 32]) -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(2000), AccountId32 { network: NetworkId::Any, id }))
+}
+
+fn local_account(key: [u8; 20]) -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key }.into()
+}
+
+fn xcm_origin(location: MultiLocation) -> Origin {
+	pallet_xcm::Origin::Xcm(location).into()
+}
+
+fn address_of(location: MultiLocation) -> H160 {
+	EnsureXcmEthereumAccount::try_origin(xcm_origin(location)).ok().unwrap()
+}
+
+fn transfer_to_bob(value: u128) -> EthereumXcmTransaction {
+	EthereumXcmTransaction::V1(EthereumXcmTransactionV1 {
+		gas_limit: U256::from(100_000),
+		max_fee_per_gas: None,
+		action: pallet_ethereum::TransactionAction::Call(BOB.into()),
+		value: U256::from(value),
+		input: Default::default(),
+		access_list: Vec::new(),
+	})
+}
+
+fn sent_from() -> Vec<H160> {
+	Ethereum::on_finalize(1);
+	Ethereum::current_transaction_statuses()
+		.unwrap_or_default()
+		.into_iter()
+		.map(|status| status.from)
+		.collect()
+}
+
+#[test]
+fn chains_and_local_accounts_send_from_their_account() {
+	// The sovereign account of the sibling, holding its assets on Diora.
+	let sovereign: AccountId = LocationToAccountId::convert_ref(sibling()).unwrap();
+
+	assert_eq!(address_of(sibling()), H160::from(sovereign));
+	assert_eq!(address_of(local_account(ALICE)), H160::from(ALICE));
+}
+
+#[test]
+fn accounts_of_other_chains_send_from_an_address_of_their_own() {
+	let first = address_of(sibling_account([1u8; 32]));
+	let second = address_of(sibling_account([2u8; 32]));
+
+	assert_ne!(first, second);
+	assert_ne!(first, address_of(sibling()));
+	// The account of a key on another chain is not the account of the same key on Diora.
+	let alice_on_sibling =
+		X2(Parachain(2000), AccountKey20 { network: NetworkId::Any, key: ALICE });
+	assert_ne!(address_of(MultiLocation::new(1, alice_on_sibling)), H160::from(ALICE));
+}
+
+#[test]
+fn only_xcm_origins_send_transactions() {
+	assert!(EnsureXcmEthereumAccount::try_origin(Origin::signed(ALICE.into())).is_err());
+	assert!(EnsureXcmEthereumAccount::try_origin(Origin::root()).is_err());
+
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), FUNDS)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				EthereumXcm::transact(Origin::signed(ALICE.into()), transfer_to_bob(UNIT)),
+				DispatchError::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn account_of_another_chain_sends_a_transaction() {
+	let sender = address_of(sibling_account([1u8; 32]));
+
+	ExtBuilder::default()
+		.with_balances(vec![(sender.into(), FUNDS)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(EthereumXcm::transact(
+				xcm_origin(sibling_account([1u8; 32])),
+				transfer_to_bob(UNIT)
+			));
+
+			assert_eq!(Evm::account_basic(&BOB.into()).0.balance, U256::from(UNIT));
+			assert_eq!(Evm::account_basic(&sender).0.nonce, U256::one());
+			assert_eq!(sent_from(), vec![sender]);
+		});
+}
+
+#[test]
+fn proxy_sends_a_transaction_for_the_account_it_is_a_proxy_of() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), FUNDS), (BOB.into(), FUNDS)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				EthereumXcm::transact_through_proxy(
+					xcm_origin(local_account(BOB)),
+					ALICE.into(),
+					transfer_to_bob(UNIT)
+				),
+				pallet_ethereum_xcm::Error::<diora_runtime::Runtime>::NotProxy
+			);
+
+			assert_ok!(Proxy::add_proxy(
+				Origin::signed(ALICE.into()),
+				BOB.into(),
+				ProxyType::Any,
+				0
+			));
+			assert_ok!(EthereumXcm::transact_through_proxy(
+				xcm_origin(local_account(BOB)),
+				ALICE.into(),
+				transfer_to_bob(UNIT)
+			));
+
+			assert_eq!(sent_from(), vec![H160::from(ALICE)]);
+		});
+}
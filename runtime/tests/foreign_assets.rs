@@ -0,0 +1,171 @@
+//! Tests for foreign assets registered in the asset manager, received and sent back over XCM.
+
+mod common;
+use common::*;
+
+use diora_runtime::{AccountId, AssetManager, Assets, Call, Origin, XcmConfig, XcmWeigher, UNIT};
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable, weights::Weight};
+use pallet_asset_manager::AssetMetadata;
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::WeightBounds, XcmExecutor};
+
+const ASSET_ID: u32 = 1;
+const AMOUNT: u128 = 1_000 * UNIT;
+
+/// A token of the sibling parachain 2000.
+fn sibling_token() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(2000), GeneralKey(b"TOKEN".to_vec())))
+}
+
+fn sibling(id: u32) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(id)))
+}
+
+fn alice_location() -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key: ALICE }.into()
+}
+
+fn register_sibling_token() {
+	assert_ok!(AssetManager::register_asset(
+		Origin::root(),
+		ASSET_ID,
+		Box::new(sibling_token().into()),
+		AssetMetadata { name: b"Sibling token".to_vec(), symbol: b"TOKEN".to_vec(), decimals: 12 },
+		1,
+		true,
+	));
+}
+
+fn weight_of(message: Vec<Instruction<Call>>) -> Weight {
+	XcmWeigher::weight(&mut Xcm(message)).expect("message is weighable")
+}
+
+/// Executes `message` as if received from `origin`, prepaid through the weight credit.
+fn execute(origin: MultiLocation, message: Vec<Instruction<Call>>) -> Outcome {
+	let weight = weight_of(message.clone());
+	XcmExecutor::<XcmConfig>::execute_xcm_in_credit(origin, Xcm(message), weight, weight)
+}
+
+fn reserve_deposit(asset: MultiLocation) -> Vec<Instruction<Call>> {
+	vec![
+		ReserveAssetDeposited((asset, AMOUNT).into()),
+		ClearOrigin,
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary: alice_location() },
+	]
+}
+
+fn balance_of_alice() -> u128 {
+	Assets::balance(ASSET_ID, AccountId::from(ALICE))
+}
+
+#[test]
+fn registered_asset_is_deposited_from_its_reserve() {
+	ExtBuilder::default().build().execute_with(|| {
+		register_sibling_token();
+
+		let outcome = execute(sibling(2000), reserve_deposit(sibling_token()));
+
+		assert_eq!(outcome, Outcome::Complete(weight_of(reserve_deposit(sibling_token()))));
+		assert_eq!(balance_of_alice(), AMOUNT);
+	});
+}
+
+#[test]
+fn asset_is_only_trusted_from_its_reserve() {
+	ExtBuilder::default().build().execute_with(|| {
+		register_sibling_token();
+
+		let outcome = execute(sibling(2001), reserve_deposit(sibling_token()));
+		assert!(matches!(outcome, Outcome::Incomplete(_, XcmError::UntrustedReserveLocation)));
+
+		let outcome = execute(MultiLocation::parent(), reserve_deposit(sibling_token()));
+		assert!(matches!(outcome, Outcome::Incomplete(_, XcmError::UntrustedReserveLocation)));
+
+		assert_eq!(balance_of_alice(), 0);
+	});
+}
+
+#[test]
+fn unregistered_asset_is_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		let outcome = execute(sibling(2000), reserve_deposit(sibling_token()));
+
+		assert!(matches!(outcome, Outcome::Incomplete(_, XcmError::UntrustedReserveLocation)));
+	});
+}
+
+#[test]
+fn moved_asset_is_only_received_at_its_new_location() {
+	ExtBuilder::default().build().execute_with(|| {
+		register_sibling_token();
+		let moved = MultiLocation::new(1, X2(Parachain(2000), GeneralIndex(7)));
+		assert_ok!(AssetManager::update_asset_location(
+			Origin::root(),
+			ASSET_ID,
+			Box::new(moved.clone().into())
+		));
+
+		let outcome = execute(sibling(2000), reserve_deposit(sibling_token()));
+		assert!(matches!(outcome, Outcome::Incomplete(_, XcmError::UntrustedReserveLocation)));
+
+		let outcome = execute(sibling(2000), reserve_deposit(moved));
+		assert!(matches!(outcome, Outcome::Complete(_)));
+		assert_eq!(balance_of_alice(), AMOUNT);
+	});
+}
+
+#[test]
+fn holder_withdraws_foreign_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		register_sibling_token();
+		assert!(matches!(
+			execute(sibling(2000), reserve_deposit(sibling_token())),
+			Outcome::Complete(_)
+		));
+
+		let bob = AccountKey20 { network: NetworkId::Any, key: BOB };
+		let outcome = execute(
+			alice_location(),
+			vec![
+				WithdrawAsset((sibling_token(), AMOUNT / 4).into()),
+				DepositAsset { assets: All.into(), max_assets: 1, beneficiary: bob.into() },
+			],
+		);
+
+		assert!(matches!(outcome, Outcome::Complete(_)));
+		assert_eq!(balance_of_alice(), AMOUNT - AMOUNT / 4);
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(BOB)), AMOUNT / 4);
+	});
+}
+
+#[test]
+fn accounts_cannot_create_assets_or_set_their_metadata() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), AMOUNT)])
+		.build()
+		.execute_with(|| {
+			let create = Call::Assets(pallet_assets::Call::create {
+				id: ASSET_ID,
+				admin: ALICE.into(),
+				min_balance: 1,
+			});
+			assert_noop!(
+				create.dispatch(Origin::signed(ALICE.into())),
+				frame_system::Error::<diora_runtime::Runtime>::CallFiltered
+			);
+
+			register_sibling_token();
+			let set_metadata = Call::Assets(pallet_assets::Call::set_metadata {
+				id: ASSET_ID,
+				name: b"Diora".to_vec(),
+				symbol: b"DIR".to_vec(),
+				decimals: 18,
+			});
+			assert_noop!(
+				set_metadata.dispatch(Origin::signed(ALICE.into())),
+				frame_system::Error::<diora_runtime::Runtime>::CallFiltered
+			);
+			let metadata = pallet_assets::Metadata::<diora_runtime::Runtime>::get(ASSET_ID);
+			assert_eq!(metadata.symbol.to_vec(), b"TOKEN".to_vec());
+		});
+}
@@ -0,0 +1,232 @@
+//! Tests for on-chain governance: collectives, democracy and the origins they stand in for.
+
+mod common;
+use common::*;
+
+use diora_runtime::{
+	AccountId, AuthorFilter, Call, Democracy, EligibilityValue, Event, Origin, Runtime, Scheduler,
+	System, UNIT,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::OnInitialize,
+	weights::GetDispatchInfo,
+};
+use fp_evm::{ExitReason, ExitRevert};
+use pallet_democracy::{AccountVote, Conviction, Vote};
+use codec::Encode;
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+fn alice() -> AccountId {
+	ALICE.into()
+}
+
+fn bob() -> AccountId {
+	BOB.into()
+}
+
+/// Alice and Bob, with funds to vote, sitting on both collectives.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(alice(), 1_000 * UNIT), (bob(), 1_000 * UNIT)])
+		.build();
+	ext.execute_with(|| {
+		assert_ok!(diora_runtime::Council::set_members(
+			Origin::root(),
+			vec![alice(), bob()],
+			None,
+			0
+		));
+		assert_ok!(diora_runtime::TechnicalCommittee::set_members(
+			Origin::root(),
+			vec![alice(), bob()],
+			None,
+			0
+		));
+	});
+	ext
+}
+
+fn run_to_block(n: u32) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Scheduler::on_initialize(next);
+		Democracy::on_initialize(next);
+	}
+}
+
+/// Has Alice propose `call` to the collective `I` and Bob second it, so that it is dispatched
+/// with the approval of all its members.
+fn pass_motion<I: 'static>(call: Call)
+where
+	Runtime: pallet_collective::Config<I, Proposal = Call>,
+{
+	System::reset_events();
+	let length = call.encoded_size() as u32;
+	let weight = call.get_dispatch_info().weight;
+	let hash = BlakeTwo256::hash_of(&call);
+
+	assert_ok!(pallet_collective::Pallet::<Runtime, I>::propose(
+		Origin::signed(alice()),
+		2,
+		Box::new(call),
+		length
+	));
+	let index = pallet_collective::Pallet::<Runtime, I>::proposal_count() - 1;
+	assert_ok!(pallet_collective::Pallet::<Runtime, I>::vote(
+		Origin::signed(bob()),
+		hash,
+		index,
+		true
+	));
+	assert_ok!(pallet_collective::Pallet::<Runtime, I>::close(
+		Origin::signed(alice()),
+		hash,
+		index,
+		weight,
+		length
+	));
+	assert!(System::events().iter().any(|record| matches!(
+		record.event,
+		Event::Council(pallet_collective::Event::Executed { result: Ok(()), .. }) |
+			Event::TechnicalCommittee(pallet_collective::Event::Executed {
+				result: Ok(()),
+				..
+			})
+	)));
+}
+
+#[test]
+fn runtime_upgrade_is_authorized_by_referendum() {
+	new_test_ext().execute_with(|| {
+		let code_hash = H256::repeat_byte(0x42);
+		let upgrade =
+			Call::ParachainSystem(cumulus_pallet_parachain_system::Call::authorize_upgrade {
+				code_hash,
+			});
+		let encoded = upgrade.encode();
+		let proposal_hash = BlakeTwo256::hash(&encoded[..]);
+		assert_ok!(Democracy::note_preimage(Origin::signed(alice()), encoded));
+
+		// The council puts the upgrade to a majority-carries referendum...
+		pass_motion::<diora_runtime::CouncilCollective>(Call::Democracy(
+			pallet_democracy::Call::external_propose_majority { proposal_hash },
+		));
+
+		// ...which the technical committee starts right away, with a short voting period.
+		pass_motion::<diora_runtime::TechnicalCollective>(Call::Democracy(
+			pallet_democracy::Call::fast_track { proposal_hash, voting_period: 5, delay: 1 },
+		));
+		assert_eq!(Democracy::referendum_count(), 1);
+
+		assert_ok!(Democracy::vote(
+			Origin::signed(alice()),
+			0,
+			AccountVote::Standard {
+				vote: Vote { aye: true, conviction: Conviction::Locked1x },
+				balance: 100 * UNIT,
+			}
+		));
+
+		// Voting ends five blocks after the referendum started and it is enacted a block later.
+		run_to_block(7);
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::ParachainSystem(cumulus_pallet_parachain_system::Event::UpgradeAuthorized {
+				code_hash
+			})));
+	});
+}
+
+#[test]
+fn runtime_upgrade_needs_governance() {
+	new_test_ext().execute_with(|| {
+		let code_hash = H256::repeat_byte(0x42);
+		assert_noop!(
+			diora_runtime::ParachainSystem::authorize_upgrade(Origin::signed(alice()), code_hash),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn eligible_authors_are_set_by_technical_committee() {
+	new_test_ext().execute_with(|| {
+		let new = EligibilityValue::new(10).unwrap();
+		assert_noop!(
+			AuthorFilter::set_eligible(Origin::signed(alice()), new),
+			DispatchError::BadOrigin
+		);
+
+		pass_motion::<diora_runtime::TechnicalCollective>(Call::AuthorFilter(
+			pallet_author_slot_filter::Call::set_eligible { new },
+		));
+
+		assert_eq!(AuthorFilter::eligible_count(), new);
+	});
+}
+
+#[test]
+fn council_minority_cannot_manage_deployers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Council::set_members(
+			Origin::root(),
+			vec![alice(), bob(), [6u8; 20].into()],
+			None,
+			3
+		));
+		let call = Call::DeploymentAllowlist(pallet_deployment_allowlist::Call::add_deployer {
+			deployer: BOB.into(),
+		});
+
+		// One out of three members is not more than half of the council.
+		assert_ok!(diora_runtime::Council::propose(
+			Origin::signed(alice()),
+			1,
+			Box::new(call.clone()),
+			call.encoded_size() as u32
+		));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::Council(pallet_collective::Event::Executed {
+				result: Err(DispatchError::BadOrigin),
+				..
+			})
+		)));
+		assert!(!pallet_deployment_allowlist::AllowedDeployers::<Runtime>::contains_key(
+			sp_core::H160::from(BOB)
+		));
+	});
+}
+
+#[test]
+fn collective_votes_cannot_be_delegatecalled_through_the_dispatch_precompile() {
+	new_test_ext().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let hash = BlakeTwo256::hash_of(&call);
+		assert_ok!(diora_runtime::Council::propose(
+			Origin::signed(alice()),
+			2,
+			Box::new(call.clone()),
+			call.encoded_size() as u32
+		));
+
+		// Bob calling a contract must not let it vote on his behalf.
+		let contract = H160::from_low_u64_be(0xdead);
+		install_delegating_contract(contract, 1026);
+		let vote = Call::Council(pallet_collective::Call::vote {
+			proposal: hash,
+			index: 0,
+			approve: true,
+		});
+		let info = evm_call(BOB, contract, vote.encode(), 1_000_000);
+
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		let voting = diora_runtime::Council::voting(hash).expect("the motion is open");
+		assert_eq!(voting.ayes, vec![alice()]);
+	});
+}
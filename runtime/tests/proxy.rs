@@ -0,0 +1,253 @@
+//! Tests for operating accounts through proxies, multisig accounts and batches.
+
+mod common;
+use common::*;
+
+use codec::Encode;
+use diora_runtime::{
+	pallet_account_set, AccountId, Balances, Call, Democracy, Event, Multisig, Origin,
+	PotentialAuthorSet, ProxyType, System, Utility, UNIT,
+};
+use frame_support::{assert_noop, assert_ok, traits::WrapperKeepOpaque, weights::GetDispatchInfo};
+use nimbus_primitives::{AccountLookup, NimbusId};
+use sp_core::sr25519;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn alice() -> AccountId {
+	ALICE.into()
+}
+
+fn bob() -> AccountId {
+	BOB.into()
+}
+
+fn charlie() -> AccountId {
+	[6u8; 20].into()
+}
+
+fn nimbus_id(seed: u8) -> NimbusId {
+	sr25519::Public::from_raw([seed; 32]).into()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(alice(), 1_000 * UNIT), (bob(), 1_000 * UNIT)])
+		.with_authors(vec![(alice(), nimbus_id(1))])
+		.build()
+}
+
+fn update_association(old: NimbusId, new: NimbusId) -> Call {
+	Call::PotentialAuthorSet(pallet_account_set::Call::update_association { old, new })
+}
+
+fn transfer(value: u128) -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: charlie(), value })
+}
+
+/// Bob makes `call` as a proxy of Alice.
+fn proxy_call(call: Call) -> Result<(), sp_runtime::DispatchError> {
+	System::reset_events();
+	assert_ok!(diora_runtime::Proxy::proxy(
+		Origin::signed(bob()),
+		alice(),
+		None,
+		Box::new(call)
+	));
+	System::events()
+		.into_iter()
+		.find_map(|record| match record.event {
+			Event::Proxy(pallet_proxy::Event::ProxyExecuted { result }) => Some(result),
+			_ => None,
+		})
+		.expect("proxy executed the call")
+}
+
+#[test]
+fn non_transfer_proxy_cannot_move_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Proxy::add_proxy(
+			Origin::signed(alice()),
+			bob(),
+			ProxyType::NonTransfer,
+			0
+		));
+
+		assert_eq!(
+			proxy_call(transfer(UNIT)),
+			Err(frame_system::Error::<diora_runtime::Runtime>::CallFiltered.into())
+		);
+		// Nor from within a batch.
+		assert!(proxy_call(Call::Utility(pallet_utility::Call::batch_all {
+			calls: vec![transfer(UNIT)]
+		}))
+		.is_err());
+		assert_eq!(Balances::free_balance(&charlie()), 0);
+
+		assert_ok!(proxy_call(Call::System(frame_system::Call::remark {
+			remark: b"still alive".to_vec()
+		})));
+	});
+}
+
+#[test]
+fn non_transfer_proxy_cannot_add_governance_proxies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Proxy::add_proxy(
+			Origin::signed(alice()),
+			bob(),
+			ProxyType::NonTransfer,
+			0
+		));
+
+		// A governance proxy could make treasury proposals, which the non-transfer one cannot.
+		assert_eq!(
+			proxy_call(Call::Proxy(pallet_proxy::Call::add_proxy {
+				delegate: charlie(),
+				proxy_type: ProxyType::Governance,
+				delay: 0,
+			})),
+			Err(frame_system::Error::<diora_runtime::Runtime>::CallFiltered.into())
+		);
+		assert_ok!(proxy_call(Call::Proxy(pallet_proxy::Call::add_proxy {
+			delegate: charlie(),
+			proxy_type: ProxyType::NonTransfer,
+			delay: 0,
+		})));
+	});
+}
+
+#[test]
+fn governance_proxy_votes_for_its_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Proxy::add_proxy(
+			Origin::signed(alice()),
+			bob(),
+			ProxyType::Governance,
+			0
+		));
+		let proposal_hash = BlakeTwo256::hash(b"proposal");
+
+		assert_ok!(proxy_call(Call::Democracy(pallet_democracy::Call::propose {
+			proposal_hash,
+			value: 100 * UNIT,
+		})));
+		assert_eq!(Balances::reserved_balance(&alice()), 100 * UNIT);
+		assert_eq!(Democracy::public_prop_count(), 1);
+
+		assert!(proxy_call(transfer(UNIT)).is_err());
+	});
+}
+
+#[test]
+fn author_mapping_proxy_rotates_the_authoring_key() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Proxy::add_proxy(
+			Origin::signed(alice()),
+			bob(),
+			ProxyType::AuthorMapping,
+			0
+		));
+
+		assert_ok!(proxy_call(update_association(nimbus_id(1), nimbus_id(2))));
+		assert_eq!(PotentialAuthorSet::lookup_account(&nimbus_id(1)), None);
+		assert_eq!(PotentialAuthorSet::lookup_account(&nimbus_id(2)), Some(alice()));
+
+		assert_eq!(
+			proxy_call(transfer(UNIT)),
+			Err(frame_system::Error::<diora_runtime::Runtime>::CallFiltered.into())
+		);
+		assert_eq!(
+			proxy_call(Call::System(frame_system::Call::remark { remark: vec![] })),
+			Err(frame_system::Error::<diora_runtime::Runtime>::CallFiltered.into())
+		);
+	});
+}
+
+#[test]
+fn only_the_associated_author_rotates_its_key() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PotentialAuthorSet::update_association(
+				Origin::signed(bob()),
+				nimbus_id(1),
+				nimbus_id(2)
+			),
+			pallet_account_set::Error::<diora_runtime::Runtime>::NotYourAssociation
+		);
+		assert_noop!(
+			PotentialAuthorSet::update_association(
+				Origin::signed(alice()),
+				nimbus_id(1),
+				nimbus_id(1)
+			),
+			pallet_account_set::Error::<diora_runtime::Runtime>::AlreadyAssociated
+		);
+	});
+}
+
+#[test]
+fn non_transfer_proxy_can_add_author_mapping_proxies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(diora_runtime::Proxy::add_proxy(
+			Origin::signed(alice()),
+			bob(),
+			ProxyType::NonTransfer,
+			0
+		));
+
+		assert_ok!(proxy_call(Call::Proxy(pallet_proxy::Call::add_proxy {
+			delegate: charlie(),
+			proxy_type: ProxyType::AuthorMapping,
+			delay: 0,
+		})));
+		assert_ok!(proxy_call(update_association(nimbus_id(1), nimbus_id(2))));
+	});
+}
+
+#[test]
+fn multisig_transfers_once_both_signatories_approve() {
+	new_test_ext().execute_with(|| {
+		let multisig = Multisig::multi_account_id(&[alice(), bob()], 2);
+		let _ = <Balances as frame_support::traits::Currency<_>>::deposit_creating(
+			&multisig,
+			100 * UNIT,
+		);
+		let call = transfer(10 * UNIT);
+		let weight = call.get_dispatch_info().weight;
+		let call_hash = BlakeTwo256::hash_of(&call).into();
+
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(alice()),
+			2,
+			vec![bob()],
+			None,
+			call_hash,
+			0
+		));
+		assert_eq!(Balances::free_balance(&charlie()), 0);
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(bob()),
+			2,
+			vec![alice()],
+			Some(Multisig::timepoint()),
+			WrapperKeepOpaque::from_encoded(call.encode()),
+			false,
+			weight
+		));
+		assert_eq!(Balances::free_balance(&charlie()), 10 * UNIT);
+		assert_eq!(Balances::free_balance(&multisig), 90 * UNIT);
+	});
+}
+
+#[test]
+fn batch_dispatches_every_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Utility::batch_all(
+			Origin::signed(alice()),
+			vec![transfer(UNIT), transfer(2 * UNIT)]
+		));
+
+		assert_eq!(Balances::free_balance(&charlie()), 3 * UNIT);
+	});
+}
@@ -0,0 +1,107 @@
+//! Tests for the treasury: how it is funded and how its funds are spent.
+
+mod common;
+use common::*;
+
+use diora_runtime::{
+	AccountId, Balance, Balances, Block, DealWithFees, Origin, Runtime, SpendPeriod, Treasury,
+	TreasuryFeeShare, EXISTENTIAL_DEPOSIT, UNIT,
+};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, ExistenceRequirement, Get, OnInitialize, OnUnbalanced, WithdrawReasons},
+};
+use treasury_runtime_api::{runtime_decl_for_TreasuryApi::TreasuryApi, PendingSpend};
+
+fn alice() -> AccountId {
+	ALICE.into()
+}
+
+fn bob() -> AccountId {
+	BOB.into()
+}
+
+fn treasury_api_balance() -> Balance {
+	<Runtime as TreasuryApi<Block, AccountId, Balance>>::balance()
+}
+
+fn pending_spends() -> Vec<PendingSpend<AccountId, Balance>> {
+	<Runtime as TreasuryApi<Block, AccountId, Balance>>::pending_spends()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(alice(), 1_000 * UNIT), (bob(), 1_000 * UNIT)])
+		.build();
+	// Keeps the treasury account alive, whatever it is credited.
+	ext.execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), EXISTENTIAL_DEPOSIT);
+	});
+	ext
+}
+
+#[test]
+fn treasury_takes_its_share_of_fees() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		let fee = 100 * UNIT;
+		let imbalance = Balances::withdraw(
+			&alice(),
+			fee,
+			WithdrawReasons::FEE,
+			ExistenceRequirement::KeepAlive,
+		)
+		.unwrap();
+
+		DealWithFees::on_unbalanced(imbalance);
+
+		let to_treasury = TreasuryFeeShare::get() * fee;
+		assert_eq!(Treasury::pot(), to_treasury);
+		assert_eq!(treasury_api_balance(), to_treasury);
+		// The rest is burned.
+		assert_eq!(Balances::total_issuance(), issuance - (fee - to_treasury));
+	});
+}
+
+#[test]
+fn reaped_dust_goes_to_treasury() {
+	new_test_ext().execute_with(|| {
+		let dust = EXISTENTIAL_DEPOSIT / 2;
+		assert_ok!(Balances::transfer(Origin::signed(alice()), bob(), 1_000 * UNIT - dust));
+
+		assert_eq!(Balances::free_balance(&alice()), 0);
+		assert_eq!(Treasury::pot(), dust);
+	});
+}
+
+#[test]
+fn approved_spend_is_paid_out_at_spend_period() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::deposit_creating(&Treasury::account_id(), 500 * UNIT);
+		let charlie: AccountId = [6u8; 20].into();
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(alice()), 200 * UNIT, charlie));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		let bond = 100 * UNIT;
+		assert_eq!(
+			pending_spends(),
+			vec![PendingSpend {
+				index: 0,
+				proposer: alice(),
+				value: 200 * UNIT,
+				beneficiary: charlie,
+				bond,
+				approved: true,
+			}]
+		);
+
+		Treasury::on_initialize(SpendPeriod::get());
+
+		assert_eq!(Balances::free_balance(&charlie), 200 * UNIT);
+		assert_eq!(treasury_api_balance(), 300 * UNIT);
+		assert!(pending_spends().is_empty());
+		// The bond of an approved proposal is returned.
+		assert_eq!(Balances::reserved_balance(&alice()), 0);
+	});
+}
@@ -0,0 +1,89 @@
+//! Tests for runtime upgrades scheduled by governance, enacted through the parachain system.
+
+mod common;
+use common::*;
+
+use codec::Encode;
+use cumulus_primitives_core::{AbridgedHostConfiguration, PersistedValidationData};
+use diora_runtime::{
+	Event, Origin, ParachainSystem, Runtime, System, UpgradeMinimumDelay, UpgradeScheduler, UNIT,
+	VERSION,
+};
+use frame_support::{assert_noop, assert_ok, storage::storage_prefix, traits::Get};
+use sp_core::traits::{Externalities, ReadRuntimeVersion, ReadRuntimeVersionExt};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_version::RuntimeVersion;
+
+/// Reads the code as an encoded runtime version, in place of instantiating it.
+struct CodeIsVersion;
+impl ReadRuntimeVersion for CodeIsVersion {
+	fn read_runtime_version(
+		&self,
+		wasm_code: &[u8],
+		_ext: &mut dyn Externalities,
+	) -> Result<Vec<u8>, String> {
+		Ok(wasm_code.to_vec())
+	}
+}
+
+/// Code of the next version of the runtime.
+fn next_runtime() -> Vec<u8> {
+	RuntimeVersion { spec_version: VERSION.spec_version + 1, ..VERSION }.encode()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().with_balances(vec![(ALICE.into(), UNIT)]).build();
+	ext.register_extension(ReadRuntimeVersionExt::new(CodeIsVersion));
+	ext
+}
+
+/// Stores what the `set_validation_data` inherent provides once the block is initialized.
+fn set_validation_data() {
+	frame_support::storage::unhashed::put(
+		&storage_prefix(b"ParachainSystem", b"ValidationData"),
+		&PersistedValidationData::<sp_core::H256, u32>::default(),
+	);
+	frame_support::storage::unhashed::put(
+		&storage_prefix(b"ParachainSystem", b"HostConfiguration"),
+		&AbridgedHostConfiguration {
+			max_code_size: 2 * 1024 * 1024,
+			max_head_data_size: 1024,
+			max_upward_queue_count: 8,
+			max_upward_queue_size: 1024,
+			max_upward_message_size: 256,
+			max_upward_message_num_per_candidate: 5,
+			hrmp_max_message_num_per_candidate: 5,
+			validation_upgrade_cooldown: 6,
+			validation_upgrade_delay: 6,
+		},
+	);
+}
+
+#[test]
+fn upgrade_is_handed_to_the_parachain_system() {
+	new_test_ext().execute_with(|| {
+		let code = next_runtime();
+		let code_hash = BlakeTwo256::hash(&code);
+		let at = System::block_number() + UpgradeMinimumDelay::get();
+		assert_ok!(UpgradeScheduler::schedule_upgrade(Origin::root(), code.clone(), at));
+		System::set_block_number(at);
+
+		// Before the inherents of the block, the parachain system cannot accept the code.
+		assert_noop!(
+			UpgradeScheduler::enact_upgrade(Origin::signed(ALICE.into())),
+			cumulus_pallet_parachain_system::Error::<Runtime>::ValidationDataNotAvailable
+		);
+		assert!(UpgradeScheduler::scheduled_upgrade().is_some());
+
+		set_validation_data();
+		assert_ok!(UpgradeScheduler::enact_upgrade(Origin::signed(ALICE.into())));
+
+		assert_eq!(ParachainSystem::new_validation_code(), Some(code));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::UpgradeScheduler(pallet_upgrade_scheduler::Event::UpgradeEnacted {
+				code_hash,
+				spec_version: VERSION.spec_version + 1,
+			})));
+		assert_eq!(UpgradeScheduler::scheduled_upgrade(), None);
+	});
+}
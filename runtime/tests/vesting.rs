@@ -0,0 +1,122 @@
+//! Tests for vesting schedules, their runtime API and the vest precompile.
+
+mod common;
+use common::*;
+
+use diora_runtime::{AccountId, Balance, Balances, Block, Origin, Runtime, System, Vesting, UNIT};
+use fp_evm::{ExitReason, ExitSucceed};
+use frame_support::{assert_noop, assert_ok};
+use pallet_evm::{CallInfo, Runner};
+use pallet_vesting::VestingInfo;
+use sp_core::{H160, U256};
+use vesting_runtime_api::{runtime_decl_for_VestingApi::VestingApi, VestingBalance};
+
+const GAS_LIMIT: u64 = 1_000_000;
+
+const VEST: [u8; 4] = [0x45, 0x8e, 0xfd, 0xe3];
+const LOCKED_BALANCE: [u8; 4] = [0x9a, 0xe6, 0x97, 0xbf];
+
+fn bob() -> AccountId {
+	BOB.into()
+}
+
+fn call_vest_precompile(caller: [u8; 20], input: Vec<u8>) -> CallInfo {
+	<Runtime as pallet_evm::Config>::Runner::call(
+		H160(caller),
+		H160::from_low_u64_be(1029),
+		input,
+		U256::zero(),
+		GAS_LIMIT,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false,
+		<Runtime as pallet_evm::Config>::config(),
+	)
+	.expect("runner accepts the call")
+}
+
+fn vesting_balance(who: AccountId) -> VestingBalance<Balance> {
+	<Runtime as VestingApi<Block, AccountId, Balance>>::vesting_balance(who)
+}
+
+/// Bob receives 100 units from Alice, released by 10 units per block from block 1.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_balances(vec![(ALICE.into(), 1_000 * UNIT)])
+		.build();
+	ext.execute_with(|| {
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(ALICE.into()),
+			bob(),
+			VestingInfo::new(100 * UNIT, 10 * UNIT, 1)
+		));
+	});
+	ext
+}
+
+#[test]
+fn vested_funds_stay_locked_until_the_account_vests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(4);
+
+		assert_eq!(
+			vesting_balance(bob()),
+			VestingBalance { locked: 70 * UNIT, vested: 30 * UNIT }
+		);
+		assert_noop!(
+			Balances::transfer(Origin::signed(bob()), ALICE.into(), 10 * UNIT),
+			pallet_balances::Error::<Runtime>::LiquidityRestrictions
+		);
+
+		assert_ok!(Vesting::vest(Origin::signed(bob())));
+
+		assert_eq!(vesting_balance(bob()), VestingBalance { locked: 70 * UNIT, vested: 0 });
+		assert_ok!(Balances::transfer(Origin::signed(bob()), ALICE.into(), 30 * UNIT));
+	});
+}
+
+#[test]
+fn accounts_without_schedule_have_nothing_locked() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(vesting_balance(ALICE.into()), VestingBalance::default());
+	});
+}
+
+#[test]
+fn precompile_vests_for_the_caller() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(4);
+
+		let info = call_vest_precompile(BOB, VEST.to_vec());
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(vesting_balance(bob()), VestingBalance { locked: 70 * UNIT, vested: 0 });
+	});
+}
+
+#[test]
+fn precompile_reverts_without_schedule() {
+	new_test_ext().execute_with(|| {
+		let info = call_vest_precompile(ALICE, VEST.to_vec());
+
+		assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+		assert_eq!(info.value, b"nothing to vest".to_vec());
+	});
+}
+
+#[test]
+fn precompile_reports_locked_balance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(4);
+		let mut input = LOCKED_BALANCE.to_vec();
+		input.extend([0u8; 12]);
+		input.extend(BOB);
+
+		let info = call_vest_precompile(ALICE, input);
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(U256::from_big_endian(&info.value), U256::from(70 * UNIT));
+	});
+}
@@ -0,0 +1,213 @@
+//! Tests for the XCM barrier of Diora on the XCM simulator: query responses and version
+//! subscriptions, accepted and rejected, and teleports, which are all rejected.
+
+mod xcm_mock;
+use xcm_mock::*;
+
+use frame_support::assert_ok;
+use xcm::latest::{prelude::*, VERSION};
+use xcm_simulator::TestExt;
+
+const AMOUNT: u128 = 1_000_000_000_000;
+
+fn para(id: u32) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(id)))
+}
+
+fn bob() -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key: BOB }.into()
+}
+
+fn last_msg_queue_event() -> Option<parachain::mock_msg_queue::Event<parachain::Runtime>> {
+	parachain::System::events().into_iter().rev().find_map(|record| match record.event {
+		parachain::Event::MsgQueue(event) => Some(event),
+		_ => None,
+	})
+}
+
+fn xcm_pallet_events() -> Vec<pallet_xcm::Event<parachain::Runtime>> {
+	parachain::System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			parachain::Event::PolkadotXcm(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+fn assert_rejected_with(error: XcmError) {
+	assert!(matches!(
+		last_msg_queue_event(),
+		Some(parachain::mock_msg_queue::Event::Fail(_, e)) if e == error
+	));
+}
+
+/// Sends `message` from parachain B to parachain A over HRMP.
+fn send_from_para_b(message: Vec<Instruction<()>>) {
+	ParaB::execute_with(|| {
+		assert_ok!(<parachain::XcmRouter as SendXcm>::send_xcm(para(1), Xcm(message)));
+	});
+}
+
+fn response(query_id: u64) -> Instruction<()> {
+	QueryResponse { query_id, response: Response::ExecutionResult(None), max_weight: 0 }
+}
+
+#[test]
+fn expected_query_response_is_accepted() {
+	MockNet::reset();
+
+	let query_id = ParaA::execute_with(|| parachain::PolkadotXcm::new_query(para(2), 100));
+
+	send_from_para_b(vec![response(query_id)]);
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			last_msg_queue_event(),
+			Some(parachain::mock_msg_queue::Event::Success(_))
+		));
+		assert!(xcm_pallet_events().contains(&pallet_xcm::Event::ResponseReady(
+			query_id,
+			Response::ExecutionResult(None)
+		)));
+	});
+}
+
+#[test]
+fn unexpected_query_response_is_rejected() {
+	MockNet::reset();
+
+	send_from_para_b(vec![response(7)]);
+
+	ParaA::execute_with(|| {
+		assert_rejected_with(XcmError::Barrier);
+		assert!(xcm_pallet_events().is_empty());
+	});
+}
+
+#[test]
+fn query_response_is_only_accepted_from_the_responder() {
+	MockNet::reset();
+
+	let query_id =
+		ParaA::execute_with(|| parachain::PolkadotXcm::new_query(MultiLocation::parent(), 100));
+
+	send_from_para_b(vec![response(query_id)]);
+
+	ParaA::execute_with(|| {
+		assert_rejected_with(XcmError::Barrier);
+		assert!(xcm_pallet_events().is_empty());
+	});
+}
+
+#[test]
+fn sibling_subscribes_to_xcm_version() {
+	MockNet::reset();
+
+	ParaB::execute_with(|| {
+		assert_ok!(parachain::PolkadotXcm::force_subscribe_version_notify(
+			parachain::Origin::root(),
+			Box::new(para(1).into()),
+		));
+	});
+
+	// Parachain A accepts the subscription and notifies its version, which parachain B accepts as
+	// the response to its query.
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			last_msg_queue_event(),
+			Some(parachain::mock_msg_queue::Event::Success(_))
+		));
+	});
+	ParaB::execute_with(|| {
+		assert!(xcm_pallet_events()
+			.contains(&pallet_xcm::Event::SupportedVersionChanged(para(1), VERSION)));
+	});
+}
+
+#[test]
+fn relay_chain_subscribes_to_xcm_version() {
+	MockNet::reset();
+
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmPallet::force_subscribe_version_notify(
+			relay_chain::Origin::root(),
+			Box::new(Parachain(1).into()),
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			last_msg_queue_event(),
+			Some(parachain::mock_msg_queue::Event::ExecutedDownward(_, Outcome::Complete(_)))
+		));
+	});
+	Relay::execute_with(|| {
+		let changed = relay_chain::Event::XcmPallet(pallet_xcm::Event::SupportedVersionChanged(
+			Parachain(1).into(),
+			VERSION,
+		));
+		assert!(relay_chain::System::events().iter().any(|record| record.event == changed));
+	});
+}
+
+#[test]
+fn subscription_must_come_from_the_chain_itself() {
+	MockNet::reset();
+
+	send_from_para_b(vec![
+		DescendOrigin(X1(AccountKey20 { network: NetworkId::Any, key: ALICE })),
+		SubscribeVersion { query_id: 0, max_response_weight: 0 },
+	]);
+
+	ParaA::execute_with(|| assert_rejected_with(XcmError::Barrier));
+}
+
+#[test]
+fn relay_token_teleported_from_the_relay_chain_is_rejected() {
+	MockNet::reset();
+
+	// The relay token is only reserve-backed, so minting it on a teleport would inflate it.
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmPallet::limited_teleport_assets(
+			relay_chain::Origin::signed(RELAY_ALICE),
+			Box::new(Parachain(1).into()),
+			Box::new(bob().into()),
+			Box::new(MultiAssets::from(MultiAsset::from((Here, AMOUNT))).into()),
+			0,
+			Unlimited,
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			last_msg_queue_event(),
+			Some(parachain::mock_msg_queue::Event::ExecutedDownward(
+				_,
+				Outcome::Error(XcmError::WeightNotComputable)
+			))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), 0);
+	});
+}
+
+#[test]
+fn teleports_from_siblings_are_rejected() {
+	// Neither the relay token nor the native token of the sibling are teleported to us.
+	let assets: [MultiAsset; 2] = [(Parent, AMOUNT).into(), (para(2), AMOUNT).into()];
+	for asset in assets {
+		MockNet::reset();
+
+		send_from_para_b(vec![
+			ReceiveTeleportedAsset(MultiAssets::from(vec![asset.clone()])),
+			ClearOrigin,
+			BuyExecution { fees: asset, weight_limit: Unlimited },
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: bob() },
+		]);
+
+		ParaA::execute_with(|| {
+			assert_rejected_with(XcmError::WeightNotComputable);
+			assert_eq!(parachain::Balances::free_balance(&BOB.into()), 0);
+		});
+	}
+}
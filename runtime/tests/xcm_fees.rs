@@ -0,0 +1,149 @@
+//! Tests for paying XCM execution in the relay token and in foreign assets, on the XCM simulator.
+
+mod xcm_mock;
+use xcm_mock::*;
+
+use diora_runtime::XcmFeesAccount;
+use frame_support::{
+	assert_ok,
+	traits::Get,
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
+};
+use pallet_asset_manager::AssetMetadata;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+const AMOUNT: u128 = 1_000_000_000_000;
+const ASSET_ID: u32 = 1;
+
+/// Token of parachain B, held in reserve there.
+fn para_b_token() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(2), GeneralIndex(0)))
+}
+
+fn bob() -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key: BOB }.into()
+}
+
+/// Registers the token of parachain B on parachain A, priced at `units_per_second`.
+fn register_para_b_token(units_per_second: Option<u128>) {
+	ParaA::execute_with(|| {
+		assert_ok!(parachain::AssetManager::register_asset(
+			parachain::Origin::root(),
+			ASSET_ID,
+			Box::new(para_b_token().into()),
+			AssetMetadata { name: b"B".to_vec(), symbol: b"B".to_vec(), decimals: 12 },
+			1,
+			true,
+		));
+		if let Some(units_per_second) = units_per_second {
+			assert_ok!(parachain::AssetManager::set_asset_units_per_second(
+				parachain::Origin::root(),
+				ASSET_ID,
+				units_per_second
+			));
+		}
+	});
+}
+
+/// Sends `AMOUNT` of the token of parachain B to Bob on parachain A, paying for execution with it.
+/// Returns the weight of the message on parachain A.
+fn send_para_b_token(instructions: Vec<Instruction<()>>) -> Weight {
+	let asset: MultiAsset = (para_b_token(), AMOUNT).into();
+	let mut message = vec![
+		ReserveAssetDeposited(asset.clone().into()),
+		BuyExecution { fees: asset, weight_limit: Unlimited },
+	];
+	message.extend(instructions);
+	message.push(DepositAsset { assets: All.into(), max_assets: 1, beneficiary: bob() });
+	let weight = parachain_weight(Xcm(message.clone()));
+
+	ParaB::execute_with(|| {
+		assert_ok!(<parachain::XcmRouter as SendXcm>::send_xcm(
+			(Parent, Parachain(1)),
+			Xcm(message)
+		));
+	});
+	weight
+}
+
+fn para_a_assets_of(who: parachain::AccountId) -> u128 {
+	parachain::Assets::balance(ASSET_ID, who)
+}
+
+#[test]
+fn execution_is_paid_in_relay_token() {
+	MockNet::reset();
+
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmPallet::limited_reserve_transfer_assets(
+			relay_chain::Origin::signed(RELAY_ALICE),
+			Box::new(Parachain(1).into()),
+			Box::new(bob().into()),
+			Box::new(MultiAssets::from(MultiAsset::from((Here, AMOUNT))).into()),
+			0,
+			Unlimited,
+		));
+	});
+
+	ParaA::execute_with(|| {
+		// `IdentityFee` for the four instructions of the transfer.
+		let relay_token: MultiAsset = (Parent, AMOUNT).into();
+		let weight =
+			transfer_message_weight(ReserveAssetDeposited(relay_token.clone().into()), relay_token);
+		let fee = weight as u128;
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), AMOUNT - fee);
+		assert_eq!(parachain::Balances::free_balance(&XcmFeesAccount::get()), fee);
+	});
+}
+
+#[test]
+fn execution_is_paid_in_registered_asset() {
+	MockNet::reset();
+	// Two units of the token per unit of weight.
+	register_para_b_token(Some(2 * WEIGHT_PER_SECOND as u128));
+
+	let weight = send_para_b_token(vec![]);
+
+	ParaA::execute_with(|| {
+		let fee = 2 * weight as u128;
+		assert_eq!(para_a_assets_of(BOB.into()), AMOUNT - fee);
+		assert_eq!(para_a_assets_of(XcmFeesAccount::get()), fee);
+		// Nothing was paid in the relay token.
+		assert_eq!(parachain::Balances::free_balance(&XcmFeesAccount::get()), 0);
+	});
+}
+
+#[test]
+fn execution_is_not_paid_in_unpriced_asset() {
+	MockNet::reset();
+	register_para_b_token(None);
+
+	send_para_b_token(vec![]);
+
+	ParaA::execute_with(|| {
+		assert_eq!(para_a_assets_of(BOB.into()), 0);
+		assert_eq!(para_a_assets_of(XcmFeesAccount::get()), 0);
+	});
+}
+
+#[test]
+fn unused_weight_is_refunded_in_the_asset_paid_with() {
+	MockNet::reset();
+	register_para_b_token(Some(2 * WEIGHT_PER_SECOND as u128));
+
+	// Replacing an error handler credits the weight of the previous one as surplus.
+	let weight = send_para_b_token(vec![
+		SetErrorHandler(Xcm(vec![ClearOrigin])),
+		SetErrorHandler(Xcm(vec![])),
+		RefundSurplus,
+	]);
+
+	ParaA::execute_with(|| {
+		// The whole message is bought, including the replaced handler, and the weight of the
+		// handler is refunded.
+		let fee = 2 * (weight - parachain_weight(Xcm(vec![ClearOrigin]))) as u128;
+		assert_eq!(para_a_assets_of(BOB.into()), AMOUNT - fee);
+		assert_eq!(para_a_assets_of(XcmFeesAccount::get()), fee);
+	});
+}
@@ -0,0 +1,381 @@
+//! Tests for the XCM configuration of Diora on the XCM simulator: downward and horizontal messages,
+//! `Transact`, asset trapping and claims, and the barrier.
+
+mod xcm_mock;
+use xcm_mock::*;
+
+use codec::Encode;
+use frame_support::{
+	assert_ok,
+	traits::{fungibles::Mutate, Currency},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
+};
+use pallet_asset_manager::AssetMetadata;
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_simulator::TestExt;
+
+const AMOUNT: u128 = 1_000_000_000_000;
+const MAX_WEIGHT: Weight = 10_000_000_000;
+
+fn account_key20(key: [u8; 20]) -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key }.into()
+}
+
+fn relay_token(amount: u128) -> MultiAsset {
+	(Parent, amount).into()
+}
+
+fn msg_queue_events() -> Vec<parachain::mock_msg_queue::Event<parachain::Runtime>> {
+	parachain::System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			parachain::Event::MsgQueue(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+fn last_xcm_pallet_event() -> Option<pallet_xcm::Event<parachain::Runtime>> {
+	parachain::System::events().into_iter().rev().find_map(|record| match record.event {
+		parachain::Event::PolkadotXcm(event) => Some(event),
+		_ => None,
+	})
+}
+
+/// Sends `message` from parachain B to parachain A over HRMP.
+fn send_from_para_b(message: Vec<Instruction<()>>) {
+	ParaB::execute_with(|| {
+		assert_ok!(<parachain::XcmRouter as SendXcm>::send_xcm(
+			(Parent, Parachain(1)),
+			Xcm(message)
+		));
+	});
+}
+
+fn transfer_to_bob() -> Instruction<()> {
+	Transact {
+		origin_type: OriginKind::SovereignAccount,
+		require_weight_at_most: 1_000_000_000,
+		call: parachain::Call::Balances(pallet_balances::Call::transfer {
+			dest: BOB.into(),
+			value: AMOUNT,
+		})
+		.encode()
+		.into(),
+	}
+}
+
+#[test]
+fn dmp_reserve_transfer_credits_beneficiary() {
+	MockNet::reset();
+
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmPallet::limited_reserve_transfer_assets(
+			relay_chain::Origin::signed(RELAY_ALICE),
+			Box::new(Parachain(1).into()),
+			Box::new(account_key20(BOB).into()),
+			Box::new(MultiAssets::from(MultiAsset::from((Here, AMOUNT))).into()),
+			0,
+			Unlimited,
+		));
+		assert_eq!(
+			relay_chain::Balances::free_balance(&para_account_id(1)),
+			INITIAL_BALANCE + AMOUNT
+		);
+	});
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			msg_queue_events().last(),
+			Some(parachain::mock_msg_queue::Event::ExecutedDownward(_, Outcome::Complete(_)))
+		));
+		let bob = parachain::Balances::free_balance(&BOB.into());
+		assert!(bob > 0 && bob < AMOUNT);
+	});
+}
+
+#[test]
+fn dmp_transact_as_superuser_is_unpaid() {
+	MockNet::reset();
+
+	Relay::execute_with(|| {
+		let set_balance = parachain::Call::Balances(pallet_balances::Call::set_balance {
+			who: BOB.into(),
+			new_free: AMOUNT,
+			new_reserved: 0,
+		});
+		assert_ok!(<relay_chain::XcmRouter as SendXcm>::send_xcm(
+			Parachain(1),
+			Xcm(vec![Transact {
+				origin_type: OriginKind::Superuser,
+				require_weight_at_most: 1_000_000_000,
+				call: set_balance.encode().into(),
+			}]),
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), AMOUNT);
+	});
+}
+
+#[test]
+fn sibling_transacts_as_its_sovereign_account() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		parachain::Balances::make_free_balance_be(&sibling_account_id(2), INITIAL_BALANCE);
+	});
+
+	send_from_para_b(vec![
+		WithdrawAsset(relay_token(AMOUNT).into()),
+		BuyExecution { fees: relay_token(AMOUNT), weight_limit: Unlimited },
+		transfer_to_bob(),
+		RefundSurplus,
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: MultiLocation::new(1, X1(Parachain(2))),
+		},
+	]);
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			msg_queue_events().last(),
+			Some(parachain::mock_msg_queue::Event::Success(_))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), AMOUNT);
+		// The transfer and the execution are paid by the sovereign account of parachain B.
+		let sovereign = parachain::Balances::free_balance(&sibling_account_id(2));
+		assert!(sovereign < INITIAL_BALANCE - AMOUNT);
+		assert!(sovereign > INITIAL_BALANCE - 2 * AMOUNT);
+	});
+}
+
+#[test]
+fn hrmp_transfers_sibling_token_and_back() {
+	MockNet::reset();
+	let units_per_second = WEIGHT_PER_SECOND as u128;
+
+	// Parachain B registers its own token, parachain A the same token as a foreign asset.
+	ParaB::execute_with(|| {
+		let location = MultiLocation::new(0, X1(GeneralIndex(0)));
+		assert_ok!(parachain::AssetManager::register_asset(
+			parachain::Origin::root(),
+			1,
+			Box::new(location.into()),
+			AssetMetadata { name: b"B".to_vec(), symbol: b"B".to_vec(), decimals: 12 },
+			1,
+			true,
+		));
+		assert_ok!(parachain::AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			1,
+			units_per_second
+		));
+		assert_ok!(parachain::Assets::mint_into(1, &ALICE.into(), INITIAL_BALANCE));
+	});
+	ParaA::execute_with(|| {
+		let location = MultiLocation::new(1, X2(Parachain(2), GeneralIndex(0)));
+		assert_ok!(parachain::AssetManager::register_asset(
+			parachain::Origin::root(),
+			1,
+			Box::new(location.into()),
+			AssetMetadata { name: b"B".to_vec(), symbol: b"B".to_vec(), decimals: 12 },
+			1,
+			true,
+		));
+		assert_ok!(parachain::AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			1,
+			units_per_second
+		));
+	});
+
+	ParaB::execute_with(|| {
+		assert_ok!(parachain::PolkadotXcm::limited_reserve_transfer_assets(
+			parachain::Origin::signed(ALICE.into()),
+			Box::new(MultiLocation::new(1, X1(Parachain(1))).into()),
+			Box::new(account_key20(BOB).into()),
+			Box::new(MultiAssets::from(MultiAsset::from((GeneralIndex(0), AMOUNT))).into()),
+			0,
+			Unlimited,
+		));
+		assert_eq!(parachain::Assets::balance(1, sibling_account_id(1)), AMOUNT);
+	});
+
+	// One unit per unit of weight of the received message.
+	let received: MultiAsset =
+		(MultiLocation::new(1, X2(Parachain(2), GeneralIndex(0))), AMOUNT).into();
+	let fee = transfer_message_weight(ReserveAssetDeposited(received.clone().into()), received);
+	let fee = fee as u128;
+	let withdrawn: MultiAsset = (GeneralIndex(0), AMOUNT / 2).into();
+	let back_fee = transfer_message_weight(WithdrawAsset(withdrawn.clone().into()), withdrawn);
+	let back_fee = back_fee as u128;
+	ParaA::execute_with(|| {
+		assert_eq!(parachain::Assets::balance(1, parachain::AccountId::from(BOB)), AMOUNT - fee);
+
+		// Bob sends half of it back to Alice, withdrawing it from the reserve on parachain B.
+		let back = AMOUNT / 2;
+		let token = MultiLocation::new(1, X2(Parachain(2), GeneralIndex(0)));
+		let message = Xcm(vec![
+			WithdrawAsset((token, back).into()),
+			InitiateReserveWithdraw {
+				assets: All.into(),
+				reserve: MultiLocation::new(1, X1(Parachain(2))),
+				xcm: Xcm(vec![
+					BuyExecution { fees: (GeneralIndex(0), back).into(), weight_limit: Unlimited },
+					DepositAsset {
+						assets: All.into(),
+						max_assets: 1,
+						beneficiary: account_key20(ALICE),
+					},
+				]),
+			},
+		]);
+		assert_ok!(parachain::PolkadotXcm::execute(
+			parachain::Origin::signed(BOB.into()),
+			Box::new(VersionedXcm::V2(message)),
+			MAX_WEIGHT,
+		));
+		assert_eq!(
+			parachain::Assets::balance(1, parachain::AccountId::from(BOB)),
+			AMOUNT - fee - back
+		);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(parachain::Assets::balance(1, sibling_account_id(1)), AMOUNT / 2);
+		assert_eq!(
+			parachain::Assets::balance(1, parachain::AccountId::from(ALICE)),
+			INITIAL_BALANCE - AMOUNT / 2 - back_fee
+		);
+	});
+}
+
+#[test]
+fn trapped_assets_are_claimed_by_their_origin() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		// Assets withdrawn and never deposited are trapped.
+		assert_ok!(parachain::PolkadotXcm::execute(
+			parachain::Origin::signed(ALICE.into()),
+			Box::new(VersionedXcm::V2(Xcm(vec![WithdrawAsset(relay_token(AMOUNT).into())]))),
+			MAX_WEIGHT,
+		));
+		assert!(parachain::System::events().iter().any(|record| matches!(
+			record.event,
+			parachain::Event::PolkadotXcm(pallet_xcm::Event::AssetsTrapped(..))
+		)));
+		assert_eq!(parachain::Balances::free_balance(&ALICE.into()), INITIAL_BALANCE - AMOUNT);
+
+		let claim = || {
+			Box::new(VersionedXcm::V2(Xcm(vec![
+				ClaimAsset { assets: relay_token(AMOUNT).into(), ticket: Here.into() },
+				DepositAsset { assets: All.into(), max_assets: 1, beneficiary: account_key20(BOB) },
+			])))
+		};
+
+		// Only the origin the assets were trapped for may claim them.
+		assert_ok!(parachain::PolkadotXcm::execute(
+			parachain::Origin::signed(BOB.into()),
+			claim(),
+			MAX_WEIGHT,
+		));
+		assert!(matches!(
+			last_xcm_pallet_event(),
+			Some(pallet_xcm::Event::Attempted(Outcome::Incomplete(_, XcmError::UnknownClaim)))
+		));
+
+		assert_ok!(parachain::PolkadotXcm::execute(
+			parachain::Origin::signed(ALICE.into()),
+			claim(),
+			MAX_WEIGHT,
+		));
+		assert!(matches!(
+			last_xcm_pallet_event(),
+			Some(pallet_xcm::Event::Attempted(Outcome::Complete(_)))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), AMOUNT);
+
+		// Claims are single use.
+		assert_ok!(parachain::PolkadotXcm::execute(
+			parachain::Origin::signed(ALICE.into()),
+			claim(),
+			MAX_WEIGHT,
+		));
+		assert!(matches!(
+			last_xcm_pallet_event(),
+			Some(pallet_xcm::Event::Attempted(Outcome::Incomplete(_, XcmError::UnknownClaim)))
+		));
+	});
+}
+
+#[test]
+fn barrier_rejects_unpaid_execution_from_sibling() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		parachain::Balances::make_free_balance_be(&sibling_account_id(2), INITIAL_BALANCE);
+	});
+
+	send_from_para_b(vec![transfer_to_bob()]);
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			msg_queue_events().last(),
+			Some(parachain::mock_msg_queue::Event::Fail(_, XcmError::Barrier))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), 0);
+	});
+}
+
+#[test]
+fn barrier_rejects_execution_bought_for_too_little_weight() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		parachain::Balances::make_free_balance_be(&sibling_account_id(2), INITIAL_BALANCE);
+	});
+
+	send_from_para_b(vec![
+		WithdrawAsset(relay_token(AMOUNT).into()),
+		BuyExecution { fees: relay_token(AMOUNT), weight_limit: Limited(1) },
+		transfer_to_bob(),
+	]);
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			msg_queue_events().last(),
+			Some(parachain::mock_msg_queue::Event::Fail(_, XcmError::Barrier))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), 0);
+		assert_eq!(parachain::Balances::free_balance(&sibling_account_id(2)), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn relay_chain_accounts_have_no_origin() {
+	MockNet::reset();
+
+	// The relay chain executes for free, but its accounts are not converted to a local origin.
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmPallet::send(
+			relay_chain::Origin::signed(RELAY_ALICE),
+			Box::new(Parachain(1).into()),
+			Box::new(VersionedXcm::V2(Xcm(vec![transfer_to_bob()]))),
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert!(matches!(
+			msg_queue_events().last(),
+			Some(parachain::mock_msg_queue::Event::ExecutedDownward(
+				_,
+				Outcome::Incomplete(_, XcmError::BadOrigin)
+			))
+		));
+		assert_eq!(parachain::Balances::free_balance(&BOB.into()), 0);
+	});
+}
@@ -0,0 +1,138 @@
+//! XCM simulator network: a relay chain and two parachains with the XCM configuration of Diora.
+
+#![allow(dead_code)]
+
+pub mod parachain;
+pub mod relay_chain;
+
+use frame_support::{traits::GenesisBuild, weights::Weight};
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
+use sp_runtime::traits::AccountIdConversion;
+use xcm_executor::traits::WeightBounds;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub const ALICE: [u8; 20] = [4u8; 20];
+pub const BOB: [u8; 20] = [5u8; 20];
+pub const RELAY_ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([4u8; 32]);
+pub const RELAY_BOB: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([5u8; 32]);
+
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000_000;
+
+decl_test_parachain! {
+	pub struct ParaA {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(1),
+	}
+}
+
+decl_test_parachain! {
+	pub struct ParaB {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(2),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		XcmConfig = relay_chain::XcmConfig,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(1, ParaA),
+			(2, ParaB),
+		],
+	}
+}
+
+/// Sovereign account of a parachain on the relay chain.
+pub fn para_account_id(id: u32) -> relay_chain::AccountId {
+	ParaId::from(id).into_account()
+}
+
+/// Sovereign account of a sibling parachain on a parachain.
+pub fn sibling_account_id(id: u32) -> parachain::AccountId {
+	Sibling::from(id).into_account()
+}
+
+/// Weight the parachains charge for executing `message`.
+pub fn parachain_weight(message: xcm::latest::Xcm<()>) -> Weight {
+	let mut message = xcm::latest::Xcm::<parachain::Call>::from(message);
+	parachain::XcmWeigher::weight(&mut message).expect("message is weighable")
+}
+
+/// Weight the parachains charge for the message a transfer is received with: `received` puts the
+/// assets in holding, `fees` pays for the execution and the rest is deposited to the beneficiary.
+pub fn transfer_message_weight(
+	received: xcm::latest::Instruction<()>,
+	fees: xcm::latest::MultiAsset,
+) -> Weight {
+	use xcm::latest::prelude::*;
+
+	parachain_weight(Xcm(vec![
+		received,
+		ClearOrigin,
+		BuyExecution { fees, weight_limit: Unlimited },
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Here.into() },
+	]))
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+	use parachain::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE.into(), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	<pallet_xcm::GenesisConfig as GenesisBuild<Runtime>>::assimilate_storage(
+		&pallet_xcm::GenesisConfig {
+			safe_xcm_version: Some(2),
+		},
+		&mut t,
+	)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(para_id.into());
+	});
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(RELAY_ALICE, INITIAL_BALANCE),
+			(para_account_id(1), INITIAL_BALANCE),
+			(para_account_id(2), INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
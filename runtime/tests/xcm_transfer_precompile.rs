@@ -0,0 +1,281 @@
+//! Tests for the XCM transfer precompile, on the XCM simulator.
+
+mod xcm_mock;
+use xcm_mock::*;
+
+use diora_runtime::DioraGasWeightMapping;
+use fp_evm::{ExitError, ExitReason, ExitSucceed};
+use pallet_evm::{CallInfo, GasWeightMapping, Runner};
+use sp_core::{H160, U256};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::WeightBounds;
+use xcm_simulator::TestExt;
+
+const GAS_LIMIT: u64 = 1_000_000;
+const AMOUNT: u128 = 1_000_000_000_000;
+
+const TRANSFER_RESERVE: [u8; 4] = [0xd6, 0xdf, 0x14, 0xa2];
+const TRANSFER_TELEPORT: [u8; 4] = [0xf7, 0xd5, 0xa0, 0x9d];
+
+fn word(value: U256) -> Vec<u8> {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	word.to_vec()
+}
+
+fn encode_bytes_array(elements: Vec<Vec<u8>>) -> Vec<u8> {
+	let mut heads = Vec::new();
+	let mut tails = Vec::new();
+	let mut offset = 32 * elements.len();
+	for data in &elements {
+		heads.extend(word(offset.into()));
+		let mut tail = word(data.len().into());
+		tail.extend(data);
+		tail.resize(32 + (data.len() + 31) / 32 * 32, 0);
+		offset += tail.len();
+		tails.extend(tail);
+	}
+
+	let mut encoded = word(elements.len().into());
+	encoded.extend(heads);
+	encoded.extend(tails);
+	encoded
+}
+
+/// ABI encoding of a `Multilocation` struct.
+fn location(parents: u8, junctions: Vec<Vec<u8>>) -> Vec<u8> {
+	let mut encoded = word(parents.into());
+	encoded.extend(word(64.into()));
+	encoded.extend(encode_bytes_array(junctions));
+	encoded
+}
+
+fn parachain(id: u32) -> Vec<u8> {
+	let mut encoded = vec![0x00];
+	encoded.extend(id.to_be_bytes());
+	encoded
+}
+
+fn account_id32(id: [u8; 32]) -> Vec<u8> {
+	let mut encoded = vec![0x01];
+	encoded.extend(id);
+	encoded.push(0x00);
+	encoded
+}
+
+fn account_key20(key: [u8; 20]) -> Vec<u8> {
+	let mut encoded = vec![0x03];
+	encoded.extend(key);
+	encoded.push(0x00);
+	encoded
+}
+
+fn relay_token() -> Vec<u8> {
+	location(1, vec![])
+}
+
+fn encode_transfer(
+	selector: [u8; 4],
+	asset: Vec<u8>,
+	amount: u128,
+	destination: Vec<u8>,
+	weight: u64,
+) -> Vec<u8> {
+	let mut input = selector.to_vec();
+	input.extend(word(128.into()));
+	input.extend(word(amount.into()));
+	input.extend(word((128 + asset.len()).into()));
+	input.extend(word(weight.into()));
+	input.extend(asset);
+	input.extend(destination);
+	input
+}
+
+fn call_precompile(input: Vec<u8>, gas_limit: u64) -> CallInfo {
+	<parachain::Runtime as pallet_evm::Config>::Runner::call(
+		H160(ALICE),
+		H160::from_low_u64_be(parachain::XCM_TRANSFER),
+		input,
+		U256::zero(),
+		gas_limit,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false,
+		<parachain::Runtime as pallet_evm::Config>::config(),
+	)
+	.expect("runner accepts the call")
+}
+
+fn teleport_to_relay_bob() -> Vec<u8> {
+	encode_transfer(
+		TRANSFER_TELEPORT,
+		relay_token(),
+		AMOUNT,
+		location(1, vec![account_id32(RELAY_BOB.into())]),
+		0,
+	)
+}
+
+fn reverted_with(info: &CallInfo, message: &str) -> bool {
+	matches!(info.exit_reason, ExitReason::Revert(_)) && info.value == message.as_bytes()
+}
+
+#[test]
+fn teleport_to_relay_chain_credits_beneficiary() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		let info = call_precompile(teleport_to_relay_bob(), GAS_LIMIT);
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(
+			parachain::Balances::free_balance(&ALICE.into()),
+			INITIAL_BALANCE - AMOUNT
+		);
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(relay_chain::Balances::free_balance(&RELAY_BOB), AMOUNT);
+	});
+}
+
+#[test]
+fn reserve_transfer_to_sibling_moves_funds_to_its_sovereign_account() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		let input = encode_transfer(
+			TRANSFER_RESERVE,
+			relay_token(),
+			AMOUNT,
+			location(1, vec![parachain(2), account_key20(BOB)]),
+			4_000_000_000,
+		);
+		let info = call_precompile(input, GAS_LIMIT);
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(
+			parachain::Balances::free_balance(&ALICE.into()),
+			INITIAL_BALANCE - AMOUNT
+		);
+		assert_eq!(parachain::Balances::free_balance(&sibling_account_id(2)), AMOUNT);
+	});
+}
+
+#[test]
+fn gas_covers_the_weight_of_the_transfer() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		// The weight of the local message, given by the XCM weigher.
+		let beneficiary = AccountId32 { network: NetworkId::Any, id: RELAY_BOB.into() };
+		let mut message = Xcm::<parachain::Call>(vec![
+			WithdrawAsset((MultiLocation::parent(), AMOUNT).into()),
+			InitiateTeleport {
+				assets: Wild(All),
+				dest: MultiLocation::parent(),
+				xcm: Xcm(vec![
+					BuyExecution { fees: (Here, AMOUNT).into(), weight_limit: Unlimited },
+					DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						beneficiary: beneficiary.into(),
+					},
+				]),
+			},
+		]);
+		let weight = <parachain::Runtime as pallet_xcm::Config>::Weigher::weight(&mut message)
+			.expect("message is weighable");
+		let transfer_gas = DioraGasWeightMapping::weight_to_gas(weight);
+
+		let info = call_precompile(teleport_to_relay_bob(), GAS_LIMIT);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert!(info.used_gas > U256::from(transfer_gas));
+
+		// Not enough gas is left for the transfer once the intrinsic cost is paid.
+		let info = call_precompile(teleport_to_relay_bob(), transfer_gas);
+		assert_eq!(info.exit_reason, ExitReason::Error(ExitError::OutOfGas));
+		assert_eq!(
+			parachain::Balances::free_balance(&ALICE.into()),
+			INITIAL_BALANCE - AMOUNT
+		);
+	});
+}
+
+#[test]
+fn gas_does_not_depend_on_the_events_of_the_block() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		let used_gas = call_precompile(teleport_to_relay_bob(), GAS_LIMIT).used_gas;
+
+		for _ in 0..1_000 {
+			let event = frame_system::Event::<parachain::Runtime>::CodeUpdated;
+			parachain::System::deposit_event(event);
+		}
+		let info = call_precompile(teleport_to_relay_bob(), GAS_LIMIT);
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.used_gas, used_gas);
+	});
+}
+
+#[test]
+fn invalid_locations_are_rejected() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		let no_beneficiary =
+			encode_transfer(TRANSFER_TELEPORT, relay_token(), AMOUNT, location(1, vec![]), 0);
+		let info = call_precompile(no_beneficiary, GAS_LIMIT);
+		assert!(reverted_with(&info, "destination has no beneficiary"));
+
+		let unknown_junction = encode_transfer(
+			TRANSFER_TELEPORT,
+			relay_token(),
+			AMOUNT,
+			location(1, vec![vec![0x42]]),
+			0,
+		);
+		let info = call_precompile(unknown_junction, GAS_LIMIT);
+		assert!(reverted_with(&info, "invalid junction"));
+
+		let unknown_network = encode_transfer(
+			TRANSFER_TELEPORT,
+			relay_token(),
+			AMOUNT,
+			location(1, vec![[account_key20(BOB), vec![0x04]].concat()]),
+			0,
+		);
+		let info = call_precompile(unknown_network, GAS_LIMIT);
+		assert!(reverted_with(&info, "invalid network"));
+
+		assert_eq!(parachain::Balances::free_balance(&ALICE.into()), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn failed_transfers_revert() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		// More than Alice owns.
+		let input = encode_transfer(
+			TRANSFER_TELEPORT,
+			relay_token(),
+			INITIAL_BALANCE + 1,
+			location(1, vec![account_id32(RELAY_BOB.into())]),
+			0,
+		);
+		let info = call_precompile(input, GAS_LIMIT);
+
+		assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+		assert_eq!(parachain::Balances::free_balance(&ALICE.into()), INITIAL_BALANCE);
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(relay_chain::Balances::free_balance(&RELAY_BOB), 0);
+	});
+}
@@ -0,0 +1,169 @@
+//! Tests for the weights the XCM weigher charges for typical transfer programs.
+
+use codec::Encode;
+use diora_runtime::{Call, DioraXcmWeight, MaxAssetsIntoHolding, Runtime, XcmWeigher};
+use frame_support::{traits::Get, weights::Weight};
+use xcm::latest::{prelude::*, XcmWeightInfo};
+use xcm_executor::traits::WeightBounds;
+
+/// The expectations are stated in terms of the per-instruction weights rather than their values,
+/// so that they hold whatever the benchmarks measure.
+type Weights = DioraXcmWeight<Runtime, Call>;
+type DbWeight = <Runtime as frame_system::Config>::DbWeight;
+
+const AMOUNT: u128 = 1_000_000_000_000;
+/// Weight a `Transact` requires for its call.
+const CALL_WEIGHT: Weight = 1_000_000_000;
+
+fn weigh(message: Vec<Instruction<Call>>) -> Result<Weight, ()> {
+	XcmWeigher::weight(&mut Xcm(message))
+}
+
+fn relay_token() -> MultiAsset {
+	(Parent, AMOUNT).into()
+}
+
+fn sibling_token() -> MultiAsset {
+	(MultiLocation::new(1, X2(Parachain(2000), GeneralIndex(0))), AMOUNT).into()
+}
+
+fn beneficiary() -> MultiLocation {
+	AccountKey20 { network: NetworkId::Any, key: [4u8; 20] }.into()
+}
+
+/// The program a transfer is received with: `received` puts `assets` in holding, the first of
+/// them pays for execution and `last` empties holding.
+fn transfer(
+	received: fn(MultiAssets) -> Instruction<Call>,
+	assets: Vec<MultiAsset>,
+	last: Instruction<Call>,
+) -> Vec<Instruction<Call>> {
+	vec![
+		received(assets.clone().into()),
+		ClearOrigin,
+		BuyExecution { fees: assets[0].clone(), weight_limit: Unlimited },
+		last,
+	]
+}
+
+fn deposit(max_assets: u32) -> Instruction<Call> {
+	DepositAsset { assets: All.into(), max_assets, beneficiary: beneficiary() }
+}
+
+#[test]
+fn transfer_programs_weigh_the_sum_of_their_instructions() {
+	let assets: MultiAssets = relay_token().into();
+	let receives: [(fn(MultiAssets) -> Instruction<Call>, Weight); 2] = [
+		(ReserveAssetDeposited, Weights::reserve_asset_deposited(&assets)),
+		(WithdrawAsset, Weights::withdraw_asset(&assets)),
+	];
+	let rest = Weights::clear_origin() +
+		Weights::buy_execution(&relay_token(), &Unlimited) +
+		Weights::deposit_asset(&All.into(), &1, &beneficiary());
+
+	for (received, weight) in receives {
+		let program = transfer(received, vec![relay_token()], deposit(1));
+		assert_eq!(weigh(program), Ok(weight + rest));
+	}
+}
+
+#[test]
+fn moving_assets_in_and_out_of_accounts_is_charged_its_storage() {
+	let account = DbWeight::get().reads_writes(1, 1);
+	let assets: MultiAssets = relay_token().into();
+
+	assert!(Weights::withdraw_asset(&assets) >= account);
+	assert!(Weights::deposit_asset(&All.into(), &1, &beneficiary()) >= account);
+	// Both the sender and the receiver.
+	assert!(Weights::transfer_asset(&assets, &beneficiary()) >= 2 * account);
+}
+
+#[test]
+fn foreign_assets_weigh_like_the_relay_token() {
+	assert_eq!(
+		weigh(transfer(ReserveAssetDeposited, vec![sibling_token()], deposit(1))),
+		weigh(transfer(ReserveAssetDeposited, vec![relay_token()], deposit(1))),
+	);
+}
+
+#[test]
+fn every_transferred_asset_is_charged() {
+	let one = weigh(transfer(ReserveAssetDeposited, vec![sibling_token()], deposit(1))).unwrap();
+	let two = weigh(transfer(
+		ReserveAssetDeposited,
+		vec![relay_token(), sibling_token()],
+		deposit(2),
+	))
+	.unwrap();
+
+	let received = weigh(vec![ReserveAssetDeposited(relay_token().into())]).unwrap();
+	let deposited = weigh(vec![deposit(1)]).unwrap();
+	assert_eq!(two, one + received + deposited);
+}
+
+#[test]
+fn wildcard_deposit_is_charged_for_its_maximum_of_assets() {
+	let one = weigh(vec![deposit(1)]).unwrap();
+
+	assert_eq!(weigh(vec![deposit(3)]).unwrap(), 3 * one);
+}
+
+#[test]
+fn wildcard_leaving_holding_is_charged_for_the_holding_limit() {
+	let teleport = |assets: MultiAssetFilter| InitiateTeleport {
+		assets,
+		dest: MultiLocation::parent(),
+		xcm: Xcm(vec![]),
+	};
+	let one = weigh(vec![teleport(relay_token().into())]).unwrap();
+
+	assert_eq!(
+		weigh(vec![teleport(All.into())]).unwrap(),
+		MaxAssetsIntoHolding::get() as Weight * one
+	);
+	// All of a fungible asset is a single asset.
+	let all_of = Wild(AllOf { id: Concrete(MultiLocation::parent()), fun: WildFungible });
+	assert_eq!(weigh(vec![teleport(all_of)]).unwrap(), one);
+}
+
+#[test]
+fn forwarding_a_transfer_weighs_more_than_depositing_it() {
+	let forward = DepositReserveAsset {
+		assets: All.into(),
+		max_assets: 1,
+		dest: MultiLocation::new(1, X1(Parachain(2000))),
+		xcm: Xcm(vec![
+			BuyExecution { fees: relay_token(), weight_limit: Unlimited },
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: beneficiary() },
+		]),
+	};
+
+	let deposited = weigh(transfer(ReserveAssetDeposited, vec![relay_token()], deposit(1)));
+	let forwarded = weigh(transfer(ReserveAssetDeposited, vec![relay_token()], forward));
+	assert!(forwarded.unwrap() > deposited.unwrap());
+}
+
+#[test]
+fn transact_is_charged_its_overhead_on_top_of_the_call() {
+	let transact = |require_weight_at_most| Transact {
+		origin_type: OriginKind::SovereignAccount,
+		require_weight_at_most,
+		call: Call::System(frame_system::Call::remark { remark: vec![] }).encode().into(),
+	};
+
+	let overhead = weigh(vec![transact(0)]).unwrap();
+	assert!(overhead > 0);
+	assert_eq!(weigh(vec![transact(CALL_WEIGHT)]).unwrap(), overhead + CALL_WEIGHT);
+}
+
+#[test]
+fn unsupported_programs_are_not_weighable() {
+	let abstract_asset: MultiAsset = (Abstract(b"TOKEN".to_vec()), AMOUNT).into();
+	let non_fungible: MultiAsset = (Parent, Index(1)).into();
+
+	assert!(weigh(transfer(ReserveAssetDeposited, vec![abstract_asset], deposit(1))).is_err());
+	assert!(weigh(transfer(ReserveAssetDeposited, vec![non_fungible], deposit(1))).is_err());
+	assert!(weigh(vec![ClearOrigin, HrmpChannelAccepted { recipient: 2000 }]).is_err());
+	// No asset is teleported to us.
+	assert!(weigh(transfer(ReceiveTeleportedAsset, vec![relay_token()], deposit(1))).is_err());
+}
@@ -0,0 +1,54 @@
+#!/bin/bash
+# Regenerates the runtime weight files under runtime/src/weights.
+# The node has to be built with `cargo build --release --features runtime-benchmarks` first.
+
+set -e
+
+cd "$(dirname "$0")/.."
+
+DIORA=./target/release/diora
+STEPS=${STEPS:-50}
+REPEAT=${REPEAT:-20}
+
+# Pallets whose weights are used by the runtime configuration.
+WEIGHTED_PALLETS=(
+	cumulus_pallet_xcmp_queue
+	pallet_author_inherent
+	pallet_author_slot_filter
+)
+
+# Benchmarks of the XCM instructions, weighed by the XCM weigher of the runtime.
+XCM_PALLETS=(
+	fungible
+	generic
+)
+
+# `pallet_account_set` and `pallet_evm` are benchmarked by the runtime but have no weight file:
+# the author set is only read through the author inherent, whose `kick_off_authorship_validation`
+# benchmark covers the lookup, and EVM calls are charged by gas rather than by weight. Either can
+# be checked by hand with `$DIORA benchmark pallet --pallet=<pallet> --extrinsic='*'`.
+
+for pallet in "${WEIGHTED_PALLETS[@]}"; do
+	$DIORA benchmark pallet \
+		--chain=rococo \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--pallet="$pallet" \
+		--extrinsic='*' \
+		--steps="$STEPS" \
+		--repeat="$REPEAT" \
+		--output="./runtime/src/weights/$pallet.rs"
+done
+
+for pallet in "${XCM_PALLETS[@]}"; do
+	$DIORA benchmark pallet \
+		--chain=rococo \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--pallet="pallet_xcm_benchmarks::$pallet" \
+		--extrinsic='*' \
+		--steps="$STEPS" \
+		--repeat="$REPEAT" \
+		--template=./script/xcm-weight-template.hbs \
+		--output="./runtime/src/weights/xcm/pallet_xcm_benchmarks_$pallet.rs"
+done